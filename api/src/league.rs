@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Roster {
+    pub league_id: i64,
+    pub league_name: String,
+    pub tier: i64,
+    pub marble_ids: Vec<i64>,
+}
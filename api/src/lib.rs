@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
 
+pub mod league;
 pub mod marble;
 pub mod race;
+pub mod rating;
+pub mod season;
+pub mod team;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct RegisterRequest {
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{fmt::Display, path::PathBuf, str::FromStr};
 
 use palette::Srgba;
 use serde::{Deserialize, Serialize};
@@ -15,6 +15,7 @@ pub struct Marble {
     pub sound_path: PathBuf,
     pub maybe_image_path: Option<PathBuf>,
     pub active: bool,
+    pub pronouns: Pronouns,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -28,4 +29,64 @@ pub struct WriteMarble {
     pub sound_path: PathBuf,
     pub maybe_image_path: Option<PathBuf>,
     pub active: bool,
+    pub pronouns: Pronouns,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Pronouns {
+    pub subject: String,
+    pub object: String,
+    pub possessive: String,
+}
+
+impl Default for Pronouns {
+    fn default() -> Self {
+        Self {
+            subject: String::from("it"),
+            object: String::from("it"),
+            possessive: String::from("its"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RivalryKind {
+    Opposites,
+    Grudge,
+}
+
+impl Display for RivalryKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Opposites => write!(f, "opposites"),
+            Self::Grudge => write!(f, "grudge"),
+        }
+    }
+}
+
+impl FromStr for RivalryKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "opposites" => Ok(Self::Opposites),
+            "grudge" => Ok(Self::Grudge),
+            kind => Err(format!("Unknown rivalry kind {kind:?}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Rivalry {
+    pub marble_id: i64,
+    pub rival_id: i64,
+    pub kind: RivalryKind,
+}
+
+impl Rivalry {
+    pub fn involves(&self, marble_id: i64, other_id: i64) -> bool {
+        (self.marble_id == marble_id && self.rival_id == other_id)
+            || (self.marble_id == other_id && self.rival_id == marble_id)
+    }
 }
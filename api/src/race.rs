@@ -7,4 +7,5 @@ pub struct Race {
     pub id: i64,
     pub level_id: i64,
     pub time: Option<DateTime<Utc>>,
+    pub maybe_league_id: Option<i64>,
 }
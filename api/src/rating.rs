@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Rating {
+    pub marble_id: i64,
+    pub maybe_league_id: Option<i64>,
+    pub rating: f64,
+    pub deviation: f64,
+    pub volatility: f64,
+    pub races: i64,
+}
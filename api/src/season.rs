@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::team::TeamStanding;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Season {
+    pub id: i64,
+    pub league_id: i64,
+    pub name: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Standing {
+    pub marble_id: i64,
+    pub points: i64,
+    pub races: i64,
+    pub wins: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SeasonStandings {
+    pub season: Season,
+    pub points: Vec<i64>,
+    pub standings: Vec<Standing>,
+    pub team_standings: Vec<TeamStanding>,
+}
@@ -0,0 +1,20 @@
+use palette::Srgba;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Team {
+    pub id: i64,
+    pub name: String,
+    #[serde(with = "palette::serde::as_array")]
+    pub color: Srgba,
+    pub marble_ids: Vec<i64>,
+}
+
+/// A team's championship standing, from the points its marbles scored.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamStanding {
+    pub team_id: i64,
+    pub points: i64,
+    pub races: i64,
+    pub wins: i64,
+}
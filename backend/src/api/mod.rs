@@ -1,3 +1,8 @@
 pub mod marbles;
+pub mod ratings;
 pub mod register;
+pub mod rivalries;
+pub mod rosters;
+pub mod standings;
+pub mod teams;
 pub mod token;
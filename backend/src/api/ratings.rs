@@ -0,0 +1,17 @@
+use api::rating::Rating;
+use axum::{Json, extract::State, http::StatusCode};
+use database::rating::DbRating;
+
+use crate::{AppState, util::internal_server_error};
+
+pub async fn get_ratings(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<Rating>>, (StatusCode, String)> {
+    let db_ratings = DbRating::get_all(&state.pool)
+        .await
+        .map_err(internal_server_error)?;
+
+    let ratings = db_ratings.into_iter().map(Rating::from).collect();
+
+    Ok(Json(ratings))
+}
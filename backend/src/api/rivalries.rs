@@ -0,0 +1,17 @@
+use api::marble::Rivalry;
+use axum::{Json, extract::State, http::StatusCode};
+use database::rivalry::DbRivalry;
+
+use crate::{AppState, util::internal_server_error};
+
+pub async fn get_rivalries(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<Rivalry>>, (StatusCode, String)> {
+    let db_rivalries = DbRivalry::get_all(&state.pool)
+        .await
+        .map_err(internal_server_error)?;
+
+    let rivalries = db_rivalries.into_iter().map(Rivalry::from).collect();
+
+    Ok(Json(rivalries))
+}
@@ -0,0 +1,50 @@
+use api::league::Roster;
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Utc};
+use database::league::DbLeague;
+use serde::Deserialize;
+
+use crate::{AppState, util::internal_server_error};
+
+#[derive(Deserialize)]
+pub struct RosterQuery {
+    /// Defaults to now.
+    time: Option<DateTime<Utc>>,
+}
+
+/// Which marbles were in each league at the given time.
+pub async fn get_rosters(
+    State(state): State<AppState>,
+    Query(query): Query<RosterQuery>,
+) -> Result<Json<Vec<Roster>>, (StatusCode, String)> {
+    let time = query.time.unwrap_or_else(Utc::now);
+
+    let db_leagues = DbLeague::get_all(&state.pool)
+        .await
+        .map_err(internal_server_error)?;
+
+    let mut rosters = Vec::new();
+
+    for db_league in db_leagues {
+        let marble_ids = db_league
+            .get_roster_at(&state.pool, time)
+            .await
+            .map_err(internal_server_error)?
+            .into_iter()
+            .map(|db_marble| db_marble.id)
+            .collect();
+
+        rosters.push(Roster {
+            league_id: db_league.id,
+            league_name: db_league.name,
+            tier: db_league.tier,
+            marble_ids,
+        });
+    }
+
+    Ok(Json(rosters))
+}
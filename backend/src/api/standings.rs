@@ -0,0 +1,51 @@
+use api::{
+    season::{Season, SeasonStandings, Standing},
+    team::TeamStanding,
+};
+use axum::{Json, extract::State, http::StatusCode};
+use chrono::Utc;
+use database::season::DbSeason;
+
+use crate::{AppState, util::internal_server_error};
+
+pub async fn get_standings(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<SeasonStandings>>, (StatusCode, String)> {
+    let db_seasons = DbSeason::get_all_current(&state.pool, Utc::now())
+        .await
+        .map_err(internal_server_error)?;
+
+    let mut season_standings = Vec::new();
+
+    for db_season in db_seasons {
+        let points = db_season
+            .get_points(&state.pool)
+            .await
+            .map_err(internal_server_error)?;
+
+        let standings = db_season
+            .get_standings(&state.pool)
+            .await
+            .map_err(internal_server_error)?
+            .into_iter()
+            .map(Standing::from)
+            .collect();
+
+        let team_standings = db_season
+            .get_team_standings(&state.pool)
+            .await
+            .map_err(internal_server_error)?
+            .into_iter()
+            .map(TeamStanding::from)
+            .collect();
+
+        season_standings.push(SeasonStandings {
+            season: Season::from(db_season),
+            points,
+            standings,
+            team_standings,
+        });
+    }
+
+    Ok(Json(season_standings))
+}
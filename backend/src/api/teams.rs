@@ -0,0 +1,34 @@
+use api::team::Team;
+use axum::{Json, extract::State, http::StatusCode};
+use database::team::DbTeam;
+
+use crate::{AppState, util::internal_server_error};
+
+pub async fn get_teams(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<Team>>, (StatusCode, String)> {
+    let db_teams = DbTeam::get_all(&state.pool)
+        .await
+        .map_err(internal_server_error)?;
+
+    let mut teams = Vec::new();
+
+    for db_team in db_teams {
+        let marble_ids = db_team
+            .get_marbles(&state.pool)
+            .await
+            .map_err(internal_server_error)?
+            .into_iter()
+            .map(|db_marble| db_marble.id)
+            .collect();
+
+        teams.push(Team {
+            id: db_team.id,
+            color: db_team.get_color(),
+            name: db_team.name,
+            marble_ids,
+        });
+    }
+
+    Ok(Json(teams))
+}
@@ -13,7 +13,10 @@ use sqlx::SqlitePool;
 use tokio::net::{TcpListener, UnixListener};
 use tower_http::{cors::CorsLayer, services::ServeDir};
 
-use crate::api::{marbles::get_marbles, register::post_register, token::post_token};
+use crate::api::{
+    marbles::get_marbles, ratings::get_ratings, register::post_register, rivalries::get_rivalries,
+    rosters::get_rosters, standings::get_standings, teams::get_teams, token::post_token,
+};
 
 pub mod api;
 pub mod util;
@@ -86,6 +89,11 @@ where
 
     let app = axum::Router::new()
         .route("/marbles", get(get_marbles))
+        .route("/ratings", get(get_ratings))
+        .route("/rivalries", get(get_rivalries))
+        .route("/rosters", get(get_rosters))
+        .route("/standings", get(get_standings))
+        .route("/teams", get(get_teams))
         .route("/register", post(post_register))
         .route("/token", post(post_token))
         .nest_service("/images", ServeDir::new("ball_images"))
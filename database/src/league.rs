@@ -0,0 +1,137 @@
+use chrono::{DateTime, Utc};
+use sqlx::{SqlitePool, query, query_as};
+
+use crate::marble::DbMarble;
+
+#[derive(Debug, Clone)]
+pub struct DbLeague {
+    pub id: i64,
+    pub name: String,
+    /// 1 is the top league. Marbles are promoted to the tier above and relegated to the one below.
+    pub tier: i64,
+}
+
+/// A spell a marble spent in a league. Rows seeded before memberships were dated have no
+/// `joined_time`, and the current membership has no `left_time`.
+#[derive(Debug, Clone, Copy)]
+pub struct DbLeagueMarble {
+    pub league_id: i64,
+    pub marble_id: i64,
+    pub joined_time: Option<i64>,
+    pub left_time: Option<i64>,
+}
+
+/// A marble moving from one league straight into another.
+#[derive(Debug, Clone)]
+pub struct DbLeagueMove {
+    pub marble_id: i64,
+    pub from_league_id: i64,
+    pub from_tier: i64,
+    pub to_league_id: i64,
+    pub to_league_name: String,
+    pub to_tier: i64,
+    pub time: i64,
+}
+
+impl DbLeagueMove {
+    pub fn is_promotion(&self) -> bool {
+        self.to_tier < self.from_tier
+    }
+}
+
+impl DbLeague {
+    /// Every league, top tier first.
+    pub async fn get_all(pool: &SqlitePool) -> sqlx::Result<Vec<Self>> {
+        query_as!(Self, "SELECT * FROM league ORDER BY tier ASC")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get_by_name(pool: &SqlitePool, name: &str) -> sqlx::Result<Option<Self>> {
+        query_as!(Self, "SELECT * FROM league WHERE name = ?", name)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// The marbles that were in the league at `time`.
+    pub async fn get_roster_at(
+        &self,
+        pool: &SqlitePool,
+        time: DateTime<Utc>,
+    ) -> sqlx::Result<Vec<DbMarble>> {
+        let time = time.timestamp();
+
+        query_as!(
+            DbMarble,
+            "SELECT marble.* FROM marble INNER JOIN league_marble ON marble.id = league_marble.marble_id WHERE league_marble.league_id = ? AND (league_marble.joined_time IS NULL OR league_marble.joined_time <= ?) AND (league_marble.left_time IS NULL OR ? < league_marble.left_time)",
+            self.id,
+            time,
+            time,
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
+
+impl DbLeagueMarble {
+    /// Ends the marble's current membership and starts a new one in `league_id`, both at `time`.
+    pub async fn transfer(
+        pool: &SqlitePool,
+        marble_id: i64,
+        league_id: i64,
+        time: DateTime<Utc>,
+    ) -> sqlx::Result<Self> {
+        let time = time.timestamp();
+
+        query!(
+            "UPDATE league_marble SET left_time = ? WHERE marble_id = ? AND left_time IS NULL",
+            time,
+            marble_id,
+        )
+        .execute(pool)
+        .await?;
+
+        query_as!(
+            Self,
+            "INSERT INTO league_marble VALUES (?, ?, ?, NULL) RETURNING *",
+            league_id,
+            marble_id,
+            time,
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Every promotion and relegation at or after `time`, most recent first.
+    pub async fn get_moves_since(
+        pool: &SqlitePool,
+        time: DateTime<Utc>,
+    ) -> sqlx::Result<Vec<DbLeagueMove>> {
+        let time = time.timestamp();
+
+        query_as!(
+            DbLeagueMove,
+            r#"SELECT
+                spell.marble_id AS "marble_id!",
+                previous_league.id AS "from_league_id!",
+                previous_league.tier AS "from_tier!",
+                league.id AS "to_league_id!",
+                league.name AS "to_league_name!",
+                league.tier AS "to_tier!",
+                spell.joined_time AS "time!: i64"
+            FROM league_marble AS spell
+            INNER JOIN league_marble AS previous_spell
+            ON previous_spell.marble_id = spell.marble_id
+            AND previous_spell.left_time = spell.joined_time
+            INNER JOIN league
+            ON league.id = spell.league_id
+            INNER JOIN league AS previous_league
+            ON previous_league.id = previous_spell.league_id
+            WHERE spell.joined_time >= ?
+            ORDER BY spell.joined_time DESC"#,
+            time,
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -1,4 +1,13 @@
+pub mod league;
 pub mod marble;
 pub mod race;
+pub mod race_handicap;
 pub mod race_marble;
+pub mod race_powerup;
+pub mod race_split;
+pub mod rating;
+pub mod rivalry;
+pub mod season;
+pub mod team;
+pub mod tournament;
 pub mod user;
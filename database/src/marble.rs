@@ -3,9 +3,12 @@ use std::path::PathBuf;
 use palette::Srgba;
 use sqlx::{SqlitePool, query_as};
 
-use api::marble::{Marble, WriteMarble};
+use api::marble::{Marble, Pronouns, WriteMarble};
 
-use crate::race_marble::DbRaceMarble;
+use crate::{
+    race_marble::{DbMarbleResult, DbRaceMarble},
+    rivalry::DbRivalry,
+};
 
 #[derive(Debug, Clone)]
 pub struct DbMarble {
@@ -20,6 +23,9 @@ pub struct DbMarble {
     pub sound: String,
     pub maybe_image: Option<String>,
     pub active: i64,
+    pub subject_pronoun: String,
+    pub object_pronoun: String,
+    pub possessive_pronoun: String,
 }
 
 impl DbMarble {
@@ -42,7 +48,7 @@ impl DbMarble {
     }
 
     pub async fn get_by_league(pool: &SqlitePool, league_name: &str) -> sqlx::Result<Vec<Self>> {
-        query_as!(Self, "SELECT marble.* FROM marble INNER JOIN league_marble ON marble.id = league_marble.marble_id INNER JOIN league ON league_marble.league_id = league.id WHERE league.name = ?", league_name)
+        query_as!(Self, "SELECT marble.* FROM marble INNER JOIN league_marble ON marble.id = league_marble.marble_id INNER JOIN league ON league_marble.league_id = league.id WHERE league.name = ? AND league_marble.left_time IS NULL", league_name)
             .fetch_all(pool)
             .await
     }
@@ -60,7 +66,7 @@ impl DbMarble {
         query_as!(
             Self,
             r#"INSERT INTO marble
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT DO UPDATE SET
             name = ?,
             red = ?,
@@ -71,7 +77,10 @@ impl DbMarble {
             elasticity = ?,
             sound = ?,
             maybe_image = ?,
-            active = ?
+            active = ?,
+            subject_pronoun = ?,
+            object_pronoun = ?,
+            possessive_pronoun = ?
             RETURNING *"#,
             id,
             write_marble.name,
@@ -84,6 +93,9 @@ impl DbMarble {
             sound_path_str,
             maybe_image_path_string,
             write_marble.active,
+            write_marble.pronouns.subject,
+            write_marble.pronouns.object,
+            write_marble.pronouns.possessive,
             write_marble.name,
             write_marble.color.red,
             write_marble.color.green,
@@ -94,6 +106,9 @@ impl DbMarble {
             sound_path_str,
             maybe_image_path_string,
             write_marble.active,
+            write_marble.pronouns.subject,
+            write_marble.pronouns.object,
+            write_marble.pronouns.possessive,
         )
         .fetch_one(pool)
         .await
@@ -117,6 +132,14 @@ impl DbMarble {
     pub async fn get_race_marbles(&self, pool: &SqlitePool) -> sqlx::Result<Vec<DbRaceMarble>> {
         DbRaceMarble::get_by_marble_id(pool, self.id).await
     }
+
+    pub async fn get_results(&self, pool: &SqlitePool) -> sqlx::Result<Vec<DbMarbleResult>> {
+        DbRaceMarble::get_results_by_marble_id(pool, self.id).await
+    }
+
+    pub async fn get_rivalries(&self, pool: &SqlitePool) -> sqlx::Result<Vec<DbRivalry>> {
+        DbRivalry::get_by_marble_id(pool, self.id).await
+    }
 }
 
 impl From<DbMarble> for Marble {
@@ -131,6 +154,11 @@ impl From<DbMarble> for Marble {
             sound_path: PathBuf::from(value.sound),
             maybe_image_path: value.maybe_image.map(PathBuf::from),
             active: value.active > 0,
+            pronouns: Pronouns {
+                subject: value.subject_pronoun,
+                object: value.object_pronoun,
+                possessive: value.possessive_pronoun,
+            },
         }
     }
 }
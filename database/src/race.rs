@@ -1,14 +1,18 @@
 use api::race::Race;
 use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
-use sqlx::{SqlitePool, query_as};
+use sqlx::{SqlitePool, query, query_as};
 
-use crate::race_marble::DbRaceMarble;
+use crate::{
+    race_handicap::DbRaceHandicap, race_marble::DbRaceMarble, race_powerup::DbRacePowerup,
+    race_split::DbRaceSplit,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub struct DbRace {
     pub id: i64,
     pub level_id: i64,
     pub time: Option<i64>,
+    pub league_id: Option<i64>,
 }
 
 impl DbRace {
@@ -18,6 +22,13 @@ impl DbRace {
             .await
     }
 
+    /// Every race, oldest first.
+    pub async fn get_all(pool: &SqlitePool) -> sqlx::Result<Vec<Self>> {
+        query_as!(Self, "SELECT * FROM race ORDER BY id ASC")
+            .fetch_all(pool)
+            .await
+    }
+
     pub async fn get_by_date(pool: &SqlitePool, date: NaiveDate) -> sqlx::Result<Vec<Self>> {
         let date_string = date.format("%F").to_string();
 
@@ -26,15 +37,16 @@ impl DbRace {
             .await
     }
 
-    pub async fn insert(pool: &SqlitePool, id: i64, level_id: i64, time: DateTime<Utc>) -> sqlx::Result<Self> {
+    pub async fn insert(pool: &SqlitePool, id: i64, level_id: i64, maybe_league_id: Option<i64>, time: DateTime<Utc>) -> sqlx::Result<Self> {
         let time = time.timestamp();
 
         query_as!(
             Self,
-            "INSERT INTO race VALUES (?, ?, ?) RETURNING *",
+            "INSERT INTO race VALUES (?, ?, ?, ?) RETURNING *",
             id,
             level_id,
             time,
+            maybe_league_id,
         )
         .fetch_one(pool)
         .await
@@ -53,6 +65,76 @@ impl DbRace {
     pub async fn get_marbles(&self, pool: &SqlitePool) -> sqlx::Result<Vec<DbRaceMarble>> {
         DbRaceMarble::get_by_race_id(pool, self.id).await
     }
+
+    /// The league every marble in the race was in when it was run, for races recorded before races
+    /// kept their league.
+    pub async fn get_roster_league_id(&self, pool: &SqlitePool) -> sqlx::Result<Option<i64>> {
+        let maybe_row = query!(
+            r#"SELECT league_marble.league_id AS "league_id!"
+            FROM race_marble
+            INNER JOIN race
+            ON race.id = race_marble.race_id
+            INNER JOIN league_marble
+            ON league_marble.marble_id = race_marble.marble_id
+            AND (race.time IS NULL OR league_marble.joined_time IS NULL OR league_marble.joined_time <= race.time)
+            AND (race.time IS NULL OR league_marble.left_time IS NULL OR race.time < league_marble.left_time)
+            WHERE race_marble.race_id = ?
+            GROUP BY league_marble.league_id
+            HAVING COUNT(DISTINCT race_marble.marble_id) = (SELECT COUNT(*) FROM race_marble WHERE race_id = ?)"#,
+            self.id,
+            self.id,
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(maybe_row.map(|row| row.league_id))
+    }
+
+    pub async fn insert_split(
+        &self,
+        pool: &SqlitePool,
+        marble_id: i64,
+        lap: i64,
+        checkpoint: i64,
+        time: TimeDelta,
+    ) -> sqlx::Result<DbRaceSplit> {
+        DbRaceSplit::insert(pool, self.id, marble_id, lap, checkpoint, time).await
+    }
+
+    pub async fn get_splits(&self, pool: &SqlitePool) -> sqlx::Result<Vec<DbRaceSplit>> {
+        DbRaceSplit::get_by_race_id(pool, self.id).await
+    }
+
+    pub async fn insert_handicap(
+        &self,
+        pool: &SqlitePool,
+        marble_id: i64,
+        radius: f64,
+        density: f64,
+        start_delay: f64,
+    ) -> sqlx::Result<DbRaceHandicap> {
+        DbRaceHandicap::insert(pool, self.id, marble_id, radius, density, start_delay).await
+    }
+
+    pub async fn get_handicaps(&self, pool: &SqlitePool) -> sqlx::Result<Vec<DbRaceHandicap>> {
+        DbRaceHandicap::get_by_race_id(pool, self.id).await
+    }
+
+    pub async fn insert_powerup(
+        &self,
+        pool: &SqlitePool,
+        kind: &str,
+        rarity: Option<&str>,
+        x: f64,
+        y: f64,
+        time: f64,
+    ) -> sqlx::Result<DbRacePowerup> {
+        DbRacePowerup::insert(pool, self.id, kind, rarity, x, y, time).await
+    }
+
+    pub async fn get_powerups(&self, pool: &SqlitePool) -> sqlx::Result<Vec<DbRacePowerup>> {
+        DbRacePowerup::get_by_race_id(pool, self.id).await
+    }
 }
 
 impl From<DbRace> for Race {
@@ -60,7 +142,8 @@ impl From<DbRace> for Race {
         Race {
             id: value.id,
             level_id: value.level_id,
-            time: value.time.map(|time| DateTime::from_timestamp_secs(time)).flatten()
+            time: value.time.map(|time| DateTime::from_timestamp_secs(time)).flatten(),
+            maybe_league_id: value.league_id,
         }
     }
 }
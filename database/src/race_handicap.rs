@@ -0,0 +1,45 @@
+use sqlx::{SqlitePool, query_as};
+
+/// A penalty or boost a marble raced with. `radius` is added to the marble's radius, `density`
+/// multiplies its density and `start_delay` is how many seconds it was held at the start.
+#[derive(Debug, Clone, Copy)]
+pub struct DbRaceHandicap {
+    pub race_id: i64,
+    pub marble_id: i64,
+    pub radius: f64,
+    pub density: f64,
+    pub start_delay: f64,
+}
+
+impl DbRaceHandicap {
+    pub async fn get_by_race_id(pool: &SqlitePool, race_id: i64) -> sqlx::Result<Vec<Self>> {
+        query_as!(
+            Self,
+            "SELECT * FROM race_handicap WHERE race_id = ?",
+            race_id,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn insert(
+        pool: &SqlitePool,
+        race_id: i64,
+        marble_id: i64,
+        radius: f64,
+        density: f64,
+        start_delay: f64,
+    ) -> sqlx::Result<Self> {
+        query_as!(
+            Self,
+            "INSERT INTO race_handicap VALUES (?, ?, ?, ?, ?) RETURNING *",
+            race_id,
+            marble_id,
+            radius,
+            density,
+            start_delay,
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -9,6 +9,34 @@ pub struct DbRaceMarble {
     pub place: i64,
 }
 
+/// One of a marble's finishes, with enough of the rest of the race to put it in context.
+#[derive(Debug, Clone, Copy)]
+pub struct DbMarbleResult {
+    pub race_id: i64,
+    pub level_id: i64,
+    pub race_time: Option<i64>,
+    pub place: i64,
+    pub time: f64,
+    pub maybe_previous_time: Option<f64>,
+    pub maybe_next_time: Option<f64>,
+    pub field_size: i64,
+}
+
+/// How many times a marble has finished ahead of and behind an opponent.
+#[derive(Debug, Clone, Copy)]
+pub struct DbHeadToHead {
+    pub marble_id: i64,
+    pub opponent_id: i64,
+    pub wins: i64,
+    pub losses: i64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DbWinCount {
+    pub marble_id: i64,
+    pub wins: i64,
+}
+
 impl DbRaceMarble {
     pub async fn get_by_race_id(pool: &SqlitePool, race_id: i64) -> sqlx::Result<Vec<Self>> {
         query_as!(
@@ -30,6 +58,88 @@ impl DbRaceMarble {
         .await
     }
 
+    /// A marble's most recent finishes, newest first.
+    pub async fn get_recent_by_marble_id(
+        pool: &SqlitePool,
+        marble_id: i64,
+        limit: i64,
+    ) -> sqlx::Result<Vec<Self>> {
+        query_as!(
+            Self,
+            "SELECT * FROM race_marble WHERE marble_id = ? ORDER BY race_id DESC LIMIT ?",
+            marble_id,
+            limit,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Every finish of a marble, oldest first.
+    pub async fn get_results_by_marble_id(
+        pool: &SqlitePool,
+        marble_id: i64,
+    ) -> sqlx::Result<Vec<DbMarbleResult>> {
+        query_as!(
+            DbMarbleResult,
+            r#"SELECT
+                race.id AS "race_id!",
+                race.level_id AS "level_id!",
+                race.time AS race_time,
+                result.place AS "place!",
+                result.time AS "time!: f64",
+                result.previous_time AS "maybe_previous_time: f64",
+                result.next_time AS "maybe_next_time: f64",
+                result.field_size AS "field_size!: i64"
+            FROM (
+                SELECT
+                    race_id,
+                    marble_id,
+                    place,
+                    time,
+                    LAG(time) OVER (PARTITION BY race_id ORDER BY place) AS previous_time,
+                    LEAD(time) OVER (PARTITION BY race_id ORDER BY place) AS next_time,
+                    COUNT(*) OVER (PARTITION BY race_id) AS field_size
+                FROM race_marble
+            ) AS result
+            INNER JOIN race
+            ON race.id = result.race_id
+            WHERE result.marble_id = ?
+            ORDER BY race.id ASC"#,
+            marble_id,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The head-to-head record of every pair of marbles that have raced each other, both ways
+    /// round.
+    pub async fn get_head_to_heads(pool: &SqlitePool) -> sqlx::Result<Vec<DbHeadToHead>> {
+        query_as!(
+            DbHeadToHead,
+            r#"SELECT
+                marble.marble_id AS "marble_id!",
+                opponent.marble_id AS "opponent_id!",
+                SUM(marble.place < opponent.place) AS "wins!: i64",
+                SUM(marble.place > opponent.place) AS "losses!: i64"
+            FROM race_marble AS marble
+            INNER JOIN race_marble AS opponent
+            ON marble.race_id = opponent.race_id
+            AND marble.marble_id != opponent.marble_id
+            GROUP BY marble.marble_id, opponent.marble_id"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get_win_counts(pool: &SqlitePool) -> sqlx::Result<Vec<DbWinCount>> {
+        query_as!(
+            DbWinCount,
+            r#"SELECT marble_id, COUNT(*) AS "wins!: i64" FROM race_marble WHERE place = 1 GROUP BY marble_id"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     pub async fn insert(
         pool: &SqlitePool,
         race_id: i64,
@@ -0,0 +1,49 @@
+use sqlx::{SqlitePool, query_as};
+
+/// A powerup that was drawn for a race, where it appeared and how many seconds into the race.
+/// Powerups drawn before the start have a time of 0.
+#[derive(Debug, Clone)]
+pub struct DbRacePowerup {
+    pub race_id: i64,
+    pub kind: String,
+    pub rarity: Option<String>,
+    pub x: f64,
+    pub y: f64,
+    pub time: f64,
+}
+
+impl DbRacePowerup {
+    /// Every powerup drawn for the race, in the order they appeared.
+    pub async fn get_by_race_id(pool: &SqlitePool, race_id: i64) -> sqlx::Result<Vec<Self>> {
+        query_as!(
+            Self,
+            "SELECT * FROM race_powerup WHERE race_id = ? ORDER BY time ASC",
+            race_id,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn insert(
+        pool: &SqlitePool,
+        race_id: i64,
+        kind: &str,
+        rarity: Option<&str>,
+        x: f64,
+        y: f64,
+        time: f64,
+    ) -> sqlx::Result<Self> {
+        query_as!(
+            Self,
+            "INSERT INTO race_powerup VALUES (?, ?, ?, ?, ?, ?) RETURNING *",
+            race_id,
+            kind,
+            rarity,
+            x,
+            y,
+            time,
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
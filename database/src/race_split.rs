@@ -0,0 +1,48 @@
+use chrono::TimeDelta;
+use sqlx::{SqlitePool, query_as};
+
+/// When a marble crossed a checkpoint, measured from the start of the race.
+#[derive(Debug, Clone, Copy)]
+pub struct DbRaceSplit {
+    pub race_id: i64,
+    pub marble_id: i64,
+    pub lap: i64,
+    pub checkpoint: i64,
+    pub time: f64,
+}
+
+impl DbRaceSplit {
+    /// Every split of the race, in the order they happened.
+    pub async fn get_by_race_id(pool: &SqlitePool, race_id: i64) -> sqlx::Result<Vec<Self>> {
+        query_as!(
+            Self,
+            "SELECT * FROM race_split WHERE race_id = ? ORDER BY time ASC",
+            race_id,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn insert(
+        pool: &SqlitePool,
+        race_id: i64,
+        marble_id: i64,
+        lap: i64,
+        checkpoint: i64,
+        time: TimeDelta,
+    ) -> sqlx::Result<Self> {
+        let seconds = time.as_seconds_f64();
+
+        query_as!(
+            Self,
+            "INSERT INTO race_split VALUES (?, ?, ?, ?, ?) RETURNING *",
+            race_id,
+            marble_id,
+            lap,
+            checkpoint,
+            seconds,
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -0,0 +1,92 @@
+use api::rating::Rating;
+use sqlx::{SqlitePool, query_as};
+
+#[derive(Debug, Clone, Copy)]
+pub struct DbRating {
+    pub id: i64,
+    pub marble_id: i64,
+    pub league_id: Option<i64>,
+    pub rating: f64,
+    pub deviation: f64,
+    pub volatility: f64,
+    pub races: i64,
+}
+
+impl DbRating {
+    pub async fn get_all(pool: &SqlitePool) -> sqlx::Result<Vec<Self>> {
+        query_as!(Self, "SELECT * FROM rating")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get_by_marble_id(pool: &SqlitePool, marble_id: i64) -> sqlx::Result<Vec<Self>> {
+        query_as!(Self, "SELECT * FROM rating WHERE marble_id = ?", marble_id)
+            .fetch_all(pool)
+            .await
+    }
+
+    /// A `maybe_league_id` of `None` is the rating from races that weren't run in a league.
+    pub async fn get(
+        pool: &SqlitePool,
+        marble_id: i64,
+        maybe_league_id: Option<i64>,
+    ) -> sqlx::Result<Option<Self>> {
+        query_as!(
+            Self,
+            "SELECT * FROM rating WHERE marble_id = ? AND league_id IS ?",
+            marble_id,
+            maybe_league_id,
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        marble_id: i64,
+        maybe_league_id: Option<i64>,
+        rating: f64,
+        deviation: f64,
+        volatility: f64,
+        races: i64,
+    ) -> sqlx::Result<Self> {
+        query_as!(
+            Self,
+            r#"INSERT INTO rating (marble_id, league_id, rating, deviation, volatility, races)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT (marble_id, IFNULL(league_id, 0)) DO UPDATE SET
+            rating = excluded.rating,
+            deviation = excluded.deviation,
+            volatility = excluded.volatility,
+            races = excluded.races
+            RETURNING *"#,
+            marble_id,
+            maybe_league_id,
+            rating,
+            deviation,
+            volatility,
+            races,
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete_all(pool: &SqlitePool) -> sqlx::Result<()> {
+        sqlx::query!("DELETE FROM rating").execute(pool).await?;
+
+        Ok(())
+    }
+}
+
+impl From<DbRating> for Rating {
+    fn from(value: DbRating) -> Self {
+        Rating {
+            marble_id: value.marble_id,
+            maybe_league_id: value.league_id,
+            rating: value.rating,
+            deviation: value.deviation,
+            volatility: value.volatility,
+            races: value.races,
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use api::marble::Rivalry;
+use sqlx::{SqlitePool, query_as};
+
+#[derive(Debug, Clone)]
+pub struct DbRivalry {
+    pub marble_id: i64,
+    pub rival_id: i64,
+    pub kind: String,
+}
+
+impl DbRivalry {
+    pub async fn get_all(pool: &SqlitePool) -> sqlx::Result<Vec<Self>> {
+        query_as!(Self, "SELECT * FROM rivalry")
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn get_by_marble_id(pool: &SqlitePool, marble_id: i64) -> sqlx::Result<Vec<Self>> {
+        query_as!(
+            Self,
+            "SELECT * FROM rivalry WHERE marble_id = ? OR rival_id = ?",
+            marble_id,
+            marble_id,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        marble_id: i64,
+        rival_id: i64,
+        kind: &str,
+    ) -> sqlx::Result<Self> {
+        query_as!(
+            Self,
+            "INSERT INTO rivalry VALUES (?, ?, ?) ON CONFLICT DO UPDATE SET kind = ? RETURNING *",
+            marble_id,
+            rival_id,
+            kind,
+            kind,
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
+
+impl From<DbRivalry> for Rivalry {
+    fn from(value: DbRivalry) -> Self {
+        Rivalry {
+            marble_id: value.marble_id,
+            rival_id: value.rival_id,
+            kind: value
+                .kind
+                .parse()
+                .expect("rivalry kind is constrained by the table"),
+        }
+    }
+}
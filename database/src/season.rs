@@ -0,0 +1,239 @@
+use api::season::{Season, Standing};
+use chrono::{DateTime, Utc};
+use sqlx::{SqlitePool, query, query_as};
+
+use crate::team::DbTeamStanding;
+
+#[derive(Debug, Clone)]
+pub struct DbSeason {
+    pub id: i64,
+    pub league_id: i64,
+    pub name: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    /// Whether promotions and relegations have been handed out for this season.
+    pub settled: i64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DbSeasonPoints {
+    pub season_id: i64,
+    pub place: i64,
+    pub points: i64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DbStanding {
+    pub marble_id: i64,
+    pub points: i64,
+    pub races: i64,
+    pub wins: i64,
+}
+
+impl DbSeason {
+    pub async fn get_by_id(pool: &SqlitePool, id: i64) -> sqlx::Result<Option<Self>> {
+        query_as!(Self, "SELECT * FROM season WHERE id = ?", id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Every season that is running at `time`, one per league at most.
+    pub async fn get_all_current(
+        pool: &SqlitePool,
+        time: DateTime<Utc>,
+    ) -> sqlx::Result<Vec<Self>> {
+        let time = time.timestamp();
+
+        query_as!(
+            Self,
+            "SELECT * FROM season WHERE start_time <= ? AND ? < end_time",
+            time,
+            time,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn get_current(
+        pool: &SqlitePool,
+        league_id: i64,
+        time: DateTime<Utc>,
+    ) -> sqlx::Result<Option<Self>> {
+        let time = time.timestamp();
+
+        query_as!(
+            Self,
+            "SELECT * FROM season WHERE league_id = ? AND start_time <= ? AND ? < end_time",
+            league_id,
+            time,
+            time,
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Seasons of the league that are over at `time`, most recently ended first.
+    pub async fn get_ended_by_league_id(
+        pool: &SqlitePool,
+        league_id: i64,
+        time: DateTime<Utc>,
+    ) -> sqlx::Result<Vec<Self>> {
+        let time = time.timestamp();
+
+        query_as!(
+            Self,
+            "SELECT * FROM season WHERE league_id = ? AND end_time <= ? ORDER BY end_time DESC",
+            league_id,
+            time,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Creates a season, where `points[0]` is awarded for first place, `points[1]` for second and
+    /// so on. Places past the end of `points` score nothing.
+    pub async fn insert(
+        pool: &SqlitePool,
+        league_id: i64,
+        name: &str,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        points: &[i64],
+    ) -> sqlx::Result<Self> {
+        let start_time = start_time.timestamp();
+        let end_time = end_time.timestamp();
+
+        let season = query_as!(
+            Self,
+            "INSERT INTO season (league_id, name, start_time, end_time) VALUES (?, ?, ?, ?) RETURNING *",
+            league_id,
+            name,
+            start_time,
+            end_time,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        for (index, points) in points.iter().enumerate() {
+            let place = (index + 1) as i64;
+
+            query_as!(
+                DbSeasonPoints,
+                "INSERT INTO season_points VALUES (?, ?, ?) RETURNING *",
+                season.id,
+                place,
+                points,
+            )
+            .fetch_one(pool)
+            .await?;
+        }
+
+        Ok(season)
+    }
+
+    pub async fn settle(&mut self, pool: &SqlitePool) -> sqlx::Result<()> {
+        query!("UPDATE season SET settled = TRUE WHERE id = ?", self.id)
+            .execute(pool)
+            .await?;
+
+        self.settled = 1;
+
+        Ok(())
+    }
+
+    /// Points awarded per place, starting with first place.
+    pub async fn get_points(&self, pool: &SqlitePool) -> sqlx::Result<Vec<i64>> {
+        Ok(query_as!(
+            DbSeasonPoints,
+            "SELECT * FROM season_points WHERE season_id = ? ORDER BY place ASC",
+            self.id,
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|season_points| season_points.points)
+        .collect())
+    }
+
+    /// Championship standings from the league's races during the season, leader first.
+    pub async fn get_standings(&self, pool: &SqlitePool) -> sqlx::Result<Vec<DbStanding>> {
+        query_as!(
+            DbStanding,
+            r#"SELECT
+                race_marble.marble_id AS "marble_id!",
+                COALESCE(SUM(season_points.points), 0) AS "points!: i64",
+                COUNT(*) AS "races!: i64",
+                COALESCE(SUM(race_marble.place = 1), 0) AS "wins!: i64"
+            FROM season
+            INNER JOIN race
+            ON race.league_id = season.league_id
+            AND season.start_time <= race.time
+            AND race.time < season.end_time
+            INNER JOIN race_marble
+            ON race_marble.race_id = race.id
+            LEFT JOIN season_points
+            ON season_points.season_id = season.id
+            AND season_points.place = race_marble.place
+            WHERE season.id = ?
+            GROUP BY race_marble.marble_id
+            ORDER BY 2 DESC, 4 DESC"#,
+            self.id,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Team standings for the season, where each team scores the points its marbles scored in the
+    /// league's races. A race counts once per team no matter how many of its marbles were in it.
+    pub async fn get_team_standings(&self, pool: &SqlitePool) -> sqlx::Result<Vec<DbTeamStanding>> {
+        query_as!(
+            DbTeamStanding,
+            r#"SELECT
+                team_marble.team_id AS "team_id!",
+                COALESCE(SUM(season_points.points), 0) AS "points!: i64",
+                COUNT(DISTINCT race.id) AS "races!: i64",
+                COALESCE(SUM(race_marble.place = 1), 0) AS "wins!: i64"
+            FROM season
+            INNER JOIN race
+            ON race.league_id = season.league_id
+            AND season.start_time <= race.time
+            AND race.time < season.end_time
+            INNER JOIN race_marble
+            ON race_marble.race_id = race.id
+            INNER JOIN team_marble
+            ON team_marble.marble_id = race_marble.marble_id
+            LEFT JOIN season_points
+            ON season_points.season_id = season.id
+            AND season_points.place = race_marble.place
+            WHERE season.id = ?
+            GROUP BY team_marble.team_id
+            ORDER BY 2 DESC, 4 DESC"#,
+            self.id,
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
+
+impl From<DbSeason> for Season {
+    fn from(value: DbSeason) -> Self {
+        Season {
+            id: value.id,
+            league_id: value.league_id,
+            name: value.name,
+            start_time: DateTime::from_timestamp_secs(value.start_time).unwrap_or_default(),
+            end_time: DateTime::from_timestamp_secs(value.end_time).unwrap_or_default(),
+        }
+    }
+}
+
+impl From<DbStanding> for Standing {
+    fn from(value: DbStanding) -> Self {
+        Standing {
+            marble_id: value.marble_id,
+            points: value.points,
+            races: value.races,
+            wins: value.wins,
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use api::team::TeamStanding;
+use palette::Srgba;
+use sqlx::{SqlitePool, query_as};
+
+use crate::marble::DbMarble;
+
+#[derive(Debug, Clone)]
+pub struct DbTeam {
+    pub id: i64,
+    pub name: String,
+    pub red: f64,
+    pub green: f64,
+    pub blue: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DbTeamStanding {
+    pub team_id: i64,
+    pub points: i64,
+    pub races: i64,
+    pub wins: i64,
+}
+
+impl DbTeam {
+    pub async fn get_all(pool: &SqlitePool) -> sqlx::Result<Vec<Self>> {
+        query_as!(Self, "SELECT * FROM team").fetch_all(pool).await
+    }
+
+    pub async fn get_by_name(pool: &SqlitePool, name: &str) -> sqlx::Result<Option<Self>> {
+        query_as!(Self, "SELECT * FROM team WHERE name = ?", name)
+            .fetch_optional(pool)
+            .await
+    }
+
+    pub async fn get_marbles(&self, pool: &SqlitePool) -> sqlx::Result<Vec<DbMarble>> {
+        query_as!(
+            DbMarble,
+            "SELECT marble.* FROM marble INNER JOIN team_marble ON marble.id = team_marble.marble_id WHERE team_marble.team_id = ?",
+            self.id,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub fn get_color(&self) -> Srgba {
+        Srgba::new(self.red as f32, self.green as f32, self.blue as f32, 1.0)
+    }
+}
+
+impl From<DbTeamStanding> for TeamStanding {
+    fn from(value: DbTeamStanding) -> Self {
+        TeamStanding {
+            team_id: value.team_id,
+            points: value.points,
+            races: value.races,
+            wins: value.wins,
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use sqlx::{SqlitePool, query_as};
+
+use crate::{marble::DbMarble, race::DbRace, race_marble::DbRaceMarble};
+
+#[derive(Debug, Clone)]
+pub struct DbTournament {
+    pub id: i64,
+    pub league_id: i64,
+    pub name: String,
+    pub heat_size: i64,
+    pub advance: i64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DbTournamentEntry {
+    pub tournament_id: i64,
+    pub marble_id: i64,
+    pub seed: i64,
+}
+
+/// One race of a tournament. `race_id` is set once the heat has been run.
+#[derive(Debug, Clone, Copy)]
+pub struct DbHeat {
+    pub id: i64,
+    pub tournament_id: i64,
+    pub round: i64,
+    pub number: i64,
+    pub race_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DbHeatMarble {
+    pub heat_id: i64,
+    pub marble_id: i64,
+}
+
+impl DbTournament {
+    pub async fn get_by_name(pool: &SqlitePool, name: &str) -> sqlx::Result<Option<Self>> {
+        query_as!(Self, "SELECT * FROM tournament WHERE name = ?", name)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Creates a tournament, where `marble_ids` is the field ordered from first seed to last.
+    pub async fn insert(
+        pool: &SqlitePool,
+        league_id: i64,
+        name: &str,
+        heat_size: i64,
+        advance: i64,
+        marble_ids: &[i64],
+    ) -> sqlx::Result<Self> {
+        let tournament = query_as!(
+            Self,
+            "INSERT INTO tournament (league_id, name, heat_size, advance) VALUES (?, ?, ?, ?) RETURNING *",
+            league_id,
+            name,
+            heat_size,
+            advance,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        for (index, marble_id) in marble_ids.iter().enumerate() {
+            let seed = (index + 1) as i64;
+
+            query_as!(
+                DbTournamentEntry,
+                "INSERT INTO tournament_entry VALUES (?, ?, ?) RETURNING *",
+                tournament.id,
+                marble_id,
+                seed,
+            )
+            .fetch_one(pool)
+            .await?;
+        }
+
+        Ok(tournament)
+    }
+
+    /// The field, first seed first.
+    pub async fn get_entries(&self, pool: &SqlitePool) -> sqlx::Result<Vec<DbTournamentEntry>> {
+        query_as!(
+            DbTournamentEntry,
+            "SELECT * FROM tournament_entry WHERE tournament_id = ? ORDER BY seed ASC",
+            self.id,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Every heat drawn so far, in the order they are run.
+    pub async fn get_heats(&self, pool: &SqlitePool) -> sqlx::Result<Vec<DbHeat>> {
+        query_as!(
+            DbHeat,
+            "SELECT * FROM heat WHERE tournament_id = ? ORDER BY round ASC, number ASC",
+            self.id,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn insert_heat(
+        &self,
+        pool: &SqlitePool,
+        round: i64,
+        number: i64,
+        marble_ids: &[i64],
+    ) -> sqlx::Result<DbHeat> {
+        let heat = query_as!(
+            DbHeat,
+            "INSERT INTO heat (tournament_id, round, number) VALUES (?, ?, ?) RETURNING *",
+            self.id,
+            round,
+            number,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        for marble_id in marble_ids {
+            query_as!(
+                DbHeatMarble,
+                "INSERT INTO heat_marble VALUES (?, ?) RETURNING *",
+                heat.id,
+                marble_id,
+            )
+            .fetch_one(pool)
+            .await?;
+        }
+
+        Ok(heat)
+    }
+}
+
+impl DbHeat {
+    pub async fn get_marbles(&self, pool: &SqlitePool) -> sqlx::Result<Vec<DbMarble>> {
+        query_as!(
+            DbMarble,
+            "SELECT marble.* FROM marble INNER JOIN heat_marble ON marble.id = heat_marble.marble_id WHERE heat_marble.heat_id = ?",
+            self.id,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The finishers of the heat, winner first. Empty if the heat hasn't been run.
+    pub async fn get_results(&self, pool: &SqlitePool) -> sqlx::Result<Vec<DbRaceMarble>> {
+        let Some(race_id) = self.race_id else {
+            return Ok(Vec::new());
+        };
+
+        let mut results = DbRaceMarble::get_by_race_id(pool, race_id).await?;
+        results.sort_by_key(|race_marble| race_marble.place);
+
+        Ok(results)
+    }
+
+    pub async fn set_race(&mut self, pool: &SqlitePool, race: &DbRace) -> sqlx::Result<()> {
+        *self = query_as!(
+            DbHeat,
+            "UPDATE heat SET race_id = ? WHERE id = ? RETURNING *",
+            race.id,
+            self.id,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(())
+    }
+}
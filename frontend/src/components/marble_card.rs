@@ -1,16 +1,25 @@
-use api::marble::Marble;
+use api::{marble::Marble, rating::Rating};
 use yew::prelude::*;
 
 #[derive(Properties, PartialEq)]
 pub struct MarbleCardProps {
     pub marble: Marble,
 
+    #[prop_or_default]
+    pub maybe_rating: Option<Rating>,
+
     #[prop_or_default]
     pub classes: Classes,
 }
 
 #[function_component]
-pub fn MarbleCard(MarbleCardProps { marble, classes }: &MarbleCardProps) -> Html {
+pub fn MarbleCard(
+    MarbleCardProps {
+        marble,
+        maybe_rating,
+        classes,
+    }: &MarbleCardProps,
+) -> Html {
     html! {
         <div
             class={ classes!("border", "flex", "flex-col", "p-4", "rounded-xl", "break-all", classes.clone()) }
@@ -20,6 +29,10 @@ pub fn MarbleCard(MarbleCardProps { marble, classes }: &MarbleCardProps) -> Html
                 <img src={ format!("/api/images/{}", image_path.to_str().unwrap()) } />
             }
             <h1 class="text-xl">{ &marble.name }</h1>
+            <p class="text-sm opacity-75">{ format!("{}/{}", marble.pronouns.subject, marble.pronouns.object) }</p>
+            if let Some(rating) = maybe_rating {
+                <p class="text-sm">{ format!("Rated {:.0} ± {:.0}", rating.rating, rating.deviation * 2.0) }</p>
+            }
         </div>
     }
 }
@@ -1,26 +1,36 @@
-use api::marble::Marble;
+use api::{marble::Marble, rating::Rating};
 use reqwest::Client;
 use yew::prelude::*;
 use yew_nav::use_hide_nav_menu;
 
-use crate::{components::marble_card::MarbleCard, util::get_marbles};
+use crate::{
+    components::marble_card::MarbleCard,
+    util::{get_marbles, get_ratings},
+};
 
 #[function_component]
 pub fn RootPage() -> Html {
     use_hide_nav_menu(());
 
     let marbles_state: UseStateHandle<Option<Vec<Marble>>> = use_state(Option::default);
+    let ratings_state: UseStateHandle<Vec<Rating>> = use_state(Vec::default);
 
     use_effect_with((), {
         let marbles_state = marbles_state.clone();
+        let ratings_state = ratings_state.clone();
 
         move |_| {
             let marbles_state = marbles_state.clone();
+            let ratings_state = ratings_state.clone();
 
             wasm_bindgen_futures::spawn_local(async move {
                 let marbles = get_marbles(Client::new()).await.unwrap();
 
                 marbles_state.set(Some(marbles));
+
+                let ratings = get_ratings(Client::new()).await.unwrap();
+
+                ratings_state.set(ratings);
             });
         }
     });
@@ -30,8 +40,14 @@ pub fn RootPage() -> Html {
             if let Some(marbles) = &*marbles_state {
                 {
                     marbles.iter().map(|marble| {
+                        let maybe_rating = ratings_state
+                            .iter()
+                            .filter(|rating| rating.marble_id == marble.id)
+                            .max_by_key(|rating| rating.races)
+                            .cloned();
+
                         html! {
-                            <MarbleCard marble={marble.clone()} />
+                            <MarbleCard marble={marble.clone()} {maybe_rating} />
                         }
                     }).collect::<Html>()
                 }
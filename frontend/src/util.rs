@@ -1,4 +1,4 @@
-use api::marble::Marble;
+use api::{marble::Marble, rating::Rating};
 use gloo::utils::window;
 use reqwest::Client;
 use yew::prelude::*;
@@ -25,3 +25,12 @@ pub async fn get_marbles(client: Client) -> Result<Vec<Marble>, reqwest::Error>
         .json::<Vec<Marble>>()
         .await
 }
+
+pub async fn get_ratings(client: Client) -> Result<Vec<Rating>, reqwest::Error> {
+    client
+        .get(with_origin("/api/ratings"))
+        .send()
+        .await?
+        .json::<Vec<Rating>>()
+        .await
+}
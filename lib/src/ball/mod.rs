@@ -5,22 +5,26 @@ use std::{
 };
 
 use ::rand::random_range;
-use api::marble::Marble;
+use api::marble::{Marble, Pronouns};
 use glam::{DVec2, dvec2};
 use palette::Srgba;
 use particula_rs::ParticleSystem;
 use render_agnostic::Renderer;
 
+pub mod status_effect;
+
 use crate::{
+    ball::status_effect::{Stat, StatusEffect, StatusEffects},
     drawer::{
         BallStyle, base_style::BaseStyle, glow_style::GlowStyle, ikea_style::IkeaStyle,
-        image_style::ImageStyle, outline_style::OutlineStyle, tail_style::TailStyle,
+        image_style::ImageStyle, outline_style::OutlineStyle, status_layer::render_status_effects,
+        tail_style::TailStyle,
     },
     particle::{
         FireParticle, ParticleLayer, ShrinkingParticle, emitter::BallParticleEmitter,
         system::BallParticleSystem,
     },
-    util::{ValueOverTime, lerp_color},
+    util::lerp_color,
     wall::Wall,
 };
 
@@ -29,10 +33,12 @@ pub struct Ball {
     id: String,
     name: String,
     name_color: Srgba,
+    pronouns: Pronouns,
     physics_ball: PhysicsBall,
     style: Box<dyn BallStyle>,
     sound_path: PathBuf,
     particles: BallParticleSystem,
+    eliminated: bool,
 }
 
 impl Ball {
@@ -50,10 +56,12 @@ impl Ball {
             id,
             name,
             name_color,
+            pronouns: Pronouns::default(),
             physics_ball,
             style,
             sound_path,
             particles: BallParticleSystem::default(),
+            eliminated: false,
         }
     }
 
@@ -94,7 +102,7 @@ impl Ball {
                     16,
                 ))
             } else if marble.name == "IKEA" || marble.name == "IKEA Jr." {
-                Box::new(IkeaStyle::default())
+                Box::new(IkeaStyle)
             } else if marble.name == "Black Hole" {
                 Box::new(GlowStyle::new(
                     Srgba::new(0.0, 0.0, 0.0, 1.0),
@@ -121,6 +129,8 @@ impl Ball {
             Path::new("ball_sounds").join(&marble.sound_path),
         );
 
+        ball.set_pronouns(marble.pronouns.clone());
+
         if marble.name == "Fireball" {
             ball.get_particles_mut()
                 .add_emitter(BallParticleEmitter::new(
@@ -202,6 +212,14 @@ impl Ball {
         self.name = name.into();
     }
 
+    pub fn get_pronouns(&self) -> &Pronouns {
+        &self.pronouns
+    }
+
+    pub fn set_pronouns(&mut self, pronouns: Pronouns) {
+        self.pronouns = pronouns;
+    }
+
     pub fn get_name_color(&self) -> Srgba {
         self.name_color
     }
@@ -218,9 +236,24 @@ impl Ball {
         self.style.as_ref()
     }
 
+    /// Eliminated balls keep their index in the scene but no longer move, collide or get drawn.
+    pub fn is_eliminated(&self) -> bool {
+        self.eliminated
+    }
+
+    pub fn set_eliminated(&mut self, eliminated: bool) {
+        self.eliminated = eliminated;
+    }
+
     pub fn render(&self, renderer: &mut dyn Renderer) {
+        if self.is_eliminated() {
+            return;
+        }
+
         self.get_particles().render_back(renderer);
-        self.get_style().render(self, renderer);
+        self.get_style()
+            .render(self, self.get_orientation(), renderer);
+        render_status_effects(self, renderer);
         self.get_particles().render_front(renderer);
     }
 
@@ -248,6 +281,22 @@ impl Ball {
         self.physics_ball.set_velocity(velocity);
     }
 
+    pub fn get_orientation(&self) -> f64 {
+        self.physics_ball.get_orientation()
+    }
+
+    pub fn set_orientation(&mut self, orientation: f64) {
+        self.physics_ball.set_orientation(orientation);
+    }
+
+    pub fn get_angular_velocity(&self) -> f64 {
+        self.physics_ball.get_angular_velocity()
+    }
+
+    pub fn set_angular_velocity(&mut self, angular_velocity: f64) {
+        self.physics_ball.set_angular_velocity(angular_velocity);
+    }
+
     pub fn get_radius(&self) -> f64 {
         self.physics_ball.get_radius()
     }
@@ -257,7 +306,7 @@ impl Ball {
     }
 
     pub fn get_density(&self) -> f64 {
-        *self.physics_ball.get_density().get_value(self.get_time())
+        self.physics_ball.get_density()
     }
 
     pub fn set_density(&mut self, density: f64) {
@@ -265,10 +314,7 @@ impl Ball {
     }
 
     pub fn get_elasticity(&self) -> f64 {
-        *self
-            .physics_ball
-            .get_elasticity()
-            .get_value(self.get_time())
+        self.physics_ball.get_elasticity()
     }
 
     pub fn set_elasticity(&mut self, elasticity: f64) {
@@ -315,12 +361,7 @@ impl Ball {
             }
         }
 
-        if *self
-            .get_physics_ball()
-            .get_bloodbath()
-            .get_value(self.get_time())
-            && dv >= 150.0
-        {
+        if self.get_physics_ball().is_bloodbath() && dv >= 150.0 {
             // TODO: Fix this to not be hard coded.
             let velocity = self.get_velocity();
 
@@ -351,14 +392,20 @@ impl Ball {
 
         new_ball.set_time(new_ball.get_time() + dt);
 
+        let time = new_ball.get_time();
+        new_ball
+            .get_physics_ball_mut()
+            .get_status_effects_mut()
+            .prune(time);
+
+        let velocity_coefficient = new_ball.get_physics_ball().get_velocity_coefficient();
+
         new_ball.set_position(
-            new_ball.get_position()
-                + new_ball.get_velocity()
-                    * dt
-                    * new_ball
-                        .get_physics_ball()
-                        .get_velocity_coefficient()
-                        .get_value(new_ball.get_time()),
+            new_ball.get_position() + new_ball.get_velocity() * dt * velocity_coefficient,
+        );
+        new_ball.set_orientation(
+            new_ball.get_orientation()
+                + new_ball.get_angular_velocity() * dt * velocity_coefficient,
         );
 
         let position = new_ball.get_position();
@@ -383,12 +430,12 @@ pub struct PhysicsBall {
     time: f64,
     position: DVec2,
     velocity: DVec2,
-    velocity_coefficient: ValueOverTime<f64>,
-    gravity_coefficient: ValueOverTime<f64>,
+    orientation: f64,
+    angular_velocity: f64,
     radius: f64,
-    density: ValueOverTime<f64>,
-    elasticity: ValueOverTime<f64>,
-    bloodbath: ValueOverTime<bool>,
+    density: f64,
+    elasticity: f64,
+    status_effects: StatusEffects,
 }
 
 impl PhysicsBall {
@@ -403,12 +450,12 @@ impl PhysicsBall {
             time: 0.0,
             position,
             velocity,
-            velocity_coefficient: ValueOverTime::new(1.0),
-            gravity_coefficient: ValueOverTime::new(1.0),
+            orientation: 0.0,
+            angular_velocity: 0.0,
             radius,
-            density: ValueOverTime::new(density),
-            elasticity: ValueOverTime::new(elasticity),
-            bloodbath: ValueOverTime::new(false),
+            density,
+            elasticity,
+            status_effects: StatusEffects::default(),
         }
     }
 
@@ -436,20 +483,36 @@ impl PhysicsBall {
         self.velocity = velocity;
     }
 
-    pub fn get_velocity_coefficient(&self) -> &ValueOverTime<f64> {
-        &self.velocity_coefficient
+    /// How far the ball has turned, in radians clockwise.
+    pub fn get_orientation(&self) -> f64 {
+        self.orientation
+    }
+
+    pub fn set_orientation(&mut self, orientation: f64) {
+        self.orientation = orientation;
+    }
+
+    /// How fast the ball is spinning, in radians per second clockwise.
+    pub fn get_angular_velocity(&self) -> f64 {
+        self.angular_velocity
     }
 
-    pub fn get_velocity_coefficient_mut(&mut self) -> &mut ValueOverTime<f64> {
-        &mut self.velocity_coefficient
+    pub fn set_angular_velocity(&mut self, angular_velocity: f64) {
+        self.angular_velocity = angular_velocity;
     }
 
-    pub fn get_gravity_coefficient(&self) -> &ValueOverTime<f64> {
-        &self.gravity_coefficient
+    /// The velocity of the point on the ball's surface in this direction from its centre,
+    /// counting its spin.
+    pub fn get_surface_velocity(&self, direction: DVec2) -> DVec2 {
+        self.velocity + self.angular_velocity * (direction * self.radius).perp()
     }
 
-    pub fn get_gravity_coefficient_mut(&mut self) -> &mut ValueOverTime<f64> {
-        &mut self.gravity_coefficient
+    pub fn get_velocity_coefficient(&self) -> f64 {
+        self.status_effects.apply(Stat::Speed, 1.0, self.time)
+    }
+
+    pub fn get_gravity_coefficient(&self) -> f64 {
+        self.status_effects.apply(Stat::Gravity, 1.0, self.time)
     }
 
     pub fn get_radius(&self) -> f64 {
@@ -460,28 +523,24 @@ impl PhysicsBall {
         self.radius = radius;
     }
 
-    pub fn get_density(&self) -> &ValueOverTime<f64> {
-        &self.density
-    }
-
-    pub fn get_density_mut(&mut self) -> &mut ValueOverTime<f64> {
-        &mut self.density
+    /// The ball's density with its status effects applied.
+    pub fn get_density(&self) -> f64 {
+        self.status_effects
+            .apply(Stat::Density, self.density, self.time)
     }
 
     pub fn set_density(&mut self, density: f64) {
-        self.get_density_mut().set_value(density);
+        self.density = density;
     }
 
-    pub fn get_elasticity(&self) -> &ValueOverTime<f64> {
-        &self.elasticity
-    }
-
-    pub fn get_elasticity_mut(&mut self) -> &mut ValueOverTime<f64> {
-        &mut self.elasticity
+    /// The ball's elasticity with its status effects applied.
+    pub fn get_elasticity(&self) -> f64 {
+        self.status_effects
+            .apply(Stat::Elasticity, self.elasticity, self.time)
     }
 
     pub fn set_elasticity(&mut self, elasticity: f64) {
-        self.get_elasticity_mut().set_value(elasticity);
+        self.elasticity = elasticity;
     }
 
     pub fn get_volume(&self) -> f64 {
@@ -489,14 +548,33 @@ impl PhysicsBall {
     }
 
     pub fn get_mass(&self) -> f64 {
-        self.get_volume() * self.get_density().get_value(self.get_time())
+        self.get_volume() * self.get_density()
+    }
+
+    pub fn is_bloodbath(&self) -> bool {
+        self.status_effects.apply(Stat::Bloodbath, 0.0, self.time) > 0.0
+    }
+
+    /// A shielded ball doesn't get pushed around by other balls.
+    pub fn is_shielded(&self) -> bool {
+        self.status_effects.apply(Stat::Shield, 0.0, self.time) > 0.0
+    }
+
+    /// How hard a ball pulls other balls near it towards itself, 0 for not at all.
+    pub fn get_magnet(&self) -> f64 {
+        self.status_effects.apply(Stat::Magnet, 0.0, self.time)
+    }
+
+    pub fn get_status_effects(&self) -> &StatusEffects {
+        &self.status_effects
     }
 
-    pub fn get_bloodbath(&self) -> &ValueOverTime<bool> {
-        &self.bloodbath
+    pub fn get_status_effects_mut(&mut self) -> &mut StatusEffects {
+        &mut self.status_effects
     }
 
-    pub fn get_bloodbath_mut(&mut self) -> &mut ValueOverTime<bool> {
-        &mut self.bloodbath
+    /// Starts a status effect now, by the ball's clock.
+    pub fn add_status_effect(&mut self, effect: StatusEffect) {
+        self.status_effects.add(effect, self.time);
     }
 }
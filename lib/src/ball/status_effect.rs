@@ -0,0 +1,226 @@
+/// Something about a ball that status effects change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stat {
+    /// Multiplies how far the ball moves for its velocity.
+    Speed,
+    /// Multiplies how hard gravity pulls on the ball.
+    Gravity,
+    /// Multiplies the ball's density.
+    Density,
+    /// Multiplies the ball's elasticity.
+    Elasticity,
+    /// Adds to how hard the ball pulls other balls near it towards itself.
+    Magnet,
+    /// While above 0, balls the ball hits get lighter and float away.
+    Bloodbath,
+    /// While above 0, the ball doesn't get pushed around by other balls.
+    Shield,
+}
+
+impl Stat {
+    /// Whether effects on this stat multiply the ball's own value, rather than adding to it.
+    pub fn is_multiplier(&self) -> bool {
+        matches!(
+            self,
+            Self::Speed | Self::Gravity | Self::Density | Self::Elasticity
+        )
+    }
+}
+
+/// What happens when a ball gets an effect with the same name and stat as one it already has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Stacking {
+    /// The new effect replaces the old one, starting the clock over.
+    #[default]
+    Refresh,
+    /// Both effects run, and their values add up.
+    Stack,
+    /// Both effects run, and their values multiply.
+    Multiply,
+    /// The stronger value is kept, and lasts until the later of the two would have ended.
+    Max,
+}
+
+/// A named change to one of a ball's stats that wears off after a while.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusEffect {
+    name: String,
+    stat: Stat,
+    value: f64,
+    seconds: f64,
+    stacking: Stacking,
+    maybe_source: Option<String>,
+    start: f64,
+}
+
+impl StatusEffect {
+    pub fn new(name: impl Into<String>, stat: Stat, value: f64, seconds: f64) -> Self {
+        Self {
+            name: name.into(),
+            stat,
+            value,
+            seconds,
+            stacking: Stacking::default(),
+            maybe_source: None,
+            start: 0.0,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_stat(&self) -> Stat {
+        self.stat
+    }
+
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn get_seconds(&self) -> f64 {
+        self.seconds
+    }
+
+    pub fn get_stacking(&self) -> Stacking {
+        self.stacking
+    }
+
+    pub fn set_stacking(&mut self, stacking: Stacking) {
+        self.stacking = stacking;
+    }
+
+    /// The id of the ball that gave this effect, if it came from one.
+    pub fn get_source(&self) -> Option<&str> {
+        self.maybe_source.as_deref()
+    }
+
+    pub fn set_source(&mut self, maybe_source: Option<String>) {
+        self.maybe_source = maybe_source;
+    }
+
+    pub fn get_start(&self) -> f64 {
+        self.start
+    }
+
+    pub fn get_end(&self) -> f64 {
+        self.start + self.seconds
+    }
+
+    pub fn is_active(&self, time: f64) -> bool {
+        (self.get_start()..=self.get_end()).contains(&time)
+    }
+
+    /// Seconds left before the effect wears off.
+    pub fn get_remaining(&self, time: f64) -> f64 {
+        (self.get_end() - time).clamp(0.0, self.seconds)
+    }
+
+    fn is_same_as(&self, other: &Self) -> bool {
+        self.name == other.name && self.stat == other.stat
+    }
+}
+
+/// Every status effect on one ball, timed by the ball's own clock.
+#[derive(Debug, Clone, Default)]
+pub struct StatusEffects {
+    effects: Vec<StatusEffect>,
+}
+
+impl StatusEffects {
+    /// Starts an effect at this time, combining it with any matching effect according to its
+    /// stacking rule.
+    pub fn add(&mut self, mut effect: StatusEffect, time: f64) {
+        effect.start = time;
+
+        let maybe_existing = self
+            .effects
+            .iter_mut()
+            .find(|existing| existing.is_active(time) && existing.is_same_as(&effect));
+
+        match (effect.stacking, maybe_existing) {
+            (Stacking::Refresh, Some(existing)) => *existing = effect,
+            (Stacking::Max, Some(existing)) => {
+                let end = existing.get_end().max(effect.get_end());
+
+                if effect.value > existing.value {
+                    *existing = effect;
+                }
+
+                existing.seconds = end - existing.start;
+            }
+            _ => self.effects.push(effect),
+        }
+    }
+
+    /// Removes every effect that has worn off by this time.
+    pub fn prune(&mut self, time: f64) {
+        self.effects.retain(|effect| effect.get_end() >= time);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StatusEffect> {
+        self.effects.iter()
+    }
+
+    /// The effects running at this time, in the order they started.
+    pub fn get_active(&self, time: f64) -> Vec<&StatusEffect> {
+        self.effects
+            .iter()
+            .filter(|effect| effect.is_active(time))
+            .collect()
+    }
+
+    /// The names of the effects running at this time, each once.
+    pub fn get_active_names(&self, time: f64) -> Vec<&str> {
+        let mut names = Vec::new();
+
+        for effect in self.get_active(time) {
+            if !names.contains(&effect.get_name()) {
+                names.push(effect.get_name());
+            }
+        }
+
+        names
+    }
+
+    pub fn has(&self, stat: Stat, time: f64) -> bool {
+        self.effects
+            .iter()
+            .any(|effect| effect.stat == stat && effect.is_active(time))
+    }
+
+    /// A stat with every running effect on it applied to the ball's own value. Effects with the
+    /// same name are combined by their stacking rule first.
+    pub fn apply(&self, stat: Stat, base_value: f64, time: f64) -> f64 {
+        let mut groups: Vec<(&str, f64)> = Vec::new();
+
+        for effect in self
+            .effects
+            .iter()
+            .filter(|effect| effect.stat == stat && effect.is_active(time))
+        {
+            match groups
+                .iter_mut()
+                .find(|(name, _)| *name == effect.get_name())
+            {
+                Some((_, value)) => match effect.stacking {
+                    Stacking::Stack => *value += effect.value,
+                    Stacking::Multiply => *value *= effect.value,
+                    Stacking::Refresh => *value = effect.value,
+                    Stacking::Max => *value = value.max(effect.value),
+                },
+                None => groups.push((effect.get_name(), effect.value)),
+            }
+        }
+
+        if stat.is_multiplier() {
+            groups
+                .iter()
+                .fold(base_value, |value, (_, multiplier)| value * multiplier)
+        } else {
+            groups
+                .iter()
+                .fold(base_value, |value, (_, addition)| value + addition)
+        }
+    }
+}
@@ -28,7 +28,7 @@ impl BallStyle for BaseStyle {
         Box::new(self.clone())
     }
 
-    fn render(&self, ball: &Ball, renderer: &mut dyn Renderer) {
+    fn render(&self, ball: &Ball, _orientation: f64, renderer: &mut dyn Renderer) {
         renderer.render_circle(ball.get_position(), ball.get_radius(), self.get_color());
 
         renderer.render_text_outline(
@@ -43,7 +43,7 @@ impl BallStyle for GlowStyle {
         Box::new(self.clone())
     }
 
-    fn render(&self, ball: &Ball, renderer: &mut dyn Renderer) {
+    fn render(&self, ball: &Ball, _orientation: f64, renderer: &mut dyn Renderer) {
         for i in 0..=self.get_size() {
             let i = self.get_size() - i;
 
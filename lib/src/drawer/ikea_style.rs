@@ -10,28 +10,16 @@ use crate::{
 };
 
 #[derive(Clone)]
-pub struct IkeaStyle {
-    theta: f64,
-}
-
-impl Default for IkeaStyle {
-    fn default() -> Self {
-        Self { theta: 0.0 }
-    }
-}
+pub struct IkeaStyle;
 
 impl BallStyle for IkeaStyle {
     fn init(&mut self, _ball: &PhysicsBall) {}
 
-    fn update(&self, ball: &PhysicsBall, dt: f64) -> Box<dyn BallStyle> {
-        let mut new_style = self.clone();
-
-        new_style.theta += ball.get_velocity().x / (10.0 * ball.get_radius() / 8.0) * dt;
-
-        Box::new(new_style)
+    fn update(&self, _ball: &PhysicsBall, _dt: f64) -> Box<dyn BallStyle> {
+        Box::new(self.clone())
     }
 
-    fn render(&self, ball: &Ball, renderer: &mut dyn Renderer) {
+    fn render(&self, ball: &Ball, orientation: f64, renderer: &mut dyn Renderer) {
         renderer.render_circle(
             ball.get_position(),
             ball.get_radius(),
@@ -42,12 +30,12 @@ impl BallStyle for IkeaStyle {
 
         renderer.render_line(
             dvec2(
-                ball.get_position().x + ball.get_radius() * self.theta.cos(),
-                ball.get_position().y + ball.get_radius() * self.theta.sin(),
+                ball.get_position().x + ball.get_radius() * orientation.cos(),
+                ball.get_position().y + ball.get_radius() * orientation.sin(),
             ),
             dvec2(
-                ball.get_position().x + ball.get_radius() * (self.theta + PI).cos(),
-                ball.get_position().y + ball.get_radius() * (self.theta + PI).sin(),
+                ball.get_position().x + ball.get_radius() * (orientation + PI).cos(),
+                ball.get_position().y + ball.get_radius() * (orientation + PI).sin(),
             ),
             2.0 * ball.get_radius() / 8.0,
             yellow.into(),
@@ -55,12 +43,12 @@ impl BallStyle for IkeaStyle {
 
         renderer.render_line(
             dvec2(
-                ball.get_position().x + ball.get_radius() * (self.theta + 2.0 / 3.0 * PI).cos(),
-                ball.get_position().y + ball.get_radius() * (self.theta + 2.0 / 3.0 * PI).sin(),
+                ball.get_position().x + ball.get_radius() * (orientation + 2.0 / 3.0 * PI).cos(),
+                ball.get_position().y + ball.get_radius() * (orientation + 2.0 / 3.0 * PI).sin(),
             ),
             dvec2(
-                ball.get_position().x + ball.get_radius() * (self.theta + 4.0 / 3.0 * PI).cos(),
-                ball.get_position().y + ball.get_radius() * (self.theta + 4.0 / 3.0 * PI).sin(),
+                ball.get_position().x + ball.get_radius() * (orientation + 4.0 / 3.0 * PI).cos(),
+                ball.get_position().y + ball.get_radius() * (orientation + 4.0 / 3.0 * PI).sin(),
             ),
             2.0 * ball.get_radius() / 8.0,
             yellow.into(),
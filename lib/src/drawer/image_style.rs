@@ -11,16 +11,11 @@ use crate::{
 pub struct ImageStyle {
     color: Srgba,
     image_name: String,
-    theta: f64,
 }
 
 impl ImageStyle {
     pub fn new(color: Srgba, image_name: String) -> Self {
-        Self {
-            color,
-            image_name,
-            theta: 0.0,
-        }
+        Self { color, image_name }
     }
 
     pub fn get_color(&self) -> Srgba {
@@ -34,22 +29,18 @@ impl ImageStyle {
 
 impl BallStyle for ImageStyle {
     fn init(&mut self, _ball: &PhysicsBall) {}
-    fn update(&self, ball: &PhysicsBall, dt: f64) -> Box<dyn BallStyle> {
-        let mut new_style = self.clone();
-
-        new_style.theta += ball.get_velocity().x / (10.0 * ball.get_radius() / 8.0) * dt;
-
-        Box::new(new_style)
+    fn update(&self, _ball: &PhysicsBall, _dt: f64) -> Box<dyn BallStyle> {
+        Box::new(self.clone())
     }
 
-    fn render(&self, ball: &Ball, renderer: &mut dyn Renderer) {
+    fn render(&self, ball: &Ball, orientation: f64, renderer: &mut dyn Renderer) {
         renderer.render_image(
             self.get_image_name(),
             ball.get_position(),
             ball.get_radius() * 2.0,
             ball.get_radius() * 2.0,
             DVec2::splat(0.5),
-            self.theta,
+            orientation,
         );
 
         renderer.render_circle_lines(
@@ -69,4 +60,8 @@ impl BallStyle for ImageStyle {
             Srgba::new(0.0, 0.0, 0.0, 1.0),
         );
     }
+
+    fn get_image_name(&self) -> Option<&str> {
+        Some(&self.image_name)
+    }
 }
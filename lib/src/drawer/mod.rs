@@ -8,12 +8,18 @@ pub mod glow_style;
 pub mod ikea_style;
 pub mod image_style;
 pub mod outline_style;
+pub mod status_layer;
 pub mod tail_style;
 
 pub trait BallStyle: Send + Sync + DynClone {
     fn init(&mut self, ball: &PhysicsBall);
     fn update(&self, ball: &PhysicsBall, dt: f64) -> Box<dyn BallStyle>;
-    fn render(&self, ball: &Ball, renderer: &mut dyn Renderer);
+    /// `orientation` is how far the ball has turned, in radians clockwise.
+    fn render(&self, ball: &Ball, orientation: f64, renderer: &mut dyn Renderer);
+
+    fn get_image_name(&self) -> Option<&str> {
+        None
+    }
 }
 
 dyn_clone::clone_trait_object!(BallStyle);
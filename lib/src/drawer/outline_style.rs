@@ -28,7 +28,7 @@ impl BallStyle for OutlineStyle {
         Box::new(self.clone())
     }
 
-    fn render(&self, ball: &Ball, renderer: &mut dyn Renderer) {
+    fn render(&self, ball: &Ball, _orientation: f64, renderer: &mut dyn Renderer) {
         renderer.render_circle_lines(
             ball.get_position(),
             ball.get_radius(),
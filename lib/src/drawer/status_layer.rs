@@ -0,0 +1,69 @@
+use palette::Srgba;
+use render_agnostic::Renderer;
+
+use crate::ball::{
+    Ball,
+    status_effect::{Stat, StatusEffect},
+};
+
+const RING_GAP: f64 = 5.0;
+const RING_THICKNESS: f64 = 2.0;
+
+/// Draws a ring around the ball for each status effect it's under, on top of its style. Each ring
+/// has an arc that runs down clockwise from the top as the effect wears off.
+pub fn render_status_effects(ball: &Ball, renderer: &mut dyn Renderer) {
+    let time = ball.get_time();
+    let status_effects = ball.get_physics_ball().get_status_effects();
+
+    for (ring, name) in status_effects
+        .get_active_names(time)
+        .into_iter()
+        .enumerate()
+    {
+        let Some(effect) = status_effects
+            .get_active(time)
+            .into_iter()
+            .filter(|effect| effect.get_name() == name && effect.get_seconds() > 0.0)
+            .max_by(|a, b| a.get_remaining(time).total_cmp(&b.get_remaining(time)))
+        else {
+            continue;
+        };
+
+        let radius = ball.get_radius() + RING_GAP * (ring + 1) as f64;
+        let color = get_indicator_color(effect);
+
+        renderer.render_circle_lines(
+            ball.get_position(),
+            radius,
+            RING_THICKNESS,
+            Srgba::new(color.red, color.green, color.blue, 0.25),
+        );
+        renderer.render_arc_lines(
+            ball.get_position(),
+            radius,
+            -90.0,
+            16,
+            360.0 * effect.get_remaining(time) / effect.get_seconds(),
+            RING_THICKNESS,
+            color,
+        );
+    }
+}
+
+/// Speedups are green and slowdowns blue, freezes are white and flipped gravity purple.
+fn get_indicator_color(effect: &StatusEffect) -> Srgba {
+    match effect.get_stat() {
+        Stat::Speed if effect.get_value() > 1.0 => Srgba::new(0.2, 1.0, 0.3, 1.0),
+        Stat::Speed | Stat::Gravity if effect.get_value() == 0.0 => {
+            Srgba::new(0.85, 0.95, 1.0, 1.0)
+        }
+        Stat::Speed => Srgba::new(0.3, 0.6, 1.0, 1.0),
+        Stat::Gravity if effect.get_value() < 0.0 => Srgba::new(0.7, 0.4, 1.0, 1.0),
+        Stat::Gravity if effect.get_value() > 1.0 => Srgba::new(0.2, 1.0, 0.3, 1.0),
+        Stat::Gravity => Srgba::new(0.3, 0.6, 1.0, 1.0),
+        Stat::Density | Stat::Elasticity => Srgba::new(1.0, 0.6, 0.2, 1.0),
+        Stat::Magnet => Srgba::new(1.0, 0.3, 0.3, 1.0),
+        Stat::Bloodbath => Srgba::new(0.6, 0.0, 0.0, 1.0),
+        Stat::Shield => Srgba::new(0.3, 0.9, 1.0, 1.0),
+    }
+}
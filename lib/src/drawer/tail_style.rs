@@ -71,7 +71,7 @@ impl BallStyle for TailStyle {
         Box::new(self.update_internal(ball, dt))
     }
 
-    fn render(&self, ball: &Ball, renderer: &mut dyn Renderer) {
+    fn render(&self, ball: &Ball, _orientation: f64, renderer: &mut dyn Renderer) {
         for (index, position) in self.get_positions().iter().copied().enumerate() {
             let percent = (index + 1) as f64 / self.get_positions().len() as f64;
 
@@ -0,0 +1,26 @@
+/// When the last-place marble gets knocked out of an elimination race. The last marble left
+/// standing wins.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Elimination {
+    /// Every this many seconds.
+    Interval(f64),
+    /// Each time the leader passes one of these points of progress through the level, from 0 at
+    /// the start to 1 at the goal.
+    Checkpoints(Vec<f64>),
+    /// Never on a schedule. Marbles only go out by going out of bounds, like being pushed out of
+    /// an arena.
+    OutOfBounds,
+}
+
+impl Elimination {
+    /// Whether another marble should go out, given how many already have.
+    pub fn is_due(&self, eliminations: usize, time: f64, leader_progress: f64) -> bool {
+        match self {
+            Self::Interval(seconds) => time >= seconds * (eliminations + 1) as f64,
+            Self::Checkpoints(checkpoints) => checkpoints
+                .get(eliminations)
+                .is_some_and(|checkpoint| leader_progress >= *checkpoint),
+            Self::OutOfBounds => false,
+        }
+    }
+}
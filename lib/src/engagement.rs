@@ -1,9 +1,18 @@
 use std::fmt::Display;
 
+use api::marble::{Pronouns, Rivalry, RivalryKind};
+use chrono::Utc;
+use database::{marble::DbMarble, race_marble::DbRaceMarble, rivalry::DbRivalry};
 use rand::{Rng, seq::IndexedRandom};
 use sqlx::SqlitePool;
 
-use crate::{ball::Ball, scene::Scene};
+use crate::{
+    elimination::Elimination,
+    promotion::get_announcements,
+    race_context::RaceContext,
+    scene::Scene,
+    stats::{MarbleRecord, ordinal},
+};
 
 #[derive(Debug, Clone)]
 pub struct Marble {
@@ -11,47 +20,6 @@ pub struct Marble {
     pub pronouns: Pronouns,
 }
 
-#[derive(Debug, Copy, Clone)]
-pub enum Pronouns {
-    Masculine,
-    Feminine,
-    Neutral,
-}
-
-impl Pronouns {
-    pub fn from_name(name: &str) -> Self {
-        match name {
-            "Blue's Wife" | "Black Hole" | "Molly" | "Perl" => Self::Feminine,
-            "IKEA" | "Beebo" | "Canada" | "Creepy" | "Estonia" | "Amogus" => Self::Neutral,
-            _ => Self::Masculine,
-        }
-    }
-
-    pub fn subject(&self) -> String {
-        match self {
-            Self::Masculine => String::from("he"),
-            Self::Feminine => String::from("she"),
-            Self::Neutral => String::from("it"),
-        }
-    }
-
-    pub fn object(&self) -> String {
-        match self {
-            Self::Masculine => String::from("him"),
-            Self::Feminine => String::from("her"),
-            Self::Neutral => String::from("it"),
-        }
-    }
-
-    pub fn posessive(&self) -> String {
-        match self {
-            Self::Masculine => String::from("his"),
-            Self::Feminine => String::from("hers"),
-            Self::Neutral => String::from("its"),
-        }
-    }
-}
-
 pub enum Engagement {
     WhoWillWin,
     OkEpic,
@@ -61,6 +29,10 @@ pub enum Engagement {
     OneRaceLeft,
     WinnerStays,
     WhoSurvives,
+    OnlyTopAdvance(usize),
+    LastPlaceIsOut,
+    LastOneInside,
+    ForTheTournament,
     NoOneExpected,
     FinalCornerDecides,
     BiggestUpsetInHistory,
@@ -68,30 +40,32 @@ pub enum Engagement {
     GetsIntense,
     FinalStretch,
     DontBlink,
-    WinToLossTodayTheDay(Marble),
-    HasntSeenPodium(Marble),
+    WinToLossTodayTheDay(Marble, usize),
+    HasntSeenPodium(Marble, usize),
     WinOrCrashOut(Marble),
-    HasntLostInStraight(Marble),
-    HistoricLosingStreak(Marble),
-    HasntFinishedTopHalfInAttempts(Marble),
-    HuntingNthWin(Marble),
-    RunsThisTrack(Marble),
-    NeverLostHere(Marble),
-    DoesntLoseCloseRaces(Marble),
+    HasntLostInStraight(Marble, usize),
+    HistoricLosingStreak(Marble, usize),
+    HasntFinishedTopHalfInAttempts(Marble, usize),
+    HuntingNthWin(Marble, usize),
+    RunsThisTrack(Marble, usize, usize),
+    NeverLostHere(Marble, usize),
+    DoesntLoseCloseRaces(Marble, usize),
     AlwaysFindsAWay(Marble),
     CompletelyUnpredictable(Marble),
     SuspiciouslyConsistent(Marble),
     EitherFirstOrLast(Marble),
     JustDoesOwnThing(Marble),
-    ChokedLastNRedemption(Marble),
+    ChokedLastNRedemption(Marble, usize),
     DesperateForAWin(Marble),
-    HasntBeenRelevant(Marble),
+    HasntBeenRelevant(Marble, i64),
     QuietlyClimbing(Marble),
-    BackAfterBrutalLosingStreak(Marble),
+    BackAfterBrutalLosingStreak(Marble, usize),
     WinsAgainTakesLead(Marble),
     AllEyes(Marble),
     AnyoneStop(Marble),
-    VsTied(Marble, Marble),
+    JustPromoted(Marble, String),
+    JustRelegated(Marble, String),
+    VsTied(Marble, Marble, usize),
     HateEachOther(Marble, Marble),
     VsControl(Marble, Marble),
     VsOppositeForces(Marble, Marble),
@@ -112,6 +86,13 @@ impl Display for Engagement {
                 f,
                 "Three marbles wiped out in the last race... who survives this one?"
             ),
+            Self::OnlyTopAdvance(advance) => match advance {
+                1 => write!(f, "Only the winner moves on"),
+                advance => write!(f, "Only the top {} move on", advance),
+            },
+            Self::LastPlaceIsOut => write!(f, "Last place gets knocked out... who survives?"),
+            Self::LastOneInside => write!(f, "Last marble inside the ring wins"),
+            Self::ForTheTournament => write!(f, "One race for the whole tournament"),
             Self::NoOneExpected => write!(f, "No one expected THIS finish"),
             Self::FinalCornerDecides => write!(f, "The final corner decides everything"),
             Self::BiggestUpsetInHistory => write!(f, "Biggest upset in marble history?"),
@@ -119,44 +100,61 @@ impl Display for Engagement {
             Self::GetsIntense => write!(f, "This one gets intense"),
             Self::FinalStretch => write!(f, "Watch the final stretch..."),
             Self::DontBlink => write!(f, "Don't blink"),
-            Self::WinToLossTodayTheDay(marble) => {
-                write!(f, "{} is 0-27... is today finally the day?", marble.name)
+            Self::WinToLossTodayTheDay(marble, losses) => {
+                write!(
+                    f,
+                    "{} is 0-{}... is today finally the day?",
+                    marble.name, losses
+                )
             }
-            Self::HasntSeenPodium(marble) => {
-                write!(f, "{} hasn't seen a podium in 15 races", marble.name)
+            Self::HasntSeenPodium(marble, races) => {
+                write!(f, "{} hasn't seen a podium in {} races", marble.name, races)
             }
             Self::WinOrCrashOut(marble) => write!(
                 f,
                 "{} either wins... or crashes out completely",
                 marble.name
             ),
-            Self::HasntLostInStraight(marble) => {
-                write!(f, "{} hasn't lost in 6 straight", marble.name)
+            Self::HasntLostInStraight(marble, races) => {
+                write!(f, "{} hasn't lost in {} straight", marble.name, races)
             }
-            Self::HistoricLosingStreak(marble) => {
-                write!(f, "{} is on a historic losing streak", marble.name)
+            Self::HistoricLosingStreak(marble, races) => {
+                write!(
+                    f,
+                    "{} is on a historic {} race losing streak",
+                    marble.name, races
+                )
             }
-            Self::HasntFinishedTopHalfInAttempts(marble) => write!(
+            Self::HasntFinishedTopHalfInAttempts(marble, races) => write!(
                 f,
-                "{} hasn't finished a race in the top half in 20 attempts",
-                marble.name
+                "{} hasn't finished a race in the top half in {} attempts",
+                marble.name, races
             ),
-            Self::HuntingNthWin(marble) => write!(
+            Self::HuntingNthWin(marble, win) => write!(
                 f,
-                "{} is hunting {} 8th win",
+                "{} is hunting {} {} win",
                 marble.name,
-                marble.pronouns.posessive()
+                marble.pronouns.possessive,
+                ordinal(*win)
             ),
-            Self::RunsThisTrack(marble) => write!(
+            Self::RunsThisTrack(marble, wins, races) => write!(
                 f,
-                "{} runs this track... nobody has stopped him yet",
-                marble.name
+                "{} runs this track... {} wins in {} races here",
+                marble.name, wins, races
             ),
-            Self::NeverLostHere(marble) => {
-                write!(f, "{} is back-and he's never lost here", marble.name)
+            Self::NeverLostHere(marble, races) => {
+                write!(
+                    f,
+                    "{} is back-and {} record here is {} races without a loss",
+                    marble.name, marble.pronouns.possessive, races
+                )
             }
-            Self::DoesntLoseCloseRaces(marble) => {
-                write!(f, "{} doesn't lose close races", marble.name)
+            Self::DoesntLoseCloseRaces(marble, wins) => {
+                write!(
+                    f,
+                    "{} doesn't lose close races... {}-0 in photo finishes",
+                    marble.name, wins
+                )
             }
             Self::AlwaysFindsAWay(marble) => write!(f, "{} always finds a way to win", marble.name),
             Self::CompletelyUnpredictable(marble) => {
@@ -171,33 +169,50 @@ impl Display for Engagement {
             Self::JustDoesOwnThing(marble) => write!(
                 f,
                 "{} just does {} own thing",
-                marble.name,
-                marble.pronouns.posessive()
+                marble.name, marble.pronouns.possessive
             ),
-            Self::ChokedLastNRedemption(marble) => write!(
+            Self::ChokedLastNRedemption(marble, races) => write!(
                 f,
-                "{} choked the last 3 finishes... redemption today?",
-                marble.name
+                "{} choked the last {} finishes... redemption today?",
+                marble.name, races
             ),
             Self::DesperateForAWin(marble) => write!(f, "{} is desperate for a win", marble.name),
-            Self::HasntBeenRelevant(marble) => write!(
+            Self::HasntBeenRelevant(marble, weeks) => write!(
                 f,
-                "{} hasn't been relevant in weeks... until now?",
-                marble.name
+                "{} hasn't been relevant in {} weeks... until now?",
+                marble.name, weeks
             ),
             Self::QuietlyClimbing(marble) => {
                 write!(f, "{} is quietly climbing the ranks", marble.name)
             }
-            Self::BackAfterBrutalLosingStreak(marble) => {
-                write!(f, "{} is back after a brutal streak of losses", marble.name)
+            Self::BackAfterBrutalLosingStreak(marble, races) => {
+                write!(
+                    f,
+                    "{} is back after a brutal streak of {} losses",
+                    marble.name, races
+                )
             }
             Self::WinsAgainTakesLead(marble) => {
-                write!(f, "If {} wins again, he takes the lead", marble.name)
+                write!(f, "One more win and {} takes the lead", marble.name)
             }
             Self::AllEyes(marble) => write!(f, "All eyes on {}", marble.name),
             Self::AnyoneStop(marble) => write!(f, "Can anyone stop {}?", marble.name),
-            Self::VsTied(marble_1, marble_2) => {
-                write!(f, "{} vs {} - tied 4-4", marble_1.name, marble_2.name)
+            Self::JustPromoted(marble, league) => write!(
+                f,
+                "{} just got promoted to the {}. Can {} hold {} own?",
+                marble.name, league, marble.name, marble.pronouns.possessive
+            ),
+            Self::JustRelegated(marble, league) => write!(
+                f,
+                "{} just got relegated to the {}. Time to bounce back?",
+                marble.name, league
+            ),
+            Self::VsTied(marble_1, marble_2, wins) => {
+                write!(
+                    f,
+                    "{} vs {} - tied {}-{}",
+                    marble_1.name, marble_2.name, wins, wins
+                )
             }
             Self::HateEachOther(marble_1, marble_2) => {
                 write!(f, "{} and {} hate each other", marble_1.name, marble_2.name)
@@ -219,45 +234,154 @@ impl Display for Engagement {
     }
 }
 
-const OPPOSITES: [(&str, &str); 7] = [
-    ("White Light", "Black Hole"),
-    ("Fireball", "Deep Blue"),
-    ("Mastermind", "Jokester"),
-    ("Giftbringer", "Psycho"),
-    ("Blue's Wife", "White's Brother"),
-    ("Trump Card", "Joe Mama"),
-    ("Homelander", "The Butcher"),
-];
-
-pub fn are_opposites(ball_1: &Ball, ball_2: &Ball) -> bool {
-    OPPOSITES.contains(&(ball_1.get_name(), ball_2.get_name()))
-}
+/// Engagements about a single marble whose claims hold for its record.
+fn get_marble_engagements(
+    marble: &Marble,
+    record: &MarbleRecord,
+    level_id: i64,
+    most_other_wins: usize,
+) -> Vec<Engagement> {
+    let mut engagements = vec![
+        Engagement::JustDoesOwnThing(marble.clone()),
+        Engagement::AllEyes(marble.clone()),
+    ];
 
-pub fn get_opposites(balls: &[Ball]) -> Vec<(&Ball, &Ball)> {
-    let mut opposites = Vec::new();
+    let now = Utc::now();
+    let races = record.races();
+    let wins = record.wins();
+    let level_races = record.level_races(level_id);
+    let level_wins = record.level_wins(level_id);
 
-    for (i, ball_1) in balls.iter().enumerate() {
-        for ball_2 in balls[i + 1..].iter() {
-            if are_opposites(ball_1, ball_2) {
-                opposites.push((ball_1, ball_2));
-            }
+    if wins == 0 && races >= 10 {
+        engagements.push(Engagement::WinToLossTodayTheDay(
+            marble.clone(),
+            record.losses(),
+        ));
+    }
+
+    if record.podium_drought() >= 10 {
+        engagements.push(Engagement::HasntSeenPodium(
+            marble.clone(),
+            record.podium_drought(),
+        ));
+    }
+
+    if races >= 10 && wins > 0 && record.first_or_last_rate() >= 0.5 {
+        engagements.push(Engagement::WinOrCrashOut(marble.clone()));
+    }
+
+    if races >= 10 && record.first_or_last_rate() >= 0.6 {
+        engagements.push(Engagement::EitherFirstOrLast(marble.clone()));
+    }
+
+    if record.win_streak() >= 3 {
+        engagements.push(Engagement::HasntLostInStraight(
+            marble.clone(),
+            record.win_streak(),
+        ));
+    }
+
+    if record.win_streak() >= 2 {
+        engagements.push(Engagement::AnyoneStop(marble.clone()));
+    }
+
+    if record.losing_streak() >= 10 {
+        engagements.push(Engagement::DesperateForAWin(marble.clone()));
+
+        if record.losing_streak() == record.longest_losing_streak() {
+            engagements.push(Engagement::HistoricLosingStreak(
+                marble.clone(),
+                record.losing_streak(),
+            ));
         }
     }
 
-    opposites
+    if record.top_half_drought() >= 10 {
+        engagements.push(Engagement::HasntFinishedTopHalfInAttempts(
+            marble.clone(),
+            record.top_half_drought(),
+        ));
+    }
+
+    if wins > 0 {
+        engagements.push(Engagement::HuntingNthWin(marble.clone(), wins + 1));
+    }
+
+    if level_wins >= 3 && level_wins * 3 >= level_races {
+        engagements.push(Engagement::RunsThisTrack(
+            marble.clone(),
+            level_wins,
+            level_races,
+        ));
+    }
+
+    if level_races >= 2 && level_wins == level_races {
+        engagements.push(Engagement::NeverLostHere(marble.clone(), level_races));
+    }
+
+    if record.close_wins() >= 3 && record.close_losses() == 0 {
+        engagements.push(Engagement::DoesntLoseCloseRaces(
+            marble.clone(),
+            record.close_wins(),
+        ));
+    }
+
+    if races >= 10 && wins * 3 >= races {
+        engagements.push(Engagement::AlwaysFindsAWay(marble.clone()));
+    }
+
+    if races >= 10 && record.place_deviation() >= 0.35 {
+        engagements.push(Engagement::CompletelyUnpredictable(marble.clone()));
+    }
+
+    if races >= 10 && record.place_deviation() <= 0.15 {
+        engagements.push(Engagement::SuspiciouslyConsistent(marble.clone()));
+    }
+
+    if record.second_place_streak() >= 2 {
+        engagements.push(Engagement::ChokedLastNRedemption(
+            marble.clone(),
+            record.second_place_streak(),
+        ));
+    }
+
+    if let (Some(days_since_podium), Some(days_since_last_race)) = (
+        record.days_since_podium(now),
+        record.days_since_last_race(now),
+    ) && days_since_podium >= 14
+        && days_since_last_race <= 7
+    {
+        engagements.push(Engagement::HasntBeenRelevant(
+            marble.clone(),
+            days_since_podium / 7,
+        ));
+    }
+
+    if record.losing_streak() >= 5 && record.recent_improvement(5) >= 1.0 {
+        engagements.push(Engagement::QuietlyClimbing(marble.clone()));
+    }
+
+    if record.broken_losing_streak() >= 10 {
+        engagements.push(Engagement::BackAfterBrutalLosingStreak(
+            marble.clone(),
+            record.broken_losing_streak(),
+        ));
+    }
+
+    if record.won_last_race() && wins == most_other_wins {
+        engagements.push(Engagement::WinsAgainTakesLead(marble.clone()));
+    }
+
+    engagements
 }
 
 pub async fn get_engagement_for_scene(
-    _pool: &SqlitePool,
+    pool: &SqlitePool,
     rng: &mut impl Rng,
     scene: &Scene,
+    context: &RaceContext,
 ) -> sqlx::Result<String> {
     let mut possible_engagements = vec![
-        Engagement::LastPlaceOverall,
-        Engagement::DecidesTheChampion,
-        Engagement::OneRaceLeft,
-        Engagement::WinnerStays,
-        Engagement::WhoSurvives,
         Engagement::NoOneExpected,
         Engagement::FinalCornerDecides,
         Engagement::BiggestUpsetInHistory,
@@ -267,58 +391,155 @@ pub async fn get_engagement_for_scene(
         Engagement::DontBlink,
     ];
 
+    let win_counts = DbRaceMarble::get_win_counts(pool).await?;
+    let head_to_heads = DbRaceMarble::get_head_to_heads(pool).await?;
+    let announcements = get_announcements(pool, Utc::now()).await?;
+    let rivalries = DbRivalry::get_all(pool)
+        .await?
+        .into_iter()
+        .map(Rivalry::from)
+        .collect::<Vec<Rivalry>>();
+
+    let mut entrants = Vec::new();
+
     for ball in scene.get_balls() {
         let marble = Marble {
             name: ball.get_name().to_string(),
-            pronouns: Pronouns::from_name(ball.get_name()),
+            pronouns: ball.get_pronouns().clone(),
+        };
+
+        let Some(db_marble) = DbMarble::get_by_name(pool, ball.get_name()).await? else {
+            continue;
         };
 
-        possible_engagements.append(&mut vec![
-            Engagement::WinToLossTodayTheDay(marble.clone()),
-            Engagement::HasntSeenPodium(marble.clone()),
-            Engagement::WinOrCrashOut(marble.clone()),
-            Engagement::HasntLostInStraight(marble.clone()),
-            Engagement::HistoricLosingStreak(marble.clone()),
-            Engagement::HasntFinishedTopHalfInAttempts(marble.clone()),
-            Engagement::HuntingNthWin(marble.clone()),
-            Engagement::RunsThisTrack(marble.clone()),
-            Engagement::NeverLostHere(marble.clone()),
-            Engagement::DoesntLoseCloseRaces(marble.clone()),
-            Engagement::AlwaysFindsAWay(marble.clone()),
-            Engagement::CompletelyUnpredictable(marble.clone()),
-            Engagement::SuspiciouslyConsistent(marble.clone()),
-            Engagement::EitherFirstOrLast(marble.clone()),
-            Engagement::JustDoesOwnThing(marble.clone()),
-            Engagement::ChokedLastNRedemption(marble.clone()),
-            Engagement::DesperateForAWin(marble.clone()),
-            Engagement::HasntBeenRelevant(marble.clone()),
-            Engagement::QuietlyClimbing(marble.clone()),
-            Engagement::BackAfterBrutalLosingStreak(marble.clone()),
-            Engagement::WinsAgainTakesLead(marble.clone()),
-            Engagement::AllEyes(marble.clone()),
-            Engagement::AnyoneStop(marble.clone()),
-        ]);
+        let record = MarbleRecord::new(db_marble.get_results(pool).await?);
+
+        let most_other_wins = win_counts
+            .iter()
+            .filter(|win_count| win_count.marble_id != db_marble.id)
+            .map(|win_count| win_count.wins as usize)
+            .max()
+            .unwrap_or_default();
+
+        possible_engagements.append(&mut get_marble_engagements(
+            &marble,
+            &record,
+            scene.get_level_id(),
+            most_other_wins,
+        ));
+
+        if let Some(announcement) = announcements
+            .iter()
+            .find(|announcement| announcement.marble_id == db_marble.id)
+        {
+            possible_engagements.push(if announcement.is_promotion() {
+                Engagement::JustPromoted(marble.clone(), announcement.to_league_name.clone())
+            } else {
+                Engagement::JustRelegated(marble.clone(), announcement.to_league_name.clone())
+            });
+        }
+
+        entrants.push((marble, db_marble));
     }
 
-    let opposites = get_opposites(scene.get_balls());
+    for (i, (marble_1, db_marble_1)) in entrants.iter().enumerate() {
+        for (marble_2, db_marble_2) in entrants[i + 1..].iter() {
+            let (wins, losses) = head_to_heads
+                .iter()
+                .find(|head_to_head| {
+                    head_to_head.marble_id == db_marble_1.id
+                        && head_to_head.opponent_id == db_marble_2.id
+                })
+                .map_or((0, 0), |head_to_head| {
+                    (head_to_head.wins, head_to_head.losses)
+                });
 
-    for (ball_1, ball_2) in opposites {
-        let marble_1 = Marble {
-            name: ball_1.get_name().to_string(),
-            pronouns: Pronouns::from_name(ball_1.get_name()),
-        };
+            if wins == losses && wins >= 2 {
+                possible_engagements.push(Engagement::VsTied(
+                    marble_1.clone(),
+                    marble_2.clone(),
+                    wins as usize,
+                ));
+            }
 
-        let marble_2 = Marble {
-            name: ball_2.get_name().to_string(),
-            pronouns: Pronouns::from_name(ball_2.get_name()),
-        };
+            let Some(rivalry) = rivalries
+                .iter()
+                .find(|rivalry| rivalry.involves(db_marble_1.id, db_marble_2.id))
+            else {
+                continue;
+            };
+
+            possible_engagements.append(&mut vec![
+                Engagement::HateEachOther(marble_1.clone(), marble_2.clone()),
+                Engagement::VsControl(marble_1.clone(), marble_2.clone()),
+            ]);
+
+            if rivalry.kind == RivalryKind::Opposites {
+                possible_engagements.push(Engagement::VsOppositeForces(
+                    marble_1.clone(),
+                    marble_2.clone(),
+                ));
+            }
+
+            if wins + losses >= 5 {
+                if wins >= 2 * losses {
+                    possible_engagements.push(Engagement::AlwaysSabotages(
+                        marble_1.clone(),
+                        marble_2.clone(),
+                    ));
+                } else if losses >= 2 * wins {
+                    possible_engagements.push(Engagement::AlwaysSabotages(
+                        marble_2.clone(),
+                        marble_1.clone(),
+                    ));
+                }
+            }
+        }
+    }
+
+    if context.finale
+        && let Some(season) = &context.maybe_season
+    {
+        possible_engagements.push(Engagement::OneRaceLeft);
+
+        let standings = season.get_standings(pool).await?;
+        let most_points_per_race = season
+            .get_points(pool)
+            .await?
+            .first()
+            .copied()
+            .unwrap_or_default();
+
+        if let Some(leader) = standings.first() {
+            let contenders = standings
+                .iter()
+                .filter(|standing| standing.points + most_points_per_race >= leader.points)
+                .collect::<Vec<_>>();
+
+            let all_contenders_racing = contenders.iter().all(|contender| {
+                entrants
+                    .iter()
+                    .any(|(_, db_marble)| db_marble.id == contender.marble_id)
+            });
+
+            if contenders.len() >= 2 && all_contenders_racing {
+                possible_engagements.push(Engagement::DecidesTheChampion);
+            }
+        }
+    }
 
-        possible_engagements.append(&mut vec![
-            Engagement::HateEachOther(marble_1.clone(), marble_2.clone()),
-            Engagement::VsControl(marble_1.clone(), marble_2.clone()),
-            Engagement::VsOppositeForces(marble_1.clone(), marble_2.clone()),
-            Engagement::AlwaysSabotages(marble_1.clone(), marble_2.clone()),
-        ]);
+    match context.maybe_elimination {
+        Some(Elimination::OutOfBounds) => possible_engagements.push(Engagement::LastOneInside),
+        Some(_) => possible_engagements.push(Engagement::LastPlaceIsOut),
+        None => {}
+    }
+
+    if let Some(bracket) = &context.maybe_bracket {
+        if bracket.is_final() {
+            possible_engagements.push(Engagement::ForTheTournament);
+        } else {
+            possible_engagements.push(Engagement::OnlyTopAdvance(bracket.advance));
+        }
     }
 
     let engagement = possible_engagements.choose(rng).unwrap();
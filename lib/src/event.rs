@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use crate::wall::hazard_wall::Hazard;
+
+/// Something noteworthy that happened during a scene update.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RaceEvent {
+    Finished {
+        ball: usize,
+        place: usize,
+        time: Duration,
+    },
+    /// `place` counts from 0 like `Finished`, so the first marble out of a six marble race is in
+    /// place 5.
+    Eliminated {
+        ball: usize,
+        place: usize,
+        time: Duration,
+    },
+    LapCompleted {
+        ball: usize,
+        lap: usize,
+        time: Duration,
+    },
+    /// A ball landed on a goal in a scoring race.
+    Scored {
+        ball: usize,
+        score: u32,
+        time: Duration,
+    },
+    Special {
+        ball: usize,
+        text: String,
+    },
+    /// A ball ran into a hazard zone.
+    Hazard {
+        ball: usize,
+        hazard: Hazard,
+        time: Duration,
+    },
+    /// A trapdoor dropped open, either on its timer or from the ball that hit it last.
+    TrapdoorOpened {
+        maybe_ball: Option<usize>,
+        time: Duration,
+    },
+}
+
+impl RaceEvent {
+    /// The index of the ball the event is about, if it's about one.
+    pub fn get_ball(&self) -> Option<usize> {
+        match self {
+            Self::Finished { ball, .. }
+            | Self::Eliminated { ball, .. }
+            | Self::LapCompleted { ball, .. }
+            | Self::Scored { ball, .. }
+            | Self::Special { ball, .. }
+            | Self::Hazard { ball, .. } => Some(*ball),
+            Self::TrapdoorOpened { maybe_ball, .. } => *maybe_ball,
+        }
+    }
+
+    pub fn get_ball_mut(&mut self) -> Option<&mut usize> {
+        match self {
+            Self::Finished { ball, .. }
+            | Self::Eliminated { ball, .. }
+            | Self::LapCompleted { ball, .. }
+            | Self::Scored { ball, .. }
+            | Self::Special { ball, .. }
+            | Self::Hazard { ball, .. } => Some(ball),
+            Self::TrapdoorOpened { maybe_ball, .. } => maybe_ball.as_mut(),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use glam::dvec2;
+use keyframe::AnimationSequence;
+use mint::Vector2;
+use palette::Srgba;
+use render_agnostic::Renderer;
+
+use crate::{graphic::Graphic, tournament::Bracket};
+
+/// The tournament name and what is at stake in this heat, e.g. "Semifinal 2 — winner advances".
+#[derive(Clone)]
+pub struct BracketBanner {
+    pub time: f64,
+    pub start: f64,
+    pub bracket: Bracket,
+    pub origin: AnimationSequence<Vector2<f64>>,
+    pub viewport: (f64, f64),
+}
+
+impl BracketBanner {
+    pub fn new(
+        origin: AnimationSequence<Vector2<f64>>,
+        start: f64,
+        bracket: Bracket,
+        viewport: (f64, f64),
+    ) -> Self {
+        Self {
+            time: 0.0,
+            start,
+            bracket,
+            origin,
+            viewport,
+        }
+    }
+}
+
+impl Graphic for BracketBanner {
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        let origin = self.origin();
+
+        renderer.render_text_outline(
+            &self.bracket.tournament_name,
+            origin,
+            anchor2d::CGB,
+            18.0,
+            1.0,
+            Srgba::new(0.75, 0.75, 0.75, 1.0),
+            Srgba::new(0.0, 0.0, 0.0, 1.0),
+        );
+
+        renderer.render_text_outline(
+            &self.bracket.to_string(),
+            dvec2(origin.x, origin.y + 30.0),
+            anchor2d::CGB,
+            26.0,
+            1.0,
+            Srgba::new(1.0, 0.85, 0.2, 1.0),
+            Srgba::new(0.0, 0.0, 0.0, 1.0),
+        );
+    }
+
+    fn origin_sequence(&self) -> &AnimationSequence<Vector2<f64>> {
+        &self.origin
+    }
+
+    fn origin_sequence_mut(&mut self) -> &mut AnimationSequence<Vector2<f64>> {
+        &mut self.origin
+    }
+
+    fn time(&self) -> f64 {
+        self.time
+    }
+
+    fn set_time(&mut self, new_time: f64) {
+        self.time = new_time;
+    }
+
+    fn visible(&self) -> bool {
+        self.time() >= self.start
+    }
+}
@@ -0,0 +1,415 @@
+use std::collections::HashMap;
+
+use glam::dvec2;
+use keyframe::AnimationSequence;
+use mint::Vector2;
+use palette::Srgba;
+use rand::seq::IndexedRandom;
+use render_agnostic::Renderer;
+
+use crate::{
+    event::RaceEvent, graphic::Graphic, scene::Scene, stats::ordinal, wall::hazard_wall::Hazard,
+};
+
+const CAPTION_SECONDS: f64 = 3.0;
+const FADE_SECONDS: f64 = 0.25;
+const LINE_HEIGHT: f64 = 36.0;
+const PHOTO_FINISH_SECONDS: f64 = 0.1;
+const LAST_PLACE_SECONDS: f64 = 3.0;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum CaptionKind {
+    LeadChange,
+    Special,
+    Win,
+    PhotoFinish,
+    Climbing,
+    Elimination,
+    Lap,
+    Bucket,
+    Hazard,
+    Trapdoor,
+}
+
+impl CaptionKind {
+    /// Minimum number of seconds between two captions of this kind.
+    fn cooldown(&self) -> f64 {
+        match self {
+            Self::LeadChange => 3.0,
+            Self::Special => 0.5,
+            Self::Win => 0.0,
+            Self::PhotoFinish => 1.0,
+            Self::Climbing => 8.0,
+            Self::Elimination => 0.0,
+            Self::Lap => 5.0,
+            Self::Bucket => 1.0,
+            Self::Hazard => 2.0,
+            Self::Trapdoor => 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Caption {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+#[derive(Clone)]
+pub struct Commentary {
+    pub time: f64,
+    pub start: f64,
+    pub max_on_screen: usize,
+    pub origin: AnimationSequence<Vector2<f64>>,
+    pub viewport: (f64, f64),
+    captions: Vec<Caption>,
+    last_said: HashMap<CaptionKind, f64>,
+    maybe_leader: Option<usize>,
+    maybe_last_place: Option<(usize, f64)>,
+}
+
+impl Commentary {
+    pub fn new(
+        origin: AnimationSequence<Vector2<f64>>,
+        start: f64,
+        max_on_screen: usize,
+        viewport: (f64, f64),
+    ) -> Self {
+        Self {
+            time: 0.0,
+            start,
+            max_on_screen,
+            origin,
+            viewport,
+            captions: Vec::new(),
+            last_said: HashMap::new(),
+            maybe_leader: None,
+            maybe_last_place: None,
+        }
+    }
+
+    /// Every caption said so far, including ones that are no longer on screen.
+    pub fn get_captions(&self) -> &Vec<Caption> {
+        &self.captions
+    }
+
+    pub fn to_srt(&self) -> String {
+        self.captions
+            .iter()
+            .enumerate()
+            .map(|(index, caption)| {
+                format!(
+                    "{}\n{} --> {}\n{}\n",
+                    index + 1,
+                    format_srt_time(caption.start),
+                    format_srt_time(caption.end),
+                    caption.text
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn say(&mut self, kind: CaptionKind, text: String) {
+        if self
+            .last_said
+            .get(&kind)
+            .is_some_and(|last_said| self.time - last_said < kind.cooldown())
+        {
+            return;
+        }
+
+        self.last_said.insert(kind, self.time);
+
+        let time = self.time;
+        let mut on_screen = self
+            .captions
+            .iter_mut()
+            .filter(|caption| caption.end > time)
+            .collect::<Vec<&mut Caption>>();
+
+        let overflow = (on_screen.len() + 1).saturating_sub(self.max_on_screen);
+
+        for caption in on_screen.iter_mut().take(overflow) {
+            caption.end = time;
+        }
+
+        self.captions.push(Caption {
+            start: time,
+            end: time + CAPTION_SECONDS,
+            text,
+        });
+    }
+}
+
+fn format_srt_time(seconds: f64) -> String {
+    let milliseconds = (seconds.max(0.0) * 1000.0).round() as u64;
+
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        milliseconds / 3_600_000,
+        (milliseconds / 60_000) % 60,
+        (milliseconds / 1000) % 60,
+        milliseconds % 1000
+    )
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+impl Graphic for Commentary {
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        let origin = self.origin();
+
+        let on_screen = self
+            .captions
+            .iter()
+            .filter(|caption| caption.start <= self.time && self.time < caption.end)
+            .collect::<Vec<&Caption>>();
+
+        for (line, caption) in on_screen.iter().rev().enumerate() {
+            let alpha = ((self.time - caption.start) / FADE_SECONDS)
+                .min((caption.end - self.time) / FADE_SECONDS)
+                .clamp(0.0, 1.0) as f32;
+
+            renderer.render_text_outline(
+                &caption.text,
+                dvec2(origin.x, origin.y - LINE_HEIGHT * line as f64),
+                anchor2d::CGB,
+                24.0,
+                1.5,
+                Srgba::new(1.0, 1.0, 1.0, alpha),
+                Srgba::new(0.0, 0.0, 0.0, alpha),
+            );
+        }
+    }
+
+    fn observe(&mut self, scene: &Scene) {
+        if self.time < self.start {
+            return;
+        }
+
+        for event in scene.get_events() {
+            match event {
+                RaceEvent::Special { ball, text } => {
+                    if text != "nothing" {
+                        let name = scene.get_balls()[*ball].get_name();
+                        self.say(CaptionKind::Special, format!("{} goes {}", name, text));
+                    }
+                }
+                RaceEvent::Eliminated { ball, place, .. } => {
+                    let name = scene.get_balls()[*ball].get_name().to_string();
+
+                    let lines = [
+                        format!("{} is out!", name),
+                        format!("That's it for {}!", name),
+                        format!("{} goes out in {} place", name, ordinal(place + 1)),
+                    ];
+
+                    self.say(
+                        CaptionKind::Elimination,
+                        lines.choose(&mut rand::rng()).unwrap().clone(),
+                    );
+                }
+                RaceEvent::LapCompleted { ball, lap, .. } => {
+                    let is_leader = scene.get_standings().first() == Some(ball);
+
+                    if let Some(laps) = scene.get_laps()
+                        && is_leader
+                        && *lap < laps
+                    {
+                        let name = scene.get_balls()[*ball].get_name().to_string();
+
+                        let text = if lap + 1 == laps {
+                            format!("{} starts the final lap!", name)
+                        } else {
+                            format!("{} leads after lap {}", name, lap)
+                        };
+
+                        self.say(CaptionKind::Lap, text);
+                    }
+                }
+                RaceEvent::Scored { ball, score, .. } => {
+                    let is_top_bucket = scene
+                        .get_walls()
+                        .iter()
+                        .filter_map(|wall| wall.get_score())
+                        .max()
+                        .is_some_and(|top_score| *score == top_score);
+
+                    if is_top_bucket {
+                        let name = scene.get_balls()[*ball].get_name();
+                        self.say(
+                            CaptionKind::Bucket,
+                            format!("{} hits the {} bucket!", name, score),
+                        );
+                    }
+                }
+                RaceEvent::Hazard { ball, hazard, .. } => {
+                    let name = scene.get_balls()[*ball].get_name();
+
+                    let text = match hazard {
+                        Hazard::Lava { respawn: false } => format!("{} falls into the lava!", name),
+                        Hazard::Spikes { respawn: false } => format!("{} is skewered!", name),
+                        Hazard::Lava { respawn: true } | Hazard::Spikes { respawn: true } => {
+                            format!(
+                                "{} hits the {}, back to the checkpoint!",
+                                name,
+                                hazard.get_name()
+                            )
+                        }
+                        Hazard::Mud { .. } => format!("{} gets stuck in the mud", name),
+                    };
+
+                    self.say(CaptionKind::Hazard, text);
+                }
+                RaceEvent::TrapdoorOpened { maybe_ball, .. } => {
+                    let text = match maybe_ball {
+                        Some(ball) => format!(
+                            "{} breaks through the trapdoor!",
+                            scene.get_balls()[*ball].get_name()
+                        ),
+                        None => String::from("The trapdoor drops open!"),
+                    };
+
+                    self.say(CaptionKind::Trapdoor, text);
+                }
+                RaceEvent::Finished { .. } if scene.get_drops().is_some() => {
+                    if scene.all_won()
+                        && let Some(winner) = scene.get_standings().first()
+                    {
+                        let name = scene.get_balls()[*winner].get_name();
+                        let total_score = scene.get_total_score(*winner);
+
+                        self.say(
+                            CaptionKind::Win,
+                            format!("{} wins with {} points!", name, total_score),
+                        );
+                    }
+                }
+                RaceEvent::Finished { ball, place, time } => {
+                    let name = scene.get_balls()[*ball].get_name().to_string();
+                    let pronouns = scene.get_balls()[*ball].get_pronouns();
+
+                    let is_photo_finish = place.checked_sub(1).is_some_and(|previous| {
+                        scene
+                            .get_win_times()
+                            .get(previous)
+                            .is_some_and(|previous_time| {
+                                (*time - *previous_time).as_secs_f64() <= PHOTO_FINISH_SECONDS
+                            })
+                    });
+
+                    if is_photo_finish {
+                        self.say(CaptionKind::PhotoFinish, String::from("Photo finish!"));
+                    } else if *place == 0 {
+                        let lines = [
+                            format!("{} wins! Nobody could catch {}!", name, pronouns.object),
+                            format!(
+                                "{} takes it! {} never looked back!",
+                                name,
+                                capitalize(&pronouns.subject)
+                            ),
+                        ];
+
+                        self.say(
+                            CaptionKind::Win,
+                            lines.choose(&mut rand::rng()).unwrap().clone(),
+                        );
+                    }
+                }
+            }
+        }
+
+        let standings = scene.get_standings();
+
+        if !scene.any_won()
+            && let Some(leader) = standings.first().copied()
+        {
+            if self.maybe_leader.is_some_and(|previous| previous != leader) {
+                let name = scene.get_balls()[leader].get_name().to_string();
+                let pronouns = scene.get_balls()[leader].get_pronouns();
+
+                let lines = [
+                    format!("{} takes the lead!", name),
+                    format!("{} moves to the front!", name),
+                    format!(
+                        "{} is in front! Can anyone catch {}?",
+                        name, pronouns.object
+                    ),
+                ];
+
+                self.say(
+                    CaptionKind::LeadChange,
+                    lines.choose(&mut rand::rng()).unwrap().clone(),
+                );
+            }
+
+            self.maybe_leader = Some(leader);
+        }
+
+        if let Some(last) = standings
+            .iter()
+            .rev()
+            .find(|index| !scene.get_balls()[**index].is_eliminated())
+            .copied()
+        {
+            match self.maybe_last_place {
+                Some((previous, since)) if previous != last => {
+                    // Split copies being removed can leave the previous index past the end
+                    if self.time - since >= LAST_PLACE_SECONDS
+                        && !scene.get_winners().contains(&previous)
+                        && let Some(previous_ball) = scene.get_balls().get(previous)
+                    {
+                        let name = previous_ball.get_name().to_string();
+                        let pronouns = previous_ball.get_pronouns();
+
+                        let lines = [
+                            format!("Last place {} is climbing!", name),
+                            format!(
+                                "{} was dead last, now look at {} go!",
+                                name, pronouns.object
+                            ),
+                        ];
+
+                        self.say(
+                            CaptionKind::Climbing,
+                            lines.choose(&mut rand::rng()).unwrap().clone(),
+                        );
+                    }
+
+                    self.maybe_last_place = Some((last, self.time));
+                }
+                Some(_) => {}
+                None => self.maybe_last_place = Some((last, self.time)),
+            }
+        }
+    }
+
+    fn origin_sequence(&self) -> &AnimationSequence<Vector2<f64>> {
+        &self.origin
+    }
+
+    fn origin_sequence_mut(&mut self) -> &mut AnimationSequence<Vector2<f64>> {
+        &mut self.origin
+    }
+
+    fn time(&self) -> f64 {
+        self.time
+    }
+
+    fn set_time(&mut self, new_time: f64) {
+        self.time = new_time;
+    }
+
+    fn visible(&self) -> bool {
+        self.time() >= self.start
+    }
+}
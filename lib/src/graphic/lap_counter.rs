@@ -0,0 +1,120 @@
+use glam::dvec2;
+use keyframe::AnimationSequence;
+use mint::Vector2;
+use palette::Srgba;
+use render_agnostic::Renderer;
+
+use crate::{graphic::Graphic, scene::Scene};
+
+/// The leader's lap and the fastest lap so far in a circuit race.
+#[derive(Clone)]
+pub struct LapCounter {
+    pub time: f64,
+    pub start: f64,
+    pub origin: AnimationSequence<Vector2<f64>>,
+    pub viewport: (f64, f64),
+    lap: usize,
+    laps: usize,
+    maybe_best_lap: Option<(String, Srgba, f64)>,
+}
+
+impl LapCounter {
+    pub fn new(
+        origin: AnimationSequence<Vector2<f64>>,
+        start: f64,
+        laps: usize,
+        viewport: (f64, f64),
+    ) -> Self {
+        Self {
+            time: 0.0,
+            start,
+            origin,
+            viewport,
+            lap: 1,
+            laps,
+            maybe_best_lap: None,
+        }
+    }
+}
+
+impl Graphic for LapCounter {
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        let origin = self.origin();
+
+        renderer.render_text_outline(
+            &format!("Lap {}/{}", self.lap, self.laps),
+            origin,
+            anchor2d::RGB,
+            28.0,
+            1.0,
+            Srgba::new(1.0, 1.0, 1.0, 1.0),
+            Srgba::new(0.0, 0.0, 0.0, 1.0),
+        );
+
+        if let Some((name, color, seconds)) = &self.maybe_best_lap {
+            renderer.render_text_outline(
+                "Best lap",
+                dvec2(origin.x, origin.y + 22.0),
+                anchor2d::RGB,
+                14.0,
+                1.0,
+                Srgba::new(0.75, 0.75, 0.75, 1.0),
+                Srgba::new(0.0, 0.0, 0.0, 1.0),
+            );
+
+            renderer.render_text_outline(
+                &format!("{} {:.3}s", name, seconds),
+                dvec2(origin.x, origin.y + 40.0),
+                anchor2d::RGB,
+                16.0,
+                1.0,
+                *color,
+                Srgba::new(0.0, 0.0, 0.0, 1.0),
+            );
+        }
+    }
+
+    fn observe(&mut self, scene: &Scene) {
+        if let Some(leader) = scene.get_standings().first() {
+            self.lap = (scene.get_lap_trackers()[*leader].get_laps() + 1).min(self.laps);
+        }
+
+        self.maybe_best_lap = scene
+            .get_lap_trackers()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, lap_tracker)| {
+                lap_tracker.get_best_lap().map(|best_lap| (index, best_lap))
+            })
+            .min_by_key(|(_, best_lap)| *best_lap)
+            .map(|(index, best_lap)| {
+                let ball = &scene.get_balls()[index];
+
+                (
+                    ball.get_name().to_string(),
+                    ball.get_name_color(),
+                    best_lap.as_secs_f64(),
+                )
+            });
+    }
+
+    fn origin_sequence(&self) -> &AnimationSequence<Vector2<f64>> {
+        &self.origin
+    }
+
+    fn origin_sequence_mut(&mut self) -> &mut AnimationSequence<Vector2<f64>> {
+        &mut self.origin
+    }
+
+    fn time(&self) -> f64 {
+        self.time
+    }
+
+    fn set_time(&mut self, new_time: f64) {
+        self.time = new_time;
+    }
+
+    fn visible(&self) -> bool {
+        self.time() >= self.start
+    }
+}
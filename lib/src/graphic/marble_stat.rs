@@ -1,5 +1,5 @@
 use api::marble::Marble;
-use database::marble::DbMarble;
+use database::{marble::DbMarble, rating::DbRating};
 use glam::DVec2;
 use keyframe::AnimationSequence;
 use mint::Vector2;
@@ -8,13 +8,15 @@ use rand::{RngExt, rng};
 use render_agnostic::Renderer;
 use sqlx::SqlitePool;
 
-use crate::{graphic::Graphic, username::generate_username};
+use crate::{graphic::Graphic, handicap::Handicap, username::generate_username};
 
 #[derive(Clone)]
 pub struct MarbleStat {
     pub time: f64,
     pub marble: Marble,
     wins: usize,
+    maybe_rating: Option<DbRating>,
+    maybe_handicap: Option<Handicap>,
     pub origin: AnimationSequence<Vector2<f64>>,
     pub viewport: (f64, f64),
     pub maybe_sponsor_name: Option<String>,
@@ -25,6 +27,8 @@ impl MarbleStat {
         pool: &SqlitePool,
         origin: AnimationSequence<Vector2<f64>>,
         marble_name: String,
+        maybe_league_id: Option<i64>,
+        maybe_handicap: Option<Handicap>,
         viewport: (f64, f64),
     ) -> Self {
         let db_marble = DbMarble::get_by_name(pool, &marble_name)
@@ -34,6 +38,10 @@ impl MarbleStat {
 
         let db_race_marbles = db_marble.get_race_marbles(pool).await.unwrap();
 
+        let maybe_rating = DbRating::get(pool, db_marble.id, maybe_league_id)
+            .await
+            .unwrap();
+
         Self {
             time: 0.0,
             marble: Marble::from(db_marble),
@@ -41,6 +49,8 @@ impl MarbleStat {
                 .iter()
                 .filter(|db_race_marble| db_race_marble.place == 1)
                 .count(),
+            maybe_rating,
+            maybe_handicap,
             origin,
             viewport,
             maybe_sponsor_name: rng()
@@ -62,11 +72,17 @@ impl Graphic for MarbleStat {
             Srgba::new(0.0, 0.0, 0.0, 1.0),
         );
 
+        let sponsor_text = if let Some(sponsor_name) = &self.maybe_sponsor_name {
+            format!("Sponsored by {}!", sponsor_name)
+        } else {
+            format!("Sponsor {}, link in bio!", self.marble.name)
+        };
+
         renderer.render_text_outline(
-            &if let Some(sponsor_name) = &self.maybe_sponsor_name {
-                format!("Sponsored by {}!", sponsor_name)
+            &if let Some(rating) = &self.maybe_rating {
+                format!("Rated {:.0} | {}", rating.rating, sponsor_text)
             } else {
-                format!("Sponsor {}, link in bio!", self.marble.name)
+                sponsor_text
             },
             self.origin() + DVec2::Y * 36.0,
             anchor2d::CGC,
@@ -75,6 +91,22 @@ impl Graphic for MarbleStat {
             Srgba::new(1.0, 1.0, 1.0, 1.0),
             Srgba::new(0.0, 0.0, 0.0, 1.0),
         );
+
+        if let Some(handicap) = &self.maybe_handicap {
+            renderer.render_text_outline(
+                &handicap.to_string(),
+                self.origin() + DVec2::Y * 64.0,
+                anchor2d::CGC,
+                20.0,
+                1.0,
+                if handicap.is_penalty() {
+                    Srgba::new(1.0, 0.5, 0.2, 1.0)
+                } else {
+                    Srgba::new(0.3, 1.0, 0.4, 1.0)
+                },
+                Srgba::new(0.0, 0.0, 0.0, 1.0),
+            );
+        }
     }
 
     fn origin_sequence(&self) -> &AnimationSequence<Vector2<f64>> {
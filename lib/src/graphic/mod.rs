@@ -1,19 +1,27 @@
+use std::any::Any;
+
 use dyn_clone::DynClone;
 use glam::DVec2;
 use keyframe::AnimationSequence;
 use mint::Vector2;
 use render_agnostic::Renderer;
 
-use crate::rendering::Render;
+use crate::{rendering::Render, scene::Scene};
 
+pub mod bracket;
+pub mod commentary;
 pub mod countdown;
 pub mod engagement;
+pub mod lap_counter;
 pub mod marble_stat;
+pub mod season_rank;
 pub mod special_message;
+pub mod standings;
+pub mod team_scoreboard;
 
 dyn_clone::clone_trait_object!(Graphic);
 
-pub trait Graphic: Send + Sync + DynClone {
+pub trait Graphic: Any + Send + Sync + DynClone {
     fn draw(&self, renderer: &mut dyn Renderer);
 
     fn update(&mut self, dt: f64) {
@@ -22,6 +30,9 @@ pub trait Graphic: Send + Sync + DynClone {
         self.origin_sequence_mut().advance_to(time);
     }
 
+    /// Called every frame with the latest scene, for graphics that follow the race.
+    fn observe(&mut self, _scene: &Scene) {}
+
     fn time(&self) -> f64;
 
     fn set_time(&mut self, new_time: f64);
@@ -0,0 +1,158 @@
+use api::marble::Marble;
+use database::{marble::DbMarble, season::DbSeason};
+use glam::{DVec2, dvec2};
+use keyframe::AnimationSequence;
+use mint::Vector2;
+use palette::Srgba;
+use render_agnostic::Renderer;
+use sqlx::SqlitePool;
+
+use crate::{graphic::Graphic, stats::ordinal};
+
+const WIDTH: f64 = 320.0;
+const ROW_HEIGHT: f64 = 24.0;
+const MAX_ROWS: usize = 6;
+
+#[derive(Clone)]
+struct SeasonRankRow {
+    place: Option<usize>,
+    name: String,
+    color: Srgba,
+    points: i64,
+}
+
+/// Where the marbles in the race currently sit in their league's season, shown before the start.
+#[derive(Clone)]
+pub struct SeasonRank {
+    pub time: f64,
+    pub start: f64,
+    pub length: f64,
+    pub title: String,
+    pub origin: AnimationSequence<Vector2<f64>>,
+    pub viewport: (f64, f64),
+    rows: Vec<SeasonRankRow>,
+}
+
+impl SeasonRank {
+    pub async fn new(
+        pool: &SqlitePool,
+        origin: AnimationSequence<Vector2<f64>>,
+        start: f64,
+        length: f64,
+        season: &DbSeason,
+        marble_names: &[String],
+        viewport: (f64, f64),
+    ) -> sqlx::Result<Self> {
+        let standings = season.get_standings(pool).await?;
+
+        let mut rows = Vec::new();
+
+        for marble_name in marble_names {
+            let Some(db_marble) = DbMarble::get_by_name(pool, marble_name).await? else {
+                continue;
+            };
+
+            let maybe_place = standings
+                .iter()
+                .position(|standing| standing.marble_id == db_marble.id);
+
+            rows.push(SeasonRankRow {
+                place: maybe_place.map(|place| place + 1),
+                points: maybe_place
+                    .map(|place| standings[place].points)
+                    .unwrap_or_default(),
+                name: db_marble.name.clone(),
+                color: Marble::from(db_marble).color,
+            });
+        }
+
+        rows.sort_by_key(|row| row.place.unwrap_or(usize::MAX));
+        rows.truncate(MAX_ROWS);
+
+        Ok(Self {
+            time: 0.0,
+            start,
+            length,
+            title: season.name.clone(),
+            origin,
+            viewport,
+            rows,
+        })
+    }
+}
+
+impl Graphic for SeasonRank {
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        let origin = self.origin();
+        let top_left = origin - dvec2(WIDTH / 2.0, 0.0);
+
+        renderer.render_rectangle(
+            top_left,
+            WIDTH,
+            ROW_HEIGHT * (self.rows.len() + 1) as f64 + 8.0,
+            DVec2::ZERO,
+            0.0,
+            Srgba::new(0.0, 0.0, 0.0, 0.5),
+        );
+
+        renderer.render_text_outline(
+            &self.title,
+            dvec2(origin.x, top_left.y + ROW_HEIGHT - 4.0),
+            anchor2d::CGB,
+            18.0,
+            1.0,
+            Srgba::new(1.0, 1.0, 1.0, 1.0),
+            Srgba::new(0.0, 0.0, 0.0, 1.0),
+        );
+
+        for (index, row) in self.rows.iter().enumerate() {
+            let baseline = top_left.y + ROW_HEIGHT * (index + 2) as f64 - 4.0;
+
+            renderer.render_text(
+                &row.place.map(ordinal).unwrap_or(String::from("-")),
+                dvec2(top_left.x + 8.0, baseline),
+                anchor2d::LGB,
+                16.0,
+                Srgba::new(0.75, 0.75, 0.75, 1.0),
+            );
+
+            renderer.render_text_outline(
+                &row.name,
+                dvec2(top_left.x + 56.0, baseline),
+                anchor2d::LGB,
+                16.0,
+                1.0,
+                row.color,
+                Srgba::new(0.0, 0.0, 0.0, 1.0),
+            );
+
+            renderer.render_text(
+                &format!("{} pts", row.points),
+                dvec2(top_left.x + WIDTH - 8.0, baseline),
+                anchor2d::RGB,
+                16.0,
+                Srgba::new(1.0, 1.0, 1.0, 1.0),
+            );
+        }
+    }
+
+    fn origin_sequence(&self) -> &AnimationSequence<Vector2<f64>> {
+        &self.origin
+    }
+
+    fn origin_sequence_mut(&mut self) -> &mut AnimationSequence<Vector2<f64>> {
+        &mut self.origin
+    }
+
+    fn time(&self) -> f64 {
+        self.time
+    }
+
+    fn set_time(&mut self, new_time: f64) {
+        self.time = new_time;
+    }
+
+    fn visible(&self) -> bool {
+        self.start <= self.time() && self.time() < self.start + self.length
+    }
+}
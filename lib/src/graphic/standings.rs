@@ -0,0 +1,217 @@
+use glam::{DVec2, dvec2};
+use keyframe::AnimationSequence;
+use mint::Vector2;
+use palette::Srgba;
+use render_agnostic::Renderer;
+
+use crate::{graphic::Graphic, scene::Scene};
+
+const WIDTH: f64 = 240.0;
+const ROW_HEIGHT: f64 = 28.0;
+const SWAP_SPEED: f64 = 8.0;
+
+#[derive(Clone)]
+struct StandingsRow {
+    index: usize,
+    name: String,
+    color: Srgba,
+    maybe_image_name: Option<String>,
+    gap: String,
+    place: usize,
+    position: f64,
+}
+
+#[derive(Clone)]
+pub struct Standings {
+    pub time: f64,
+    pub start: f64,
+    pub count: usize,
+    pub origin: AnimationSequence<Vector2<f64>>,
+    pub viewport: (f64, f64),
+    rows: Vec<StandingsRow>,
+}
+
+impl Standings {
+    pub fn new(
+        origin: AnimationSequence<Vector2<f64>>,
+        start: f64,
+        count: usize,
+        viewport: (f64, f64),
+    ) -> Self {
+        Self {
+            time: 0.0,
+            start,
+            count,
+            origin,
+            viewport,
+            rows: Vec::new(),
+        }
+    }
+}
+
+fn format_race_time(seconds: f64) -> String {
+    format!(
+        "{:02}:{:02}.{:03}",
+        (seconds / 60.0).floor(),
+        (seconds % 60.0).floor(),
+        ((seconds % 1.0) * 1000.0).floor()
+    )
+}
+
+impl Graphic for Standings {
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        let origin = self.origin();
+
+        // Leave out the rows that would run off the bottom of the screen
+        let count = self
+            .count
+            .min(((self.viewport.1 - origin.y) / ROW_HEIGHT).max(0.0) as usize);
+        let shown = count.min(self.rows.len());
+
+        renderer.render_rectangle(
+            origin,
+            WIDTH,
+            ROW_HEIGHT * shown as f64,
+            DVec2::ZERO,
+            0.0,
+            Srgba::new(0.0, 0.0, 0.0, 0.5),
+        );
+
+        for row in self
+            .rows
+            .iter()
+            .filter(|row| row.position < count as f64 - 0.01)
+        {
+            let alpha = (count as f64 - row.position).clamp(0.0, 1.0) as f32;
+            let top = origin.y + ROW_HEIGHT * row.position;
+            let baseline = top + ROW_HEIGHT - 8.0;
+            let marker = dvec2(origin.x + 44.0, top + ROW_HEIGHT / 2.0);
+
+            renderer.render_text_outline(
+                &format!("{}.", row.place + 1),
+                dvec2(origin.x + 8.0, baseline),
+                anchor2d::LGB,
+                16.0,
+                1.0,
+                Srgba::new(1.0, 1.0, 1.0, alpha),
+                Srgba::new(0.0, 0.0, 0.0, alpha),
+            );
+
+            if let Some(image_name) = &row.maybe_image_name {
+                renderer.render_image(image_name, marker, 16.0, 16.0, DVec2::splat(0.5), 0.0);
+                renderer.render_circle_lines(marker, 8.0, 1.0, row.color);
+            } else {
+                renderer.render_circle(marker, 8.0, row.color);
+            }
+
+            renderer.render_text_outline(
+                &row.name,
+                dvec2(origin.x + 60.0, baseline),
+                anchor2d::LGB,
+                16.0,
+                1.0,
+                Srgba::new(row.color.red, row.color.green, row.color.blue, alpha),
+                Srgba::new(0.0, 0.0, 0.0, alpha),
+            );
+
+            renderer.render_text(
+                &row.gap,
+                dvec2(origin.x + WIDTH - 8.0, baseline),
+                anchor2d::RGB,
+                12.0,
+                Srgba::new(0.75, 0.75, 0.75, alpha),
+            );
+        }
+    }
+
+    fn update(&mut self, dt: f64) {
+        let time = self.time() + dt;
+        self.set_time(time);
+        self.origin_sequence_mut().advance_to(time);
+
+        let blend = 1.0 - (-SWAP_SPEED * dt).exp();
+
+        for row in self.rows.iter_mut() {
+            row.position += (row.place as f64 - row.position) * blend;
+        }
+    }
+
+    fn observe(&mut self, scene: &Scene) {
+        let standings = scene.get_standings();
+
+        let Some(leader) = standings.first().copied() else {
+            return;
+        };
+
+        let leader_progress = if scene.any_won() {
+            1.0
+        } else {
+            scene.get_progress(leader)
+        };
+
+        let maybe_first_win_time = scene.get_win_times().first().copied();
+
+        for (place, index) in standings.iter().copied().enumerate() {
+            let ball = &scene.get_balls()[index];
+
+            let gap = match (
+                scene
+                    .get_winners()
+                    .iter()
+                    .position(|winner| *winner == index),
+                maybe_first_win_time,
+            ) {
+                _ if scene.get_drops().is_some() && !ball.is_eliminated() => {
+                    format!("{} pts", scene.get_total_score(index))
+                }
+                (Some(0), Some(first_win_time)) => format_race_time(first_win_time.as_secs_f64()),
+                (Some(winner_place), Some(first_win_time)) => format!(
+                    "+{:.2}s",
+                    (scene.get_win_times()[winner_place] - first_win_time).as_secs_f64()
+                ),
+                _ if ball.is_eliminated() => String::from("Out"),
+                _ if place == 0 => String::from("Leader"),
+                _ => format!(
+                    "-{:.0}%",
+                    ((leader_progress - scene.get_progress(index)) * 100.0).max(0.0)
+                ),
+            };
+
+            if let Some(row) = self.rows.iter_mut().find(|row| row.index == index) {
+                row.name = ball.get_name().to_string();
+                row.gap = gap;
+                row.place = place;
+            } else {
+                self.rows.push(StandingsRow {
+                    index,
+                    name: ball.get_name().to_string(),
+                    color: ball.get_name_color(),
+                    maybe_image_name: ball.get_style().get_image_name().map(str::to_string),
+                    gap,
+                    place,
+                    position: place as f64,
+                });
+            }
+        }
+    }
+
+    fn origin_sequence(&self) -> &AnimationSequence<Vector2<f64>> {
+        &self.origin
+    }
+
+    fn origin_sequence_mut(&mut self) -> &mut AnimationSequence<Vector2<f64>> {
+        &mut self.origin
+    }
+
+    fn time(&self) -> f64 {
+        self.time
+    }
+
+    fn set_time(&mut self, new_time: f64) {
+        self.time = new_time;
+    }
+
+    fn visible(&self) -> bool {
+        self.time() >= self.start
+    }
+}
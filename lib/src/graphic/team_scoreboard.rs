@@ -0,0 +1,141 @@
+use glam::{DVec2, dvec2};
+use keyframe::AnimationSequence;
+use mint::Vector2;
+use palette::Srgba;
+use render_agnostic::Renderer;
+
+use crate::{graphic::Graphic, scene::Scene, team::TeamScoring};
+
+const WIDTH: f64 = 200.0;
+const ROW_HEIGHT: f64 = 28.0;
+
+#[derive(Clone)]
+struct TeamRow {
+    name: String,
+    color: Srgba,
+    score: String,
+    winner: bool,
+}
+
+/// Every team in a team race, best first, with its score under the race's scoring rule.
+#[derive(Clone)]
+pub struct TeamScoreboard {
+    pub time: f64,
+    pub start: f64,
+    pub origin: AnimationSequence<Vector2<f64>>,
+    pub viewport: (f64, f64),
+    rows: Vec<TeamRow>,
+}
+
+impl TeamScoreboard {
+    pub fn new(origin: AnimationSequence<Vector2<f64>>, start: f64, viewport: (f64, f64)) -> Self {
+        Self {
+            time: 0.0,
+            start,
+            origin,
+            viewport,
+            rows: Vec::new(),
+        }
+    }
+}
+
+impl Graphic for TeamScoreboard {
+    fn draw(&self, renderer: &mut dyn Renderer) {
+        let origin = self.origin();
+
+        renderer.render_rectangle(
+            origin,
+            WIDTH,
+            ROW_HEIGHT * self.rows.len() as f64,
+            DVec2::ZERO,
+            0.0,
+            Srgba::new(0.0, 0.0, 0.0, 0.5),
+        );
+
+        for (place, row) in self.rows.iter().enumerate() {
+            let top = origin.y + ROW_HEIGHT * place as f64;
+            let baseline = top + ROW_HEIGHT - 8.0;
+
+            renderer.render_rectangle(
+                dvec2(origin.x, top),
+                6.0,
+                ROW_HEIGHT,
+                DVec2::ZERO,
+                0.0,
+                row.color,
+            );
+
+            renderer.render_text_outline(
+                &row.name,
+                dvec2(origin.x + 16.0, baseline),
+                anchor2d::LGB,
+                16.0,
+                1.0,
+                row.color,
+                Srgba::new(0.0, 0.0, 0.0, 1.0),
+            );
+
+            renderer.render_text_outline(
+                &row.score,
+                dvec2(origin.x + WIDTH - 8.0, baseline),
+                anchor2d::RGB,
+                16.0,
+                1.0,
+                if row.winner {
+                    Srgba::new(1.0, 0.85, 0.2, 1.0)
+                } else {
+                    Srgba::new(1.0, 1.0, 1.0, 1.0)
+                },
+                Srgba::new(0.0, 0.0, 0.0, 1.0),
+            );
+        }
+    }
+
+    fn observe(&mut self, scene: &Scene) {
+        let Some(teams) = scene.get_teams() else {
+            return;
+        };
+
+        let maybe_winning_team = scene.get_winning_team();
+
+        self.rows = scene
+            .get_team_results()
+            .into_iter()
+            .map(|result| {
+                let team = &teams.get_teams()[result.team];
+
+                TeamRow {
+                    name: team.name.clone(),
+                    color: team.color,
+                    score: match teams.get_scoring() {
+                        TeamScoring::AveragePlace => format!("{:.1}", result.get_average_place()),
+                        TeamScoring::FirstTo(finishers) => {
+                            format!("{}/{}", result.finishers.min(finishers), finishers)
+                        }
+                    },
+                    winner: maybe_winning_team == Some(result.team),
+                }
+            })
+            .collect();
+    }
+
+    fn origin_sequence(&self) -> &AnimationSequence<Vector2<f64>> {
+        &self.origin
+    }
+
+    fn origin_sequence_mut(&mut self) -> &mut AnimationSequence<Vector2<f64>> {
+        &mut self.origin
+    }
+
+    fn time(&self) -> f64 {
+        self.time
+    }
+
+    fn set_time(&mut self, new_time: f64) {
+        self.time = new_time;
+    }
+
+    fn visible(&self) -> bool {
+        self.time() >= self.start
+    }
+}
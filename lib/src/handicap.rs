@@ -0,0 +1,128 @@
+use std::{collections::HashMap, fmt::Display};
+
+use api::marble::Marble;
+use database::race_marble::DbRaceMarble;
+use serde::Deserialize;
+use sqlx::SqlitePool;
+
+use crate::{
+    HandicapConfig,
+    ball::{
+        Ball,
+        status_effect::{Stat, StatusEffect},
+    },
+};
+
+/// A change to a marble for one race, to even the field out.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Handicap {
+    /// Added to the marble's radius.
+    pub radius: f64,
+    /// Multiplies the marble's density.
+    pub density: f64,
+    /// Seconds the marble is held in place after the start.
+    pub start_delay: f64,
+}
+
+impl Default for Handicap {
+    fn default() -> Self {
+        Self {
+            radius: 0.0,
+            density: 1.0,
+            start_delay: 0.0,
+        }
+    }
+}
+
+impl Handicap {
+    /// Whether this holds the marble back rather than helping it.
+    pub fn is_penalty(&self) -> bool {
+        self.radius > 0.0 || self.density < 1.0 || self.start_delay > 0.0
+    }
+
+    pub fn apply(&self, ball: &mut Ball) {
+        ball.set_radius((ball.get_radius() + self.radius).max(4.0));
+        ball.set_density(ball.get_density() * self.density);
+
+        if self.start_delay > 0.0 {
+            let physics_ball = ball.get_physics_ball_mut();
+
+            physics_ball.add_status_effect(StatusEffect::new(
+                "Start delay",
+                Stat::Speed,
+                0.0,
+                self.start_delay,
+            ));
+            physics_ball.add_status_effect(StatusEffect::new(
+                "Start delay",
+                Stat::Gravity,
+                0.0,
+                self.start_delay,
+            ));
+        }
+    }
+}
+
+impl Display for Handicap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut changes = Vec::new();
+
+        if self.radius != 0.0 {
+            changes.push(format!("{:+.0} size", self.radius));
+        }
+
+        if self.density != 1.0 {
+            changes.push(format!("x{:.2} weight", self.density));
+        }
+
+        if self.start_delay > 0.0 {
+            changes.push(format!("starts {:.1}s late", self.start_delay));
+        }
+
+        write!(
+            f,
+            "{}: {}",
+            if self.is_penalty() {
+                "Handicap"
+            } else {
+                "Boost"
+            },
+            changes.join(", ")
+        )
+    }
+}
+
+/// Handicaps for the marbles about to race, by marble name. Marbles that have been winning a lot
+/// lately get the favourite handicap and marbles that have barely been winning get the underdog
+/// one. Marbles without enough recent races are left alone.
+pub async fn get_handicaps(
+    pool: &SqlitePool,
+    config: &HandicapConfig,
+    marbles: &[Marble],
+) -> sqlx::Result<HashMap<String, Handicap>> {
+    let mut handicaps = HashMap::new();
+
+    for marble in marbles {
+        let recent =
+            DbRaceMarble::get_recent_by_marble_id(pool, marble.id, config.recent_races).await?;
+
+        if recent.len() < config.min_races {
+            continue;
+        }
+
+        let wins = recent
+            .iter()
+            .filter(|race_marble| race_marble.place == 1)
+            .count();
+        let win_rate = wins as f64 / recent.len() as f64;
+
+        if win_rate >= config.favourite_win_rate {
+            handicaps.insert(marble.name.clone(), config.favourite);
+        } else if win_rate <= config.underdog_win_rate {
+            handicaps.insert(marble.name.clone(), config.underdog);
+        }
+    }
+
+    Ok(handicaps)
+}
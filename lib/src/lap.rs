@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+/// A ball crossing a checkpoint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Split {
+    /// Laps the ball had completed, counting this crossing.
+    pub lap: usize,
+    pub checkpoint: usize,
+    pub time: Duration,
+}
+
+/// The checkpoints one ball has crossed, in order. Crossing the start/finish line (checkpoint 0)
+/// the first time starts the first lap, and every time after that completes one.
+#[derive(Debug, Clone, Default)]
+pub struct LapTracker {
+    splits: Vec<Split>,
+    lap_times: Vec<Duration>,
+    next_checkpoint: usize,
+    touching: Vec<usize>,
+}
+
+impl LapTracker {
+    pub fn get_splits(&self) -> &Vec<Split> {
+        &self.splits
+    }
+
+    pub fn get_laps(&self) -> usize {
+        self.lap_times.len()
+    }
+
+    pub fn get_lap_times(&self) -> &Vec<Duration> {
+        &self.lap_times
+    }
+
+    pub fn get_best_lap(&self) -> Option<Duration> {
+        self.lap_times.iter().min().copied()
+    }
+
+    /// The checkpoints the ball was touching when it was last observed.
+    pub fn get_touching(&self) -> &Vec<usize> {
+        &self.touching
+    }
+
+    /// Treats the ball as already touching these checkpoints, so that it has to leave them and
+    /// come back before touching them counts.
+    pub fn hold(&mut self, touching: Vec<usize>) {
+        self.touching = touching;
+    }
+
+    /// Records the checkpoints the ball has just started touching, ignoring any that are out of
+    /// order. Returns whether this completed a lap.
+    pub fn observe(
+        &mut self,
+        touching: Vec<usize>,
+        checkpoint_count: usize,
+        time: Duration,
+    ) -> bool {
+        let mut completed_lap = false;
+
+        for checkpoint in touching.iter().copied() {
+            if self.touching.contains(&checkpoint) || checkpoint != self.next_checkpoint {
+                continue;
+            }
+
+            if checkpoint == 0
+                && let Some(lap_start) = self
+                    .splits
+                    .iter()
+                    .rev()
+                    .find(|split| split.checkpoint == 0)
+                    .map(|split| split.time)
+            {
+                self.lap_times.push(time - lap_start);
+                completed_lap = true;
+            }
+
+            self.splits.push(Split {
+                lap: self.get_laps(),
+                checkpoint,
+                time,
+            });
+
+            self.next_checkpoint = (checkpoint + 1) % checkpoint_count.max(1);
+        }
+
+        self.touching = touching;
+
+        completed_lap
+    }
+}
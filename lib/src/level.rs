@@ -2,10 +2,19 @@ use std::sync::Arc;
 
 use api::marble::Marble;
 use glam::DVec2;
-use rand::{Rng, seq::IndexedRandom};
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::IndexedRandom};
 
 use crate::{
-    ball::Ball, powerup::Powerup, scene::Scene, simulation::Simulation, util::space_evenly,
+    ball::Ball,
+    portal::Portal,
+    powerup::pool::{PowerupPool, PowerupSpawner},
+    progress::ProgressFunction,
+    scene::Scene,
+    simulation::Simulation,
+    solver::Solver,
+    team::Teams,
+    timeline::Timeline,
+    util::space_evenly,
     wall::Wall,
 };
 
@@ -30,16 +39,29 @@ impl BallSpace {
 
 pub struct PowerupSpace {
     position: DVec2,
+    maybe_respawn_seconds: Option<f64>,
 }
 
 impl PowerupSpace {
     pub fn new(position: DVec2) -> Self {
-        Self { position }
+        Self {
+            position,
+            maybe_respawn_seconds: None,
+        }
     }
 
     pub fn get_position(&self) -> DVec2 {
         self.position
     }
+
+    /// Seconds before a consumed powerup here is replaced. Without one the level's pool decides.
+    pub fn get_respawn_seconds(&self) -> Option<f64> {
+        self.maybe_respawn_seconds
+    }
+
+    pub fn set_respawn_seconds(&mut self, maybe_respawn_seconds: Option<f64>) {
+        self.maybe_respawn_seconds = maybe_respawn_seconds;
+    }
 }
 
 pub struct Level {
@@ -47,6 +69,11 @@ pub struct Level {
     ball_spaces: Vec<BallSpace>,
     powerup_spaces: Vec<PowerupSpace>,
     walls: Vec<Box<dyn Wall>>,
+    progress_function: Arc<ProgressFunction>,
+    timeline: Timeline,
+    portals: Vec<Portal>,
+    solver: Solver,
+    powerup_pool: PowerupPool,
 }
 
 impl Level {
@@ -55,38 +82,91 @@ impl Level {
         ball_spaces: Vec<BallSpace>,
         powerup_spaces: Vec<PowerupSpace>,
         walls: Vec<Box<dyn Wall>>,
+        progress_function: impl Fn(DVec2) -> f64 + Send + Sync + 'static,
     ) -> Self {
         Self {
             id,
             ball_spaces,
             powerup_spaces,
             walls,
+            progress_function: Arc::new(progress_function),
+            timeline: Timeline::new(),
+            portals: Vec::new(),
+            solver: Solver::default(),
+            powerup_pool: PowerupPool::default(),
         }
     }
 
+    /// Scripts what happens during races on this level.
+    pub fn set_timeline(&mut self, timeline: Timeline) {
+        self.timeline = timeline;
+    }
+
+    pub fn set_portals(&mut self, portals: Vec<Portal>) {
+        self.portals = portals;
+    }
+
+    /// How races on this level work out contacts between balls and walls.
+    pub fn set_solver(&mut self, solver: Solver) {
+        self.solver = solver;
+    }
+
+    /// The powerups this level's powerup spaces are stocked from.
+    pub fn set_powerup_pool(&mut self, powerup_pool: PowerupPool) {
+        self.powerup_pool = powerup_pool;
+    }
+
+    /// Builds a scene for these marbles, as a team race if `maybe_teams` is set. `bounds` is the
+    /// size of the area teleports can land in.
     pub fn build_scene(
         &self,
         rng: &mut impl Rng,
         marbles: &[Marble],
-        powerup_function: impl Fn(&PowerupSpace) -> Box<dyn Powerup>,
+        maybe_teams: Option<&Teams>,
+        bounds: (f64, f64),
         finished_condition: impl Fn(&Simulation) -> bool + Send + Sync + 'static,
     ) -> Scene {
-        let balls = marbles
+        let racing_marbles = marbles
             .sample(rng, self.ball_spaces.len())
+            .collect::<Vec<&Marble>>();
+
+        let balls = racing_marbles
+            .iter()
             .zip(self.ball_spaces.iter())
             .map(|(marble, ball_space)| {
                 Ball::from_marble(marble, ball_space.position, ball_space.velocity)
             })
             .collect();
 
-        let powerups = self.powerup_spaces.iter().map(powerup_function).collect();
-
-        Scene::new(
+        let mut scene = Scene::new(
             self.id,
             balls,
-            powerups,
+            Vec::new(),
             self.walls.clone(),
             Arc::new(finished_condition),
-        )
+            Arc::clone(&self.progress_function),
+        );
+
+        if let Some(teams) = maybe_teams {
+            scene.set_teams(
+                Some(teams.clone()),
+                racing_marbles
+                    .iter()
+                    .map(|marble| teams.get_team_index(marble))
+                    .collect(),
+            );
+        }
+
+        scene.set_timeline(self.timeline.clone());
+        scene.set_portals(self.portals.clone());
+        scene.set_solver(self.solver);
+        scene.set_rng(StdRng::from_rng(rng));
+        scene.set_powerup_spawner(Some(PowerupSpawner::new(
+            self.powerup_pool.clone(),
+            bounds,
+            &self.powerup_spaces,
+        )));
+
+        scene
     }
 }
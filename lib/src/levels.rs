@@ -1,12 +1,25 @@
+use std::f64::consts::PI;
+
 use even_odd_traits::{IsEven, IsOdd};
 use glam::{DVec2, dvec2};
+use palette::Srgba;
 
 use crate::{
     level::{BallSpace, Level, PowerupSpace},
+    portal::Portal,
+    powerup::{change_density::ChangeDensity, change_elasticity::ChangeElasticity},
+    progress::{radial_progress, shelf_progress, vertical_progress},
+    solver::Solver,
+    timeline::{Action, Cue, EventKind, Timeline},
     wall::{
         Wall,
+        bucket_wall::BucketWall,
         circle_wall::CircleWall,
+        door_wall::DoorWall,
+        hazard_wall::{Hazard, HazardWall},
         straight_wall::{Line, StraightWall},
+        trapdoor_wall::{Trapdoor, TrapdoorWall},
+        trigger_wall::{Trigger, TriggerWall},
     },
 };
 
@@ -58,6 +71,7 @@ pub fn level_1(n: usize, scene_width: f64, scene_height: f64) -> Level {
             PowerupSpace::new(dvec2(scene_width - 64.0, 250.0)),
         ],
         walls,
+        shelf_progress(scene_width, scene_height, 100.0, offset, 8, 50.0),
     )
 }
 
@@ -95,6 +109,7 @@ pub fn level_2(n: usize, scene_width: f64, scene_height: f64) -> Level {
         BallSpace::spaced_evenly(n, dvec2(0.0, 50.0), dvec2(scene_width, 50.0), DVec2::ZERO),
         Vec::new(),
         walls,
+        vertical_progress(scene_height),
     )
 }
 
@@ -109,6 +124,7 @@ pub fn level_3(n: usize, scene_width: f64, scene_height: f64) -> Level {
         BallSpace::spaced_evenly(n, dvec2(0.0, 50.0), dvec2(scene_width, 50.0), DVec2::ZERO),
         Vec::new(),
         walls,
+        vertical_progress(scene_height),
     )
 }
 
@@ -145,6 +161,7 @@ pub fn level_4(n: usize, scene_width: f64, scene_height: f64) -> Level {
         ),
         Vec::new(),
         walls,
+        vertical_progress(scene_height),
     )
 }
 
@@ -187,6 +204,7 @@ pub fn level_5(n: usize, scene_width: f64, scene_height: f64) -> Level {
         BallSpace::spaced_evenly(n, dvec2(0.0, 50.0), dvec2(scene_width, 50.0), DVec2::ZERO),
         powerup_spaces,
         walls,
+        vertical_progress(scene_height),
     )
 }
 
@@ -227,6 +245,7 @@ pub fn level_6(n: usize, scene_width: f64, scene_height: f64) -> Level {
             PowerupSpace::new(dvec2(scene_width * 0.9, scene_height * 0.05)),
         ],
         walls,
+        vertical_progress(scene_height),
     )
 }
 
@@ -283,6 +302,7 @@ pub fn level_8(n: usize, scene_width: f64, scene_height: f64) -> Level {
         BallSpace::spaced_evenly(n, dvec2(0.0, 50.0), dvec2(scene_width, 50.0), DVec2::ZERO),
         powerup_spaces,
         walls,
+        shelf_progress(scene_width, scene_height, 100.0, offset, 8, 50.0),
     )
 }
 
@@ -326,6 +346,7 @@ pub fn level_9(n: usize, scene_width: f64, scene_height: f64) -> Level {
             PowerupSpace::new(dvec2(scene_width * 0.9, scene_height * 0.05)),
         ],
         walls,
+        vertical_progress(scene_height),
     )
 }
 
@@ -382,5 +403,426 @@ pub fn level_10(n: usize, scene_width: f64, scene_height: f64) -> Level {
         BallSpace::spaced_evenly(n, dvec2(0.0, 50.0), dvec2(scene_width, 50.0), DVec2::ZERO),
         powerup_spaces,
         walls,
+        shelf_progress(scene_width, scene_height, 100.0, offset, 5, 75.0),
+    )
+}
+
+/// A circuit: marbles drop through the shelves and are teleported from the bottom back to the
+/// top, crossing the start/finish line at the top once per lap.
+pub fn level_11(n: usize, scene_width: f64, scene_height: f64) -> Level {
+    let offset = 150.0;
+
+    let mut walls: Vec<Box<dyn Wall>> =
+        StraightWall::rect(0.0, 0.0, scene_width, scene_height, false)
+            .into_iter()
+            .map(|straight_wall| Box::new(straight_wall) as Box<dyn Wall>)
+            .collect();
+
+    for i in 0..5 {
+        walls.push(Box::new(StraightWall::new(
+            Line::new(
+                dvec2(0.0, 125.0 + offset * i as f64),
+                dvec2(scene_width * 0.5 - 24.0, 150.0 + offset * i as f64),
+            ),
+            false,
+        )));
+        walls.push(Box::new(StraightWall::new(
+            Line::new(
+                dvec2(scene_width * 0.5 + 24.0, 150.0 + offset * i as f64),
+                dvec2(scene_width, 125.0 + offset * i as f64),
+            ),
+            false,
+        )));
+        walls.push(Box::new(StraightWall::new(
+            Line::new(
+                dvec2(scene_width * 0.5, 200.0 + offset * i as f64),
+                dvec2(48.0, 225.0 + offset * i as f64),
+            ),
+            false,
+        )));
+        walls.push(Box::new(StraightWall::new(
+            Line::new(
+                dvec2(scene_width * 0.5, 200.0 + offset * i as f64),
+                dvec2(scene_width - 48.0, 225.0 + offset * i as f64),
+            ),
+            false,
+        )));
+    }
+
+    walls.push(Box::new(TriggerWall::new(
+        Line::new(dvec2(0.0, 90.0), dvec2(scene_width, 90.0)),
+        Trigger::Checkpoint(0),
+    )));
+    walls.push(Box::new(TriggerWall::new(
+        Line::new(
+            dvec2(0.0, scene_height * 0.5),
+            dvec2(scene_width, scene_height * 0.5),
+        ),
+        Trigger::Checkpoint(1),
+    )));
+    walls.push(Box::new(TriggerWall::new(
+        Line::new(
+            dvec2(0.0, scene_height - 40.0),
+            dvec2(scene_width, scene_height - 40.0),
+        ),
+        Trigger::Teleport(dvec2(0.0, 100.0 - scene_height)),
+    )));
+
+    Level::new(
+        11,
+        BallSpace::spaced_evenly(n, dvec2(0.0, 50.0), dvec2(scene_width, 50.0), DVec2::ZERO),
+        vec![
+            PowerupSpace::new(dvec2(64.0, 275.0)),
+            PowerupSpace::new(dvec2(scene_width - 64.0, 275.0)),
+        ],
+        walls,
+        vertical_progress(scene_height),
     )
 }
+
+/// Level 2's peg board with a row of scoring buckets at the bottom instead of a single goal.
+pub fn level_12(n: usize, scene_width: f64, scene_height: f64) -> Level {
+    let mut walls: Vec<Box<dyn Wall>> =
+        StraightWall::rect(0.0, 0.0, scene_width, scene_height, false)
+            .into_iter()
+            .map(|straight_wall| Box::new(straight_wall) as Box<dyn Wall>)
+            .collect();
+
+    let max_columns = 8;
+    let x_spacing = scene_width / (max_columns as f64 + 1.0);
+
+    for j in 0..20 {
+        let column_offset = j % 2;
+        let columns = max_columns + 2 - column_offset;
+
+        for i in 0..columns {
+            let x = (x_spacing * 0.5 * column_offset as f64) + x_spacing * i as f64;
+            let y = 100.0 + 36.0 * j as f64;
+
+            walls.push(Box::new(StraightWall::new(
+                Line::new(dvec2(x - 12.0, y), dvec2(x, y - 6.0)),
+                false,
+            )));
+            walls.push(Box::new(StraightWall::new(
+                Line::new(dvec2(x + 12.0, y), dvec2(x, y - 6.0)),
+                false,
+            )));
+        }
+    }
+
+    let scores = [10, 50, 100, 500, 100, 50, 10];
+    let bucket_width = scene_width / scores.len() as f64;
+
+    for (i, score) in scores.into_iter().enumerate() {
+        let left = bucket_width * i as f64;
+
+        if i > 0 {
+            walls.push(Box::new(StraightWall::new(
+                Line::new(dvec2(left, scene_height - 120.0), dvec2(left, scene_height)),
+                false,
+            )));
+        }
+
+        walls.push(Box::new(BucketWall::new(
+            Line::new(
+                dvec2(left, scene_height),
+                dvec2(left + bucket_width, scene_height),
+            ),
+            score,
+        )));
+    }
+
+    Level::new(
+        12,
+        BallSpace::spaced_evenly(n, dvec2(0.0, 50.0), dvec2(scene_width, 50.0), DVec2::ZERO),
+        Vec::new(),
+        walls,
+        vertical_progress(scene_height),
+    )
+}
+
+/// A shrinking sumo ring in level 3's open box. Marbles drop into the ring and get pushed out as
+/// it closes in. The marbles end up piled against each other at the bottom of the ring, so it uses
+/// the impulse solver to keep the pile from jittering.
+pub fn level_13(n: usize, scene_width: f64, scene_height: f64) -> Level {
+    let center = dvec2(scene_width * 0.5, scene_height * 0.45);
+    let radius = scene_width * 0.45;
+
+    let mut walls: Vec<Box<dyn Wall>> =
+        StraightWall::rect(0.0, 0.0, scene_width, scene_height, false)
+            .into_iter()
+            .map(|straight_wall| Box::new(straight_wall) as Box<dyn Wall>)
+            .collect();
+
+    walls.push(Box::new(CircleWall::arena(center, radius, 40.0, 4.0)));
+
+    let mut powerup_spaces = vec![
+        PowerupSpace::new(center + dvec2(-radius * 0.4, radius * 0.5)),
+        PowerupSpace::new(center + dvec2(radius * 0.4, radius * 0.5)),
+    ];
+
+    // The ring keeps shrinking, so keep giving the marbles something to fight over
+    for powerup_space in powerup_spaces.iter_mut() {
+        powerup_space.set_respawn_seconds(Some(8.0));
+    }
+
+    let mut level = Level::new(
+        13,
+        BallSpace::spaced_evenly(
+            n,
+            dvec2(center.x - radius * 0.8, center.y),
+            dvec2(center.x + radius * 0.8, center.y),
+            DVec2::ZERO,
+        ),
+        powerup_spaces,
+        walls,
+        radial_progress(center, radius),
+    );
+
+    level.set_solver(Solver::Impulse);
+
+    level
+}
+
+/// A holding funnel with a trapdoor that opens after ten seconds, over a run of shelves. The leader
+/// reaching the first shelf spawns powerups further down, the first marble near the bottom flips
+/// everyone's gravity for a moment, and the first finish drops a special on whoever is last.
+pub fn level_14(n: usize, scene_width: f64, scene_height: f64) -> Level {
+    let mut walls: Vec<Box<dyn Wall>> =
+        StraightWall::rect(0.0, 0.0, scene_width, scene_height, true)
+            .into_iter()
+            .map(|straight_wall| Box::new(straight_wall) as Box<dyn Wall>)
+            .collect();
+
+    walls.push(Box::new(StraightWall::new(
+        Line::new(dvec2(0.0, 200.0), dvec2(scene_width * 0.5 - 40.0, 300.0)),
+        false,
+    )));
+    walls.push(Box::new(StraightWall::new(
+        Line::new(
+            dvec2(scene_width, 200.0),
+            dvec2(scene_width * 0.5 + 40.0, 300.0),
+        ),
+        false,
+    )));
+    walls.push(Box::new(DoorWall::new(
+        "trapdoor",
+        Box::new(StraightWall::new(
+            Line::new(
+                dvec2(scene_width * 0.5 - 40.0, 300.0),
+                dvec2(scene_width * 0.5 + 40.0, 300.0),
+            ),
+            false,
+        )),
+        false,
+    )));
+
+    for i in 0..3 {
+        let y = 420.0 + 140.0 * i as f64;
+
+        let line = if i.is_even() {
+            Line::new(dvec2(0.0, y), dvec2(scene_width * 0.7, y + 50.0))
+        } else {
+            Line::new(dvec2(scene_width, y), dvec2(scene_width * 0.3, y + 50.0))
+        };
+
+        walls.push(Box::new(StraightWall::new(line, false)));
+    }
+
+    let mut timeline = Timeline::new();
+
+    timeline.add(Cue::Time(10.0), Action::OpenWall("trapdoor".to_string()));
+    timeline.add(
+        Cue::Zone {
+            min: dvec2(0.0, 380.0),
+            max: dvec2(scene_width, 400.0),
+            leader_only: true,
+        },
+        Action::SpawnPowerup(Box::new(ChangeDensity::new(
+            dvec2(scene_width * 0.5, 660.0),
+            8.0,
+            2.0,
+        ))),
+    );
+    timeline.add(
+        Cue::Zone {
+            min: dvec2(0.0, 380.0),
+            max: dvec2(scene_width, 400.0),
+            leader_only: true,
+        },
+        Action::SpawnPowerup(Box::new(ChangeElasticity::new(
+            dvec2(scene_width * 0.5, 800.0),
+            8.0,
+            0.95,
+        ))),
+    );
+    timeline.add(
+        Cue::Zone {
+            min: dvec2(0.0, scene_height - 120.0),
+            max: dvec2(scene_width, scene_height - 100.0),
+            leader_only: false,
+        },
+        Action::FlipGravity(0.75),
+    );
+    timeline.add(Cue::Event(EventKind::Finished), Action::SpecialOnLastPlace);
+
+    let mut level = Level::new(
+        14,
+        BallSpace::spaced_evenly(n, dvec2(0.0, 50.0), dvec2(scene_width, 50.0), DVec2::ZERO),
+        Vec::new(),
+        walls,
+        vertical_progress(scene_height),
+    );
+
+    level.set_timeline(timeline);
+
+    level
+}
+
+/// A holding pen with a trapdoor that breaks after enough hits, over two routes. The left is a
+/// shortcut that stays shut for the first fifteen seconds, with spikes along the wall that send
+/// marbles back to the checkpoint and a lava pocket that knocks them out. The right is the long
+/// way round, through a mud bath.
+pub fn level_15(n: usize, scene_width: f64, scene_height: f64) -> Level {
+    let middle = scene_width * 0.5;
+
+    let mut walls: Vec<Box<dyn Wall>> =
+        StraightWall::rect(0.0, 0.0, scene_width, scene_height, true)
+            .into_iter()
+            .map(|straight_wall| Box::new(straight_wall) as Box<dyn Wall>)
+            .collect();
+
+    walls.push(Box::new(StraightWall::new(
+        Line::new(dvec2(0.0, 150.0), dvec2(middle - 50.0, 220.0)),
+        false,
+    )));
+    walls.push(Box::new(StraightWall::new(
+        Line::new(dvec2(scene_width, 150.0), dvec2(middle + 50.0, 220.0)),
+        false,
+    )));
+    walls.push(Box::new(TrapdoorWall::new(
+        Line::new(dvec2(middle - 50.0, 220.0), dvec2(middle + 50.0, 220.0)),
+        Trapdoor::Contacts(10),
+    )));
+    walls.push(Box::new(TriggerWall::new(
+        Line::new(dvec2(middle - 50.0, 250.0), dvec2(middle + 50.0, 250.0)),
+        Trigger::Checkpoint(0),
+    )));
+
+    // The shortcut's lid slopes into the long way until it drops open
+    walls.push(Box::new(TrapdoorWall::new(
+        Line::new(dvec2(0.0, 320.0), dvec2(middle, 380.0)),
+        Trapdoor::Timer(15.0),
+    )));
+    walls.push(Box::new(StraightWall::new(
+        Line::new(dvec2(middle, 400.0), dvec2(middle, 820.0)),
+        false,
+    )));
+
+    walls.push(Box::new(HazardWall::new(
+        dvec2(0.0, 400.0),
+        dvec2(10.0, 600.0),
+        Hazard::Spikes { respawn: true },
+    )));
+    walls.push(Box::new(StraightWall::new(
+        Line::new(
+            dvec2(scene_width * 0.2, 660.0),
+            dvec2(scene_width * 0.2, 700.0),
+        ),
+        false,
+    )));
+    walls.push(Box::new(StraightWall::new(
+        Line::new(dvec2(0.0, 700.0), dvec2(scene_width * 0.2, 700.0)),
+        false,
+    )));
+    walls.push(Box::new(HazardWall::new(
+        dvec2(0.0, 680.0),
+        dvec2(scene_width * 0.2, 700.0),
+        Hazard::Lava { respawn: false },
+    )));
+
+    walls.push(Box::new(StraightWall::new(
+        Line::new(dvec2(middle, 460.0), dvec2(scene_width * 0.88, 500.0)),
+        false,
+    )));
+    walls.push(Box::new(StraightWall::new(
+        Line::new(dvec2(scene_width, 580.0), dvec2(scene_width * 0.62, 620.0)),
+        false,
+    )));
+    walls.push(Box::new(StraightWall::new(
+        Line::new(dvec2(middle, 700.0), dvec2(scene_width * 0.88, 740.0)),
+        false,
+    )));
+    walls.push(Box::new(HazardWall::new(
+        dvec2(middle, 510.0),
+        dvec2(scene_width, 640.0),
+        Hazard::Mud { drag: 2.0 },
+    )));
+
+    Level::new(
+        15,
+        BallSpace::spaced_evenly(n, dvec2(0.0, 50.0), dvec2(scene_width, 50.0), DVec2::ZERO),
+        Vec::new(),
+        walls,
+        vertical_progress(scene_height),
+    )
+}
+
+/// Level 14's run of shelves, with two pairs of portals on them. The blue pair on the first shelf
+/// is a shortcut to the bottom, and the orange pair near the end turns marbles around and sends
+/// them back to the top. The top end of the orange pair is up in the corner past the end of the
+/// first shelf, above where the marbles start, so that nothing can fall or bounce into it.
+pub fn level_16(n: usize, scene_width: f64, scene_height: f64) -> Level {
+    let mut walls: Vec<Box<dyn Wall>> =
+        StraightWall::rect(0.0, 0.0, scene_width, scene_height, true)
+            .into_iter()
+            .map(|straight_wall| Box::new(straight_wall) as Box<dyn Wall>)
+            .collect();
+
+    for i in 0..5 {
+        let y = 200.0 + 150.0 * i as f64;
+
+        let line = if i.is_even() {
+            Line::new(dvec2(0.0, y), dvec2(scene_width * 0.75, y + 50.0))
+        } else {
+            Line::new(dvec2(scene_width, y), dvec2(scene_width * 0.25, y + 50.0))
+        };
+
+        walls.push(Box::new(StraightWall::new(line, false)));
+    }
+
+    let mut shortcut = Portal::new(
+        dvec2(scene_width * 0.45, 215.0),
+        dvec2(scene_width * 0.5, scene_height - 140.0),
+        14.0,
+        Srgba::new(0.2, 0.6, 1.0, 1.0),
+    );
+    shortcut.set_cooldown_seconds(3.0);
+
+    let mut setback = Portal::new(
+        dvec2(scene_width * 0.6, 662.0),
+        dvec2(scene_width * 0.9, 40.0),
+        14.0,
+        Srgba::new(1.0, 0.55, 0.1, 1.0),
+    );
+    setback.set_rotation(PI);
+    setback.set_cooldown_seconds(2.0);
+
+    // The marbles start over the first shelf, clear of the setback's top end
+    let mut level = Level::new(
+        16,
+        BallSpace::spaced_evenly(
+            n,
+            dvec2(0.0, 80.0),
+            dvec2(scene_width * 0.75, 80.0),
+            DVec2::ZERO,
+        ),
+        Vec::new(),
+        walls,
+        vertical_progress(scene_height),
+    );
+
+    level.set_portals(vec![shortcut, setback]);
+
+    level
+}
@@ -1,19 +1,38 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
 
+use crate::{handicap::Handicap, powerup::pool::PowerupPool};
+
 pub mod ball;
 pub mod collision;
 pub mod drawer;
+pub mod elimination;
 pub mod engagement;
+pub mod event;
 pub mod graphic;
+pub mod handicap;
+pub mod lap;
 pub mod level;
 pub mod levels;
 pub mod particle;
+pub mod portal;
 pub mod posting;
 pub mod powerup;
+pub mod progress;
+pub mod promotion;
+pub mod race_context;
+pub mod rating;
 pub mod rendering;
 pub mod scene;
 pub mod scenes;
 pub mod simulation;
+pub mod solver;
+pub mod start;
+pub mod stats;
+pub mod team;
+pub mod timeline;
+pub mod tournament;
 pub mod username;
 pub mod util;
 pub mod wall;
@@ -21,12 +40,114 @@ pub mod wall;
 #[derive(Deserialize)]
 pub struct Config {
     scene: usize,
+    #[serde(default)]
+    profiles: HashMap<String, RenderProfile>,
+    #[serde(default)]
+    season: SeasonConfig,
+    #[serde(default)]
+    handicap: HandicapConfig,
+    /// Replacement powerup pools, keyed by level id.
+    #[serde(default)]
+    powerup_pools: HashMap<String, PowerupPool>,
 }
 
 impl Config {
     pub fn get_scene(&self) -> usize {
         self.scene
     }
+
+    pub fn get_profile(&self, name: &str) -> RenderProfile {
+        self.profiles.get(name).cloned().unwrap_or_default()
+    }
+
+    pub fn get_season_config(&self) -> &SeasonConfig {
+        &self.season
+    }
+
+    pub fn get_handicap_config(&self) -> &HandicapConfig {
+        &self.handicap
+    }
+
+    pub fn get_powerup_pool(&self, level_id: i64) -> Option<&PowerupPool> {
+        self.powerup_pools.get(&level_id.to_string())
+    }
+}
+
+/// How new league seasons are set up when the previous one runs out, and how many marbles move
+/// between neighbouring leagues once it has.
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct SeasonConfig {
+    pub days: i64,
+    pub points: Vec<i64>,
+    pub promoted: usize,
+    pub relegated: usize,
+}
+
+impl Default for SeasonConfig {
+    fn default() -> Self {
+        Self {
+            days: 28,
+            points: vec![25, 18, 15, 12, 10, 8, 6, 4, 2, 1],
+            promoted: 2,
+            relegated: 2,
+        }
+    }
+}
+
+/// Which marbles get handicapped and how. A marble's win rate is taken over its most recent
+/// races, and only once it has raced at least `min_races` times.
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct HandicapConfig {
+    pub recent_races: i64,
+    pub min_races: usize,
+    pub favourite_win_rate: f64,
+    pub underdog_win_rate: f64,
+    pub favourite: Handicap,
+    pub underdog: Handicap,
+}
+
+impl Default for HandicapConfig {
+    fn default() -> Self {
+        Self {
+            recent_races: 20,
+            min_races: 5,
+            favourite_win_rate: 0.35,
+            underdog_win_rate: 0.05,
+            favourite: Handicap {
+                radius: 2.0,
+                density: 1.0,
+                start_delay: 0.5,
+            },
+            underdog: Handicap {
+                radius: -2.0,
+                density: 1.0,
+                start_delay: 0.0,
+            },
+        }
+    }
+}
+
+/// Which overlays get drawn on top of a race.
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct RenderProfile {
+    pub standings: bool,
+    pub standings_count: usize,
+    pub commentary: bool,
+    pub commentary_lines: usize,
+}
+
+impl Default for RenderProfile {
+    fn default() -> Self {
+        Self {
+            standings: false,
+            standings_count: 5,
+            commentary: false,
+            commentary_lines: 2,
+        }
+    }
 }
 
 pub const ENGAGEMENTS: [&str; 8] = [
@@ -0,0 +1,151 @@
+use std::f64::consts::PI;
+
+use glam::DVec2;
+use palette::Srgba;
+use render_agnostic::Renderer;
+
+use crate::{ball::Ball, rendering::Render};
+
+/// Two linked holes. A ball that drops into either end comes out of the other at the same speed,
+/// turned by `rotation`, and the pair then needs `cooldown_seconds` to recharge before anyone can
+/// use it again.
+#[derive(Debug, Clone)]
+pub struct Portal {
+    ends: [DVec2; 2],
+    radius: f64,
+    rotation: f64,
+    cooldown_seconds: f64,
+    color: Srgba,
+    time: f64,
+    ready_time: f64,
+}
+
+impl Portal {
+    pub fn new(a: DVec2, b: DVec2, radius: f64, color: Srgba) -> Self {
+        Self {
+            ends: [a, b],
+            radius,
+            rotation: 0.0,
+            cooldown_seconds: 1.0,
+            color,
+            time: 0.0,
+            ready_time: 0.0,
+        }
+    }
+
+    pub fn get_ends(&self) -> [DVec2; 2] {
+        self.ends
+    }
+
+    pub fn get_radius(&self) -> f64 {
+        self.radius
+    }
+
+    pub fn get_rotation(&self) -> f64 {
+        self.rotation
+    }
+
+    /// Turns balls' velocity by this many radians on the way through. Going back the other way
+    /// turns them back.
+    pub fn set_rotation(&mut self, rotation: f64) {
+        self.rotation = rotation;
+    }
+
+    pub fn get_cooldown_seconds(&self) -> f64 {
+        self.cooldown_seconds
+    }
+
+    pub fn set_cooldown_seconds(&mut self, cooldown_seconds: f64) {
+        self.cooldown_seconds = cooldown_seconds;
+    }
+
+    pub fn get_color(&self) -> Srgba {
+        self.color
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.time >= self.ready_time
+    }
+
+    pub fn update(&self, dt: f64) -> Self {
+        let mut new_portal = self.clone();
+        new_portal.time += dt;
+        new_portal
+    }
+
+    /// Which end the ball has dropped into, if the portal is ready. A ball is in once its centre
+    /// is.
+    pub fn get_entrance(&self, ball: &Ball) -> Option<usize> {
+        if !self.is_ready() {
+            return None;
+        }
+
+        self.ends
+            .iter()
+            .position(|end| end.distance(ball.get_position()) < self.radius)
+    }
+
+    /// The ball's velocity once it's been through from the end it went into.
+    fn get_exit_velocity(&self, entrance: usize, ball: &Ball) -> DVec2 {
+        let rotation = if entrance == 0 {
+            self.rotation
+        } else {
+            -self.rotation
+        };
+
+        DVec2::from_angle(rotation).rotate(ball.get_velocity())
+    }
+
+    /// Where a ball that went into this end comes out, just clear of the other end in the
+    /// direction it's going.
+    pub fn get_exit(&self, entrance: usize, ball: &Ball) -> DVec2 {
+        let direction = self
+            .get_exit_velocity(entrance, ball)
+            .try_normalize()
+            .unwrap_or(DVec2::Y);
+
+        self.ends[1 - entrance] + direction * (self.radius + ball.get_radius())
+    }
+
+    /// Sends a ball through from the end it went into, out at `exit`, and starts recharging.
+    pub fn send(&mut self, entrance: usize, ball: &mut Ball, exit: DVec2) {
+        ball.set_velocity(self.get_exit_velocity(entrance, ball));
+        ball.set_position(exit);
+
+        self.ready_time = self.time + self.cooldown_seconds;
+    }
+}
+
+impl Render for Portal {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let charge = if self.cooldown_seconds > 0.0 {
+            (1.0 - (self.ready_time - self.time) / self.cooldown_seconds).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+
+        let color = Srgba::new(
+            self.color.red,
+            self.color.green,
+            self.color.blue,
+            self.color.alpha * (0.3 + 0.7 * charge as f32),
+        );
+
+        for (index, end) in self.ends.iter().enumerate() {
+            // The two ends swirl opposite ways
+            let spin = if index == 0 { 1.0 } else { -1.0 } * self.time * 3.0;
+
+            renderer.render_circle_lines(*end, self.radius, 3.0, color);
+
+            for i in 0..6 {
+                let angle = spin + i as f64 * PI / 3.0;
+
+                renderer.render_circle(
+                    *end + DVec2::from_angle(angle) * self.radius * 0.6 * charge,
+                    2.0,
+                    color,
+                );
+            }
+        }
+    }
+}
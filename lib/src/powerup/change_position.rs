@@ -97,6 +97,14 @@ impl ChangePosition {
     pub fn get_position(&self) -> DVec2 {
         self.position + 2.0 * (self.time * 4.0).sin() * DVec2::Y
     }
+
+    pub fn get_x_range(&self) -> RangeInclusive<f64> {
+        self.x_range.clone()
+    }
+
+    pub fn get_y_range(&self) -> RangeInclusive<f64> {
+        self.y_range.clone()
+    }
 }
 
 impl Powerup for ChangePosition {
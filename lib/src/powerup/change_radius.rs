@@ -0,0 +1,105 @@
+use glam::DVec2;
+use palette::Srgba;
+use render_agnostic::Renderer;
+
+use crate::{ball::Ball, powerup::Powerup, rendering::Render};
+
+pub struct ChangeRadiusConfig {
+    radius: f64,
+    amount: f64,
+}
+
+impl ChangeRadiusConfig {
+    pub fn new(radius: f64, amount: f64) -> Self {
+        Self { radius, amount }
+    }
+
+    pub fn build(&self, position: DVec2) -> ChangeRadius {
+        ChangeRadius::new(position, self.radius, self.amount)
+    }
+}
+
+/// Grows the ball when `amount` is over 1, and shrinks it when it's under.
+#[derive(Clone)]
+pub struct ChangeRadius {
+    time: f64,
+    position: DVec2,
+    radius: f64,
+    amount: f64,
+    is_active: bool,
+}
+
+impl ChangeRadius {
+    pub fn new(position: DVec2, radius: f64, amount: f64) -> Self {
+        Self {
+            time: 0.0,
+            position,
+            radius,
+            amount,
+            is_active: true,
+        }
+    }
+
+    pub fn get_position(&self) -> DVec2 {
+        self.position + 2.0 * (self.time * 4.0).sin() * DVec2::Y
+    }
+
+    pub fn get_name(&self) -> &str {
+        if self.amount >= 1.0 { "Grow" } else { "Shrink" }
+    }
+}
+
+impl Powerup for ChangeRadius {
+    fn is_colliding_with(&self, ball: &Ball) -> bool {
+        self.get_position().distance(ball.get_position()) < self.radius + ball.get_radius()
+    }
+
+    fn apply(&self, ball: &mut Ball) {
+        ball.set_radius(ball.get_radius() * self.amount);
+    }
+
+    fn consume(&mut self) {
+        self.is_active = false;
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    fn update(&self, dt: f64) -> Box<dyn Powerup> {
+        let mut new_powerup = self.clone();
+
+        new_powerup.time += dt;
+
+        Box::new(new_powerup)
+    }
+}
+
+impl Render for ChangeRadius {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let color = Srgba::new(0.3, 1.0, 0.4, 1.0);
+
+        if self.is_active {
+            renderer.render_circle_lines(self.get_position(), 8.0, 1.0, color);
+
+            // Breathes between its own size and the size it turns the ball into
+            let scale = 1.0 + (self.amount - 1.0) * (0.5 + 0.5 * (self.time * 3.0).sin());
+
+            renderer.render_circle(
+                self.get_position(),
+                6.0 * scale,
+                Srgba::new(0.3, 1.0, 0.4, 0.5),
+            );
+
+            renderer.render_text_outline(
+                self.get_name(),
+                self.get_position() - DVec2::Y * 2.0 * self.radius,
+                anchor2d::CGB,
+                20.0,
+                1.0,
+                color,
+                Srgba::new(0.0, 0.0, 0.0, 1.0),
+            );
+        }
+    }
+}
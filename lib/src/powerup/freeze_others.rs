@@ -0,0 +1,106 @@
+use std::f64::consts::PI;
+
+use glam::DVec2;
+use palette::Srgba;
+use render_agnostic::Renderer;
+
+use crate::{ball::Ball, powerup::Powerup, rendering::Render};
+
+pub struct FreezeOthersConfig {
+    radius: f64,
+    seconds: f64,
+}
+
+impl FreezeOthersConfig {
+    pub fn new(radius: f64, seconds: f64) -> Self {
+        Self { radius, seconds }
+    }
+
+    pub fn build(&self, position: DVec2) -> FreezeOthers {
+        FreezeOthers::new(position, self.radius, self.seconds)
+    }
+}
+
+/// Stops every marble except the one that picked it up in place for a while. The scene does the
+/// freezing, since it's the other balls that are affected.
+#[derive(Clone)]
+pub struct FreezeOthers {
+    time: f64,
+    position: DVec2,
+    radius: f64,
+    seconds: f64,
+    is_active: bool,
+}
+
+impl FreezeOthers {
+    pub fn new(position: DVec2, radius: f64, seconds: f64) -> Self {
+        Self {
+            time: 0.0,
+            position,
+            radius,
+            seconds,
+            is_active: true,
+        }
+    }
+
+    pub fn get_position(&self) -> DVec2 {
+        self.position + 2.0 * (self.time * 4.0).sin() * DVec2::Y
+    }
+
+    pub fn get_seconds(&self) -> f64 {
+        self.seconds
+    }
+}
+
+impl Powerup for FreezeOthers {
+    fn is_colliding_with(&self, ball: &Ball) -> bool {
+        self.get_position().distance(ball.get_position()) < self.radius + ball.get_radius()
+    }
+
+    fn apply(&self, _ball: &mut Ball) {}
+
+    fn consume(&mut self) {
+        self.is_active = false;
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    fn update(&self, dt: f64) -> Box<dyn Powerup> {
+        let mut new_powerup = self.clone();
+
+        new_powerup.time += dt;
+
+        Box::new(new_powerup)
+    }
+}
+
+impl Render for FreezeOthers {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let color = Srgba::new(0.75, 0.9, 1.0, 1.0);
+
+        if self.is_active {
+            let position = self.get_position();
+
+            renderer.render_circle_lines(position, 8.0, 1.0, color);
+
+            // A slowly turning snowflake
+            for i in 0..3 {
+                let direction = DVec2::from_angle(self.time * 0.5 + PI * i as f64 / 3.0) * 6.0;
+
+                renderer.render_line(position - direction, position + direction, 1.0, color);
+            }
+
+            renderer.render_text_outline(
+                "Freeze",
+                position - DVec2::Y * 2.0 * self.radius,
+                anchor2d::CGB,
+                20.0,
+                1.0,
+                color,
+                Srgba::new(0.0, 0.0, 0.0, 1.0),
+            );
+        }
+    }
+}
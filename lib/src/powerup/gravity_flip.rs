@@ -0,0 +1,127 @@
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::Renderer;
+
+use crate::{
+    ball::{
+        Ball,
+        status_effect::{Stat, StatusEffect},
+    },
+    powerup::Powerup,
+    rendering::Render,
+};
+
+pub struct GravityFlipConfig {
+    radius: f64,
+    seconds: f64,
+}
+
+impl GravityFlipConfig {
+    pub fn new(radius: f64, seconds: f64) -> Self {
+        Self { radius, seconds }
+    }
+
+    pub fn build(&self, position: DVec2) -> GravityFlip {
+        GravityFlip::new(position, self.radius, self.seconds)
+    }
+}
+
+/// Makes the ball fall upwards for a while.
+#[derive(Clone)]
+pub struct GravityFlip {
+    time: f64,
+    position: DVec2,
+    radius: f64,
+    seconds: f64,
+    is_active: bool,
+}
+
+impl GravityFlip {
+    pub fn new(position: DVec2, radius: f64, seconds: f64) -> Self {
+        Self {
+            time: 0.0,
+            position,
+            radius,
+            seconds,
+            is_active: true,
+        }
+    }
+
+    pub fn get_position(&self) -> DVec2 {
+        self.position + 2.0 * (self.time * 4.0).sin() * DVec2::Y
+    }
+}
+
+impl Powerup for GravityFlip {
+    fn is_colliding_with(&self, ball: &Ball) -> bool {
+        self.get_position().distance(ball.get_position()) < self.radius + ball.get_radius()
+    }
+
+    fn apply(&self, ball: &mut Ball) {
+        ball.get_physics_ball_mut()
+            .add_status_effect(StatusEffect::new(
+                "Gravity flip",
+                Stat::Gravity,
+                -1.0,
+                self.seconds,
+            ));
+    }
+
+    fn consume(&mut self) {
+        self.is_active = false;
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    fn update(&self, dt: f64) -> Box<dyn Powerup> {
+        let mut new_powerup = self.clone();
+
+        new_powerup.time += dt;
+
+        Box::new(new_powerup)
+    }
+}
+
+impl Render for GravityFlip {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let color = Srgba::new(1.0, 0.6, 0.1, 1.0);
+
+        if self.is_active {
+            let position = self.get_position();
+
+            renderer.render_circle_lines(position, 8.0, 1.0, color);
+
+            // An arrow pointing up
+            renderer.render_line(
+                position + dvec2(0.0, 5.0),
+                position - dvec2(0.0, 5.0),
+                1.0,
+                color,
+            );
+            renderer.render_line(
+                position - dvec2(0.0, 5.0),
+                position + dvec2(-3.0, -2.0),
+                1.0,
+                color,
+            );
+            renderer.render_line(
+                position - dvec2(0.0, 5.0),
+                position + dvec2(3.0, -2.0),
+                1.0,
+                color,
+            );
+
+            renderer.render_text_outline(
+                "Gravity Flip",
+                position - DVec2::Y * 2.0 * self.radius,
+                anchor2d::CGB,
+                20.0,
+                1.0,
+                color,
+                Srgba::new(0.0, 0.0, 0.0, 1.0),
+            );
+        }
+    }
+}
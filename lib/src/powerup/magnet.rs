@@ -0,0 +1,125 @@
+use glam::DVec2;
+use palette::Srgba;
+use render_agnostic::Renderer;
+
+use crate::{
+    ball::{
+        Ball,
+        status_effect::{Stacking, Stat, StatusEffect},
+    },
+    powerup::Powerup,
+    rendering::Render,
+};
+
+/// How close another marble has to be to a magnetic ball to get pulled in.
+pub const MAGNET_RANGE: f64 = 160.0;
+
+pub struct MagnetConfig {
+    radius: f64,
+    seconds: f64,
+    strength: f64,
+}
+
+impl MagnetConfig {
+    pub fn new(radius: f64, seconds: f64, strength: f64) -> Self {
+        Self {
+            radius,
+            seconds,
+            strength,
+        }
+    }
+
+    pub fn build(&self, position: DVec2) -> Magnet {
+        Magnet::new(position, self.radius, self.seconds, self.strength)
+    }
+}
+
+/// Makes the ball pull nearby marbles towards it for a while.
+#[derive(Clone)]
+pub struct Magnet {
+    time: f64,
+    position: DVec2,
+    radius: f64,
+    seconds: f64,
+    strength: f64,
+    is_active: bool,
+}
+
+impl Magnet {
+    pub fn new(position: DVec2, radius: f64, seconds: f64, strength: f64) -> Self {
+        Self {
+            time: 0.0,
+            position,
+            radius,
+            seconds,
+            strength,
+            is_active: true,
+        }
+    }
+
+    pub fn get_position(&self) -> DVec2 {
+        self.position + 2.0 * (self.time * 4.0).sin() * DVec2::Y
+    }
+}
+
+impl Powerup for Magnet {
+    fn is_colliding_with(&self, ball: &Ball) -> bool {
+        self.get_position().distance(ball.get_position()) < self.radius + ball.get_radius()
+    }
+
+    fn apply(&self, ball: &mut Ball) {
+        let mut effect = StatusEffect::new("Magnet", Stat::Magnet, self.strength, self.seconds);
+        // Every magnet a ball picks up adds to its pull until that one wears off
+        effect.set_stacking(Stacking::Stack);
+
+        ball.get_physics_ball_mut().add_status_effect(effect);
+    }
+
+    fn consume(&mut self) {
+        self.is_active = false;
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    fn update(&self, dt: f64) -> Box<dyn Powerup> {
+        let mut new_powerup = self.clone();
+
+        new_powerup.time += dt;
+
+        Box::new(new_powerup)
+    }
+}
+
+impl Render for Magnet {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let color = Srgba::new(0.9, 0.3, 0.9, 1.0);
+
+        if self.is_active {
+            renderer.render_circle_lines(self.get_position(), 8.0, 1.0, color);
+
+            // Rings closing in on the magnet
+            for i in 0..3 {
+                let phase = (self.time + i as f64 / 3.0).fract();
+
+                renderer.render_circle_lines(
+                    self.get_position(),
+                    8.0 + 16.0 * (1.0 - phase),
+                    1.0,
+                    Srgba::new(0.9, 0.3, 0.9, phase as f32),
+                );
+            }
+
+            renderer.render_text_outline(
+                "Magnet",
+                self.get_position() - DVec2::Y * 2.0 * self.radius,
+                anchor2d::CGB,
+                20.0,
+                1.0,
+                color,
+                Srgba::new(0.0, 0.0, 0.0, 1.0),
+            );
+        }
+    }
+}
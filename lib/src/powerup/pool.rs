@@ -0,0 +1,392 @@
+use std::fmt::Display;
+
+use glam::DVec2;
+use rand::{Rng, RngExt, seq::IndexedRandom};
+use serde::{Deserialize, Deserializer, de::Error};
+
+use crate::{
+    level::PowerupSpace,
+    powerup::{
+        Powerup, change_density::ChangeDensity, change_elasticity::ChangeElasticity,
+        change_position::ChangePosition, change_radius::ChangeRadius, freeze_others::FreezeOthers,
+        gravity_flip::GravityFlip, magnet::Magnet, shield::Shield, special::Special,
+        speed_burst::SpeedBurst, split::Split,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerupKind {
+    ChangeElasticity,
+    ChangeDensity,
+    ChangePosition,
+    Special,
+    Shield,
+    Magnet,
+    ChangeRadius,
+    GravityFlip,
+    SpeedBurst,
+    FreezeOthers,
+    Split,
+}
+
+impl Display for PowerupKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::ChangeElasticity => "change_elasticity",
+            Self::ChangeDensity => "change_density",
+            Self::ChangePosition => "change_position",
+            Self::Special => "special",
+            Self::Shield => "shield",
+            Self::Magnet => "magnet",
+            Self::ChangeRadius => "change_radius",
+            Self::GravityFlip => "gravity_flip",
+            Self::SpeedBurst => "speed_burst",
+            Self::FreezeOthers => "freeze_others",
+            Self::Split => "split",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// Rarer powerups turn up less often than their weight alone would have them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+    Legendary,
+}
+
+impl Rarity {
+    pub fn get_weight(&self) -> f64 {
+        match self {
+            Self::Common => 1.0,
+            Self::Uncommon => 0.5,
+            Self::Rare => 0.2,
+            Self::Legendary => 0.05,
+        }
+    }
+}
+
+impl Display for Rarity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Common => "common",
+            Self::Uncommon => "uncommon",
+            Self::Rare => "rare",
+            Self::Legendary => "legendary",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+fn default_radius() -> f64 {
+    8.0
+}
+
+/// Reads a `(min, max)` range to draw from, rejecting ones that can't be drawn from.
+fn deserialize_range<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<(f64, f64)>, D::Error> {
+    let maybe_range = Option::<(f64, f64)>::deserialize(deserializer)?;
+
+    if let Some((min, max)) = maybe_range
+        && !(min.is_finite() && max.is_finite() && min <= max)
+    {
+        return Err(D::Error::custom(format!(
+            "invalid range ({}, {}), expected finite numbers with min <= max",
+            min, max
+        )));
+    }
+
+    Ok(maybe_range)
+}
+
+/// One kind of powerup a pool can draw. Its strength and duration are drawn from `amount` and
+/// `seconds` each time, or fall back to the kind's usual values when they're left out.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PowerupPoolEntry {
+    pub kind: PowerupKind,
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+    #[serde(default)]
+    pub rarity: Option<Rarity>,
+    #[serde(default = "default_radius")]
+    pub radius: f64,
+    /// The multiplier for density, elasticity and radius, the pull of a magnet, the speed of a
+    /// speed burst or the number of copies of a split.
+    #[serde(default, deserialize_with = "deserialize_range")]
+    pub amount: Option<(f64, f64)>,
+    /// How long shields, magnets, gravity flips and freezes last.
+    #[serde(default, deserialize_with = "deserialize_range")]
+    pub seconds: Option<(f64, f64)>,
+}
+
+impl PowerupPoolEntry {
+    pub fn new(kind: PowerupKind, weight: f64) -> Self {
+        Self {
+            kind,
+            weight,
+            rarity: None,
+            radius: default_radius(),
+            amount: None,
+            seconds: None,
+        }
+    }
+
+    /// How likely this entry is to be drawn, relative to the rest of the pool.
+    pub fn get_weight(&self) -> f64 {
+        self.weight * self.rarity.map_or(1.0, |rarity| rarity.get_weight())
+    }
+
+    /// Builds the powerup at `position`. Teleports land anywhere inside `bounds`.
+    pub fn build(
+        &self,
+        rng: &mut impl Rng,
+        position: DVec2,
+        bounds: (f64, f64),
+    ) -> Box<dyn Powerup> {
+        let mut amount = |default: f64| {
+            self.amount
+                .map_or(default, |(min, max)| rng.random_range(min..=max))
+        };
+
+        let amount = match self.kind {
+            PowerupKind::ChangeElasticity => amount(0.95),
+            PowerupKind::ChangeDensity => amount(2.0),
+            PowerupKind::ChangeRadius => amount(1.5),
+            PowerupKind::Magnet => amount(1500.0),
+            PowerupKind::SpeedBurst => amount(400.0),
+            PowerupKind::Split => amount(3.0),
+            _ => 0.0,
+        };
+
+        let mut seconds = |default: f64| {
+            self.seconds
+                .map_or(default, |(min, max)| rng.random_range(min..=max))
+        };
+
+        let seconds = match self.kind {
+            PowerupKind::Shield => seconds(8.0),
+            PowerupKind::Magnet => seconds(6.0),
+            PowerupKind::GravityFlip => seconds(1.5),
+            PowerupKind::FreezeOthers => seconds(2.0),
+            _ => 0.0,
+        };
+
+        match self.kind {
+            PowerupKind::ChangeElasticity => {
+                Box::new(ChangeElasticity::new(position, self.radius, amount))
+            }
+            PowerupKind::ChangeDensity => {
+                Box::new(ChangeDensity::new(position, self.radius, amount))
+            }
+            PowerupKind::ChangePosition => Box::new(ChangePosition::new(
+                position,
+                "Teleport",
+                self.radius,
+                16.0..=(bounds.0 - 16.0),
+                16.0..=(bounds.1 - 16.0),
+            )),
+            PowerupKind::Special => Box::new(Special::new(position, self.radius)),
+            PowerupKind::Shield => Box::new(Shield::new(position, self.radius, seconds)),
+            PowerupKind::Magnet => Box::new(Magnet::new(position, self.radius, seconds, amount)),
+            PowerupKind::ChangeRadius => Box::new(ChangeRadius::new(position, self.radius, amount)),
+            PowerupKind::GravityFlip => Box::new(GravityFlip::new(position, self.radius, seconds)),
+            PowerupKind::SpeedBurst => Box::new(SpeedBurst::new(position, self.radius, amount)),
+            PowerupKind::FreezeOthers => {
+                Box::new(FreezeOthers::new(position, self.radius, seconds))
+            }
+            PowerupKind::Split => Box::new(Split::new(
+                position,
+                self.radius,
+                amount.round().max(2.0) as usize,
+            )),
+        }
+    }
+}
+
+/// The powerups a level can hand out, with how likely each one is.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PowerupPool {
+    pub entries: Vec<PowerupPoolEntry>,
+    /// Seconds before a consumed powerup is replaced, for powerup spaces that don't set their own.
+    /// Without either, consumed powerups stay gone.
+    #[serde(default)]
+    pub respawn_seconds: Option<f64>,
+}
+
+impl PowerupPool {
+    pub fn new(entries: Vec<PowerupPoolEntry>) -> Self {
+        Self {
+            entries,
+            respawn_seconds: None,
+        }
+    }
+
+    /// Picks an entry by weight, or nothing if the pool is empty.
+    pub fn choose(&self, rng: &mut impl Rng) -> Option<&PowerupPoolEntry> {
+        self.entries
+            .choose_weighted(rng, |entry| entry.get_weight())
+            .ok()
+    }
+}
+
+impl Default for PowerupPool {
+    fn default() -> Self {
+        let entry = |kind, amount: Option<f64>| PowerupPoolEntry {
+            amount: amount.map(|amount| (amount, amount)),
+            ..PowerupPoolEntry::new(kind, 1.0)
+        };
+
+        Self::new(vec![
+            entry(PowerupKind::ChangeElasticity, Some(0.95)),
+            entry(PowerupKind::ChangeDensity, Some(2.0)),
+            entry(PowerupKind::ChangeDensity, Some(0.5)),
+            entry(PowerupKind::ChangePosition, None),
+            entry(PowerupKind::Special, None),
+            entry(PowerupKind::Shield, None),
+            entry(PowerupKind::Magnet, None),
+            entry(PowerupKind::ChangeRadius, Some(1.5)),
+            entry(PowerupKind::ChangeRadius, Some(0.67)),
+            entry(PowerupKind::GravityFlip, None),
+            entry(PowerupKind::SpeedBurst, None),
+            entry(PowerupKind::FreezeOthers, None),
+            entry(PowerupKind::Split, None),
+        ])
+    }
+}
+
+/// A powerup that was drawn during a race.
+#[derive(Debug, Clone)]
+pub struct ChosenPowerup {
+    pub kind: PowerupKind,
+    pub maybe_rarity: Option<Rarity>,
+    pub position: DVec2,
+    /// Seconds into the race, 0 for powerups that were there from the start.
+    pub time: f64,
+}
+
+#[derive(Debug, Clone)]
+struct PowerupSlot {
+    position: DVec2,
+    maybe_respawn_seconds: Option<f64>,
+    maybe_powerup_index: Option<usize>,
+    maybe_consumed_time: Option<f64>,
+}
+
+/// Keeps a level's powerup spaces stocked from its pool, replacing consumed powerups once their
+/// space's respawn timer runs out.
+#[derive(Debug, Clone)]
+pub struct PowerupSpawner {
+    pool: PowerupPool,
+    bounds: (f64, f64),
+    slots: Vec<PowerupSlot>,
+    chosen: Vec<ChosenPowerup>,
+}
+
+impl PowerupSpawner {
+    pub fn new(pool: PowerupPool, bounds: (f64, f64), powerup_spaces: &[PowerupSpace]) -> Self {
+        Self {
+            pool,
+            bounds,
+            slots: powerup_spaces
+                .iter()
+                .map(|powerup_space| PowerupSlot {
+                    position: powerup_space.get_position(),
+                    maybe_respawn_seconds: powerup_space.get_respawn_seconds(),
+                    maybe_powerup_index: None,
+                    maybe_consumed_time: None,
+                })
+                .collect(),
+            chosen: Vec::new(),
+        }
+    }
+
+    pub fn get_pool(&self) -> &PowerupPool {
+        &self.pool
+    }
+
+    /// Swaps the pool out, forgetting every powerup drawn from the old one.
+    pub fn set_pool(&mut self, pool: PowerupPool) {
+        self.pool = pool;
+        self.chosen.clear();
+    }
+
+    pub fn get_chosen(&self) -> &Vec<ChosenPowerup> {
+        &self.chosen
+    }
+
+    fn draw(&mut self, rng: &mut impl Rng, slot: usize, time: f64) -> Option<Box<dyn Powerup>> {
+        let position = self.slots[slot].position;
+        let entry = self.pool.choose(rng)?;
+
+        self.chosen.push(ChosenPowerup {
+            kind: entry.kind,
+            maybe_rarity: entry.rarity,
+            position,
+            time,
+        });
+
+        Some(entry.build(rng, position, self.bounds))
+    }
+
+    /// Draws a fresh powerup for every space, replacing whatever was there.
+    pub fn fill(&mut self, rng: &mut impl Rng, time: f64, powerups: &mut Vec<Box<dyn Powerup>>) {
+        for slot in 0..self.slots.len() {
+            let Some(powerup) = self.draw(rng, slot, time) else {
+                continue;
+            };
+
+            match self.slots[slot].maybe_powerup_index {
+                Some(index) => powerups[index] = powerup,
+                None => {
+                    self.slots[slot].maybe_powerup_index = Some(powerups.len());
+                    powerups.push(powerup);
+                }
+            }
+
+            self.slots[slot].maybe_consumed_time = None;
+        }
+    }
+
+    /// Notices newly consumed powerups and replaces the ones whose respawn timer has run out.
+    pub fn update(&mut self, rng: &mut impl Rng, time: f64, powerups: &mut [Box<dyn Powerup>]) {
+        for slot in 0..self.slots.len() {
+            let Some(index) = self.slots[slot].maybe_powerup_index else {
+                continue;
+            };
+
+            let Some(respawn_seconds) = self.slots[slot]
+                .maybe_respawn_seconds
+                .or(self.pool.respawn_seconds)
+            else {
+                continue;
+            };
+
+            if powerups[index].is_active() {
+                continue;
+            }
+
+            match self.slots[slot].maybe_consumed_time {
+                None => self.slots[slot].maybe_consumed_time = Some(time),
+                Some(consumed_time) if time >= consumed_time + respawn_seconds => {
+                    if let Some(powerup) = self.draw(rng, slot, time) {
+                        powerups[index] = powerup;
+                    }
+
+                    self.slots[slot].maybe_consumed_time = None;
+                }
+                Some(_) => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use glam::DVec2;
+use palette::Srgba;
+use render_agnostic::Renderer;
+
+use crate::{
+    ball::{
+        Ball,
+        status_effect::{Stat, StatusEffect},
+    },
+    powerup::Powerup,
+    rendering::Render,
+};
+
+pub struct ShieldConfig {
+    radius: f64,
+    seconds: f64,
+}
+
+impl ShieldConfig {
+    pub fn new(radius: f64, seconds: f64) -> Self {
+        Self { radius, seconds }
+    }
+
+    pub fn build(&self, position: DVec2) -> Shield {
+        Shield::new(position, self.radius, self.seconds)
+    }
+}
+
+/// Other marbles can't knock the ball around for a while.
+#[derive(Clone)]
+pub struct Shield {
+    time: f64,
+    position: DVec2,
+    radius: f64,
+    seconds: f64,
+    is_active: bool,
+}
+
+impl Shield {
+    pub fn new(position: DVec2, radius: f64, seconds: f64) -> Self {
+        Self {
+            time: 0.0,
+            position,
+            radius,
+            seconds,
+            is_active: true,
+        }
+    }
+
+    pub fn get_position(&self) -> DVec2 {
+        self.position + 2.0 * (self.time * 4.0).sin() * DVec2::Y
+    }
+}
+
+impl Powerup for Shield {
+    fn is_colliding_with(&self, ball: &Ball) -> bool {
+        self.get_position().distance(ball.get_position()) < self.radius + ball.get_radius()
+    }
+
+    fn apply(&self, ball: &mut Ball) {
+        ball.get_physics_ball_mut()
+            .add_status_effect(StatusEffect::new("Shield", Stat::Shield, 1.0, self.seconds));
+    }
+
+    fn consume(&mut self) {
+        self.is_active = false;
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    fn update(&self, dt: f64) -> Box<dyn Powerup> {
+        let mut new_powerup = self.clone();
+
+        new_powerup.time += dt;
+
+        Box::new(new_powerup)
+    }
+}
+
+impl Render for Shield {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let color = Srgba::new(0.3, 0.8, 1.0, 1.0);
+
+        if self.is_active {
+            renderer.render_circle_lines(self.get_position(), 8.0, 1.0, color);
+            renderer.render_circle_lines(
+                self.get_position(),
+                11.0 + (self.time * 3.0).sin(),
+                2.0,
+                Srgba::new(0.3, 0.8, 1.0, 0.5),
+            );
+
+            renderer.render_text_outline(
+                "Shield",
+                self.get_position() - DVec2::Y * 2.0 * self.radius,
+                anchor2d::CGB,
+                20.0,
+                1.0,
+                color,
+                Srgba::new(0.0, 0.0, 0.0, 1.0),
+            );
+        }
+    }
+}
@@ -51,6 +51,10 @@ impl Special {
         self.position + 2.0 * (self.time * 4.0).sin() * DVec2::Y
     }
 
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
+
     pub fn set_text(&mut self, text: impl Into<String>) {
         self.text = text.into();
     }
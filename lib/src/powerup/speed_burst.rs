@@ -0,0 +1,114 @@
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::Renderer;
+
+use crate::{ball::Ball, powerup::Powerup, rendering::Render};
+
+pub struct SpeedBurstConfig {
+    radius: f64,
+    amount: f64,
+}
+
+impl SpeedBurstConfig {
+    pub fn new(radius: f64, amount: f64) -> Self {
+        Self { radius, amount }
+    }
+
+    pub fn build(&self, position: DVec2) -> SpeedBurst {
+        SpeedBurst::new(position, self.radius, self.amount)
+    }
+}
+
+/// Adds `amount` to the ball's speed in whichever direction it's already going.
+#[derive(Clone)]
+pub struct SpeedBurst {
+    time: f64,
+    position: DVec2,
+    radius: f64,
+    amount: f64,
+    is_active: bool,
+}
+
+impl SpeedBurst {
+    pub fn new(position: DVec2, radius: f64, amount: f64) -> Self {
+        Self {
+            time: 0.0,
+            position,
+            radius,
+            amount,
+            is_active: true,
+        }
+    }
+
+    pub fn get_position(&self) -> DVec2 {
+        self.position + 2.0 * (self.time * 4.0).sin() * DVec2::Y
+    }
+}
+
+impl Powerup for SpeedBurst {
+    fn is_colliding_with(&self, ball: &Ball) -> bool {
+        self.get_position().distance(ball.get_position()) < self.radius + ball.get_radius()
+    }
+
+    fn apply(&self, ball: &mut Ball) {
+        ball.set_velocity(
+            ball.get_velocity() + ball.get_velocity().normalize_or_zero() * self.amount,
+        );
+    }
+
+    fn consume(&mut self) {
+        self.is_active = false;
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    fn update(&self, dt: f64) -> Box<dyn Powerup> {
+        let mut new_powerup = self.clone();
+
+        new_powerup.time += dt;
+
+        Box::new(new_powerup)
+    }
+}
+
+impl Render for SpeedBurst {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let color = Srgba::new(1.0, 1.0, 0.2, 1.0);
+
+        if self.is_active {
+            let position = self.get_position();
+
+            renderer.render_circle_lines(position, 8.0, 1.0, color);
+
+            // Two chevrons sliding to the right
+            for i in 0..2 {
+                let x = -4.0 + 6.0 * i as f64 + 2.0 * (self.time * 6.0).sin();
+
+                renderer.render_line(
+                    position + dvec2(x - 2.0, -4.0),
+                    position + dvec2(x + 2.0, 0.0),
+                    1.0,
+                    color,
+                );
+                renderer.render_line(
+                    position + dvec2(x - 2.0, 4.0),
+                    position + dvec2(x + 2.0, 0.0),
+                    1.0,
+                    color,
+                );
+            }
+
+            renderer.render_text_outline(
+                "Speed Burst",
+                position - DVec2::Y * 2.0 * self.radius,
+                anchor2d::CGB,
+                20.0,
+                1.0,
+                color,
+                Srgba::new(0.0, 0.0, 0.0, 1.0),
+            );
+        }
+    }
+}
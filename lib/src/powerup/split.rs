@@ -0,0 +1,108 @@
+use std::f64::consts::TAU;
+
+use glam::DVec2;
+use palette::Srgba;
+use render_agnostic::Renderer;
+
+use crate::{ball::Ball, powerup::Powerup, rendering::Render};
+
+pub struct SplitConfig {
+    radius: f64,
+    copies: usize,
+}
+
+impl SplitConfig {
+    pub fn new(radius: f64, copies: usize) -> Self {
+        Self { radius, copies }
+    }
+
+    pub fn build(&self, position: DVec2) -> Split {
+        Split::new(position, self.radius, self.copies)
+    }
+}
+
+/// Splits the ball into this many smaller copies of itself. The scene does the splitting, and
+/// only the first copy to finish counts.
+#[derive(Clone)]
+pub struct Split {
+    time: f64,
+    position: DVec2,
+    radius: f64,
+    copies: usize,
+    is_active: bool,
+}
+
+impl Split {
+    pub fn new(position: DVec2, radius: f64, copies: usize) -> Self {
+        Self {
+            time: 0.0,
+            position,
+            radius,
+            copies,
+            is_active: true,
+        }
+    }
+
+    pub fn get_position(&self) -> DVec2 {
+        self.position + 2.0 * (self.time * 4.0).sin() * DVec2::Y
+    }
+
+    pub fn get_copies(&self) -> usize {
+        self.copies
+    }
+}
+
+impl Powerup for Split {
+    fn is_colliding_with(&self, ball: &Ball) -> bool {
+        self.get_position().distance(ball.get_position()) < self.radius + ball.get_radius()
+    }
+
+    fn apply(&self, _ball: &mut Ball) {}
+
+    fn consume(&mut self) {
+        self.is_active = false;
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    fn update(&self, dt: f64) -> Box<dyn Powerup> {
+        let mut new_powerup = self.clone();
+
+        new_powerup.time += dt;
+
+        Box::new(new_powerup)
+    }
+}
+
+impl Render for Split {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let color = Srgba::new(1.0, 0.5, 0.8, 1.0);
+
+        if self.is_active {
+            let position = self.get_position();
+
+            renderer.render_circle_lines(position, 8.0, 1.0, color);
+
+            // Little copies drifting apart and back together
+            let spread = 3.0 + 2.0 * (self.time * 3.0).sin();
+
+            for i in 0..self.copies {
+                let angle = self.time + TAU * i as f64 / self.copies as f64;
+
+                renderer.render_circle(position + DVec2::from_angle(angle) * spread, 2.5, color);
+            }
+
+            renderer.render_text_outline(
+                &format!("Split x{}", self.copies),
+                position - DVec2::Y * 2.0 * self.radius,
+                anchor2d::CGB,
+                20.0,
+                1.0,
+                color,
+                Srgba::new(0.0, 0.0, 0.0, 1.0),
+            );
+        }
+    }
+}
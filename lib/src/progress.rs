@@ -0,0 +1,57 @@
+use glam::DVec2;
+
+pub type ProgressFunction = dyn Fn(DVec2) -> f64 + Send + Sync;
+
+/// Progress for levels that are a straight drop to the goal.
+pub fn vertical_progress(scene_height: f64) -> impl Fn(DVec2) -> f64 + Send + Sync + 'static {
+    move |position| (position.y / scene_height).clamp(0.0, 1.0)
+}
+
+/// Progress for arenas, where being closer to the middle is better.
+pub fn radial_progress(
+    center: DVec2,
+    radius: f64,
+) -> impl Fn(DVec2) -> f64 + Send + Sync + 'static {
+    move |position| (1.0 - position.distance(center) / radius).clamp(0.0, 1.0)
+}
+
+/// Progress for levels made of shelves, where every tier is a funnel that sends marbles to the
+/// middle followed by a ridge that sends them back out to the sides.
+pub fn shelf_progress(
+    scene_width: f64,
+    scene_height: f64,
+    first_y: f64,
+    offset: f64,
+    tiers: usize,
+    ridge_y: f64,
+) -> impl Fn(DVec2) -> f64 + Send + Sync + 'static {
+    let funnel_depth = 25.0;
+    let tier_start = first_y - (offset - ridge_y - funnel_depth);
+    let tiers_end = tier_start + offset * tiers as f64;
+    let segments = tiers as f64 + 1.0;
+
+    move |position| {
+        if position.y < tier_start {
+            return 0.0;
+        }
+
+        if position.y >= tiers_end {
+            let remaining = ((position.y - tiers_end) / (scene_height - tiers_end)).clamp(0.0, 1.0);
+
+            return (tiers as f64 + remaining) / segments;
+        }
+
+        let tier = ((position.y - tier_start) / offset).floor();
+        let tier_y = position.y - (first_y + offset * tier);
+        let distance_from_middle =
+            ((position.x - scene_width / 2.0).abs() / (scene_width / 2.0)).clamp(0.0, 1.0);
+
+        let within_tier = if tier_y < (funnel_depth + ridge_y) / 2.0 {
+            0.5 * (1.0 - distance_from_middle)
+        } else {
+            0.5 + 0.5 * distance_from_middle
+        };
+
+        (tier + within_tier) / segments
+    }
+}
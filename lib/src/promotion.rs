@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use database::{
+    league::{DbLeague, DbLeagueMarble, DbLeagueMove},
+    marble::DbMarble,
+    season::DbSeason,
+};
+use sqlx::SqlitePool;
+
+use crate::SeasonConfig;
+
+/// How long after a promotion or relegation it still gets talked about.
+const ANNOUNCEMENT_DAYS: i64 = 7;
+
+/// The league's current marbles, best finisher in the season first. Marbles that didn't race in
+/// the season rank below everyone who did.
+async fn get_ranking(
+    pool: &SqlitePool,
+    league: &DbLeague,
+    season: &DbSeason,
+) -> sqlx::Result<Vec<i64>> {
+    let standings = season.get_standings(pool).await?;
+
+    let mut members = DbMarble::get_by_league(pool, &league.name)
+        .await?
+        .into_iter()
+        .map(|marble| marble.id)
+        .collect::<Vec<i64>>();
+
+    members.sort_by_key(|marble_id| {
+        standings
+            .iter()
+            .position(|standing| standing.marble_id == *marble_id)
+            .unwrap_or(usize::MAX)
+    });
+
+    Ok(members)
+}
+
+/// Promotes the top of each league and relegates the bottom of the one above it, using each
+/// league's most recently ended season. Two neighbouring leagues only swap marbles once both of
+/// their seasons have ended, so this is meant to run after every league's season is over. Returns
+/// the moves that were made.
+pub async fn settle_seasons(
+    pool: &SqlitePool,
+    config: &SeasonConfig,
+    now: DateTime<Utc>,
+) -> sqlx::Result<Vec<DbLeagueMove>> {
+    let leagues = DbLeague::get_all(pool).await?;
+
+    let mut seasons = Vec::new();
+
+    for league in leagues.iter() {
+        let maybe_season = DbSeason::get_ended_by_league_id(pool, league.id, now)
+            .await?
+            .into_iter()
+            .next()
+            .filter(|season| season.settled == 0);
+
+        seasons.push(maybe_season);
+    }
+
+    let mut moves = HashMap::new();
+    let mut settled = Vec::new();
+
+    for (index, pair) in leagues.windows(2).enumerate() {
+        let (upper, lower) = (&pair[0], &pair[1]);
+
+        let (Some(upper_season), Some(lower_season)) = (&seasons[index], &seasons[index + 1])
+        else {
+            continue;
+        };
+
+        let upper_ranking = get_ranking(pool, upper, upper_season).await?;
+        let lower_ranking = get_ranking(pool, lower, lower_season).await?;
+
+        for marble_id in upper_ranking
+            .iter()
+            .rev()
+            .take(config.relegated.min(upper_ranking.len()))
+        {
+            moves.entry(*marble_id).or_insert(lower.id);
+        }
+
+        for marble_id in lower_ranking
+            .iter()
+            .take(config.promoted.min(lower_ranking.len()))
+        {
+            moves.entry(*marble_id).or_insert(upper.id);
+        }
+
+        settled.push(index);
+        settled.push(index + 1);
+    }
+
+    for (marble_id, league_id) in moves {
+        DbLeagueMarble::transfer(pool, marble_id, league_id, now).await?;
+    }
+
+    for index in settled {
+        if let Some(season) = seasons[index].as_mut()
+            && season.settled == 0
+        {
+            season.settle(pool).await?;
+        }
+    }
+
+    DbLeagueMarble::get_moves_since(pool, now).await
+}
+
+/// Recent promotions and relegations, at most one per marble, for engagements to announce.
+pub async fn get_announcements(
+    pool: &SqlitePool,
+    now: DateTime<Utc>,
+) -> sqlx::Result<Vec<DbLeagueMove>> {
+    let mut announcements: Vec<DbLeagueMove> = Vec::new();
+
+    for league_move in
+        DbLeagueMarble::get_moves_since(pool, now - TimeDelta::days(ANNOUNCEMENT_DAYS)).await?
+    {
+        if !announcements
+            .iter()
+            .any(|announcement| announcement.marble_id == league_move.marble_id)
+        {
+            announcements.push(league_move);
+        }
+    }
+
+    Ok(announcements)
+}
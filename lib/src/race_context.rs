@@ -0,0 +1,14 @@
+use database::season::DbSeason;
+
+use crate::{elimination::Elimination, tournament::Bracket};
+
+/// What a race is part of, for graphics and engagements that talk about more than the race itself.
+#[derive(Debug, Clone, Default)]
+pub struct RaceContext {
+    pub maybe_league_id: Option<i64>,
+    pub maybe_season: Option<DbSeason>,
+    pub finale: bool,
+    pub maybe_bracket: Option<Bracket>,
+    /// How marbles get knocked out until one is left, in elimination races.
+    pub maybe_elimination: Option<Elimination>,
+}
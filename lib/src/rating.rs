@@ -0,0 +1,244 @@
+use std::{cmp::Ordering, f64::consts::PI};
+
+use database::{race::DbRace, rating::DbRating};
+use sqlx::SqlitePool;
+
+/// Converts between the Glicko scale and the internal Glicko-2 scale.
+const SCALE: f64 = 173.7178;
+/// Constrains how quickly volatility can change.
+const TAU: f64 = 0.5;
+const CONVERGENCE_TOLERANCE: f64 = 0.000001;
+
+pub const DEFAULT_RATING: f64 = 1500.0;
+pub const DEFAULT_DEVIATION: f64 = 350.0;
+pub const DEFAULT_VOLATILITY: f64 = 0.06;
+
+/// A Glicko-2 rating, stored on the familiar Glicko scale where new marbles start at 1500.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glicko {
+    pub rating: f64,
+    pub deviation: f64,
+    pub volatility: f64,
+}
+
+impl Default for Glicko {
+    fn default() -> Self {
+        Self {
+            rating: DEFAULT_RATING,
+            deviation: DEFAULT_DEVIATION,
+            volatility: DEFAULT_VOLATILITY,
+        }
+    }
+}
+
+impl From<DbRating> for Glicko {
+    fn from(value: DbRating) -> Self {
+        Self {
+            rating: value.rating,
+            deviation: value.deviation,
+            volatility: value.volatility,
+        }
+    }
+}
+
+fn g(phi: f64) -> f64 {
+    1.0 / (1.0 + 3.0 * phi.powi(2) / PI.powi(2)).sqrt()
+}
+
+fn expected_score(mu: f64, opponent_mu: f64, opponent_phi: f64) -> f64 {
+    1.0 / (1.0 + (-g(opponent_phi) * (mu - opponent_mu)).exp())
+}
+
+impl Glicko {
+    /// The rating after one rating period against the given opponents, where each score is 1.0
+    /// for finishing ahead of the opponent, 0.5 for a tie and 0.0 for finishing behind.
+    pub fn update(&self, results: &[(Glicko, f64)]) -> Self {
+        let mu = (self.rating - DEFAULT_RATING) / SCALE;
+        let phi = self.deviation / SCALE;
+        let sigma = self.volatility;
+
+        if results.is_empty() {
+            return Self {
+                deviation: (phi.powi(2) + sigma.powi(2)).sqrt() * SCALE,
+                ..*self
+            };
+        }
+
+        let opponents = results
+            .iter()
+            .map(|(opponent, score)| {
+                let opponent_mu = (opponent.rating - DEFAULT_RATING) / SCALE;
+                let opponent_phi = opponent.deviation / SCALE;
+
+                (
+                    g(opponent_phi),
+                    expected_score(mu, opponent_mu, opponent_phi),
+                    *score,
+                )
+            })
+            .collect::<Vec<(f64, f64, f64)>>();
+
+        let v = 1.0
+            / opponents
+                .iter()
+                .map(|(g, expected, _)| g.powi(2) * expected * (1.0 - expected))
+                .sum::<f64>();
+
+        let improvement = opponents
+            .iter()
+            .map(|(g, expected, score)| g * (score - expected))
+            .sum::<f64>();
+
+        let delta = v * improvement;
+
+        let new_sigma = Self::new_volatility(phi, sigma, v, delta);
+
+        let phi_star = (phi.powi(2) + new_sigma.powi(2)).sqrt();
+        let new_phi = 1.0 / (1.0 / phi_star.powi(2) + 1.0 / v).sqrt();
+        let new_mu = mu + new_phi.powi(2) * improvement;
+
+        Self {
+            rating: new_mu * SCALE + DEFAULT_RATING,
+            deviation: new_phi * SCALE,
+            volatility: new_sigma,
+        }
+    }
+
+    /// Solves for the new volatility with the Illinois algorithm from the Glicko-2 paper.
+    fn new_volatility(phi: f64, sigma: f64, v: f64, delta: f64) -> f64 {
+        let a = sigma.powi(2).ln();
+
+        let f = |x: f64| {
+            x.exp() * (delta.powi(2) - phi.powi(2) - v - x.exp())
+                / (2.0 * (phi.powi(2) + v + x.exp()).powi(2))
+                - (x - a) / TAU.powi(2)
+        };
+
+        let mut big_a = a;
+        let mut big_b = if delta.powi(2) > phi.powi(2) + v {
+            (delta.powi(2) - phi.powi(2) - v).ln()
+        } else {
+            let mut k = 1.0;
+
+            while f(a - k * TAU) < 0.0 {
+                k += 1.0;
+            }
+
+            a - k * TAU
+        };
+
+        let mut f_a = f(big_a);
+        let mut f_b = f(big_b);
+
+        while (big_b - big_a).abs() > CONVERGENCE_TOLERANCE {
+            let big_c = big_a + (big_a - big_b) * f_a / (f_b - f_a);
+            let f_c = f(big_c);
+
+            if f_c * f_b <= 0.0 {
+                big_a = big_b;
+                f_a = f_b;
+            } else {
+                f_a /= 2.0;
+            }
+
+            big_b = big_c;
+            f_b = f_c;
+        }
+
+        (big_a / 2.0).exp()
+    }
+}
+
+/// New ratings for a race's field, given with each marble's place, treating the race as every
+/// marble playing every other marble once. Marbles on the same place tie.
+pub fn rate_race(field: &[(Glicko, i64)]) -> Vec<Glicko> {
+    field
+        .iter()
+        .enumerate()
+        .map(|(index, (glicko, place))| {
+            let results = field
+                .iter()
+                .enumerate()
+                .filter(|(other_index, _)| *other_index != index)
+                .map(|(_, (other, other_place))| {
+                    let score = match place.cmp(other_place) {
+                        Ordering::Less => 1.0,
+                        Ordering::Equal => 0.5,
+                        Ordering::Greater => 0.0,
+                    };
+
+                    (*other, score)
+                })
+                .collect::<Vec<(Glicko, f64)>>();
+
+            glicko.update(&results)
+        })
+        .collect()
+}
+
+/// Updates the ratings of everyone who finished a race, in the race's league.
+pub async fn update_ratings_for_race(pool: &SqlitePool, race: &DbRace) -> sqlx::Result<()> {
+    update_ratings_in_league(pool, race, race.league_id).await
+}
+
+async fn update_ratings_in_league(
+    pool: &SqlitePool,
+    race: &DbRace,
+    maybe_league_id: Option<i64>,
+) -> sqlx::Result<()> {
+    let race_marbles = race.get_marbles(pool).await?;
+
+    let mut field = Vec::new();
+
+    for race_marble in race_marbles.iter() {
+        field.push(DbRating::get(pool, race_marble.marble_id, maybe_league_id).await?);
+    }
+
+    let new_ratings = rate_race(
+        &field
+            .iter()
+            .zip(race_marbles.iter())
+            .map(|(maybe_rating, race_marble)| {
+                (
+                    maybe_rating.map(Glicko::from).unwrap_or_default(),
+                    race_marble.place,
+                )
+            })
+            .collect::<Vec<(Glicko, i64)>>(),
+    );
+
+    for ((race_marble, maybe_rating), new_rating) in race_marbles.iter().zip(field).zip(new_ratings)
+    {
+        DbRating::upsert(
+            pool,
+            race_marble.marble_id,
+            maybe_league_id,
+            new_rating.rating,
+            new_rating.deviation,
+            new_rating.volatility,
+            maybe_rating.map(|rating| rating.races).unwrap_or_default() + 1,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Recomputes every rating from scratch by replaying all recorded races in order. Races from
+/// before races kept their league count for the league their whole field was in at the time.
+pub async fn backfill_ratings(pool: &SqlitePool) -> sqlx::Result<usize> {
+    DbRating::delete_all(pool).await?;
+
+    let races = DbRace::get_all(pool).await?;
+
+    for race in races.iter() {
+        let maybe_league_id = match race.league_id {
+            Some(league_id) => Some(league_id),
+            None => race.get_roster_league_id(pool).await?,
+        };
+
+        update_ratings_in_league(pool, race, maybe_league_id).await?;
+    }
+
+    Ok(races.len())
+}
@@ -1,24 +1,55 @@
-use std::{any::Any, f64::consts::PI, sync::Arc, time::Duration};
+use std::{
+    any::Any,
+    cmp::Reverse,
+    f64::consts::PI,
+    ops::RangeInclusive,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 use ::rand::random_range;
 use dyn_clone::DynClone;
 use glam::{DVec2, dvec2};
 use palette::Srgba;
 use particula_rs::{ParticleEmitter, ParticleSystem, VecParticleSystem};
-use rand::{rng, seq::SliceRandom};
+use rand::{RngExt, SeedableRng, rng, rngs::StdRng, seq::SliceRandom};
 use render_agnostic::Renderer;
 
 use crate::{
-    ball::Ball,
+    ball::{
+        Ball, PhysicsBall,
+        status_effect::{Stacking, Stat, StatusEffect},
+    },
     collision::Collision,
+    elimination::Elimination,
+    event::RaceEvent,
+    lap::LapTracker,
     particle::{ConfettiParticle, ParticleLayer, RenderParticle, ShrinkingParticle},
-    powerup::{Powerup, special::Special},
+    portal::Portal,
+    powerup::{
+        Powerup,
+        change_position::ChangePosition,
+        freeze_others::FreezeOthers,
+        magnet::MAGNET_RANGE,
+        pool::{ChosenPowerup, PowerupPool, PowerupSpawner},
+        special::Special,
+        split::Split,
+    },
+    progress::ProgressFunction,
     rendering::Render,
     simulation::Simulation,
-    wall::Wall,
+    solver::{self, Contact, Solver},
+    start::{GATE_RETRACT_SECONDS, Start},
+    team::{TeamResult, TeamScoring, Teams},
+    timeline::{Action, Timeline},
+    wall::{
+        Wall, gate_wall::GateWall, hazard_wall::Hazard, straight_wall::Line, trigger_wall::Trigger,
+    },
 };
 
 const MIN_OVERLAP: f64 = 0.01;
+pub const FRICTION: f64 = 0.3;
+const CLEAR_POSITION_ATTEMPTS: usize = 100;
 
 pub trait SceneParticleEmitter:
     ParticleEmitter<ParticleType = Box<dyn RenderParticle<DVec2>>> + Send + Sync + DynClone
@@ -39,8 +70,31 @@ pub struct Scene {
     walls: Vec<Box<dyn Wall>>,
     winners: Vec<usize>,
     win_times: Vec<Duration>,
+    eliminated: Vec<usize>,
+    elimination_times: Vec<Duration>,
+    maybe_elimination: Option<Elimination>,
+    maybe_laps: Option<usize>,
+    lap_trackers: Vec<LapTracker>,
+    maybe_drops: Option<usize>,
+    scores: Vec<Vec<u32>>,
+    spawn_positions: Vec<DVec2>,
+    checkpoint_positions: Vec<DVec2>,
+    touching_hazards: Vec<Vec<usize>>,
+    touching_trapdoors: Vec<Vec<usize>>,
+    maybe_teams: Option<Teams>,
+    ball_teams: Vec<Option<usize>>,
+    families: Vec<usize>,
+    maybe_start: Option<Start>,
+    timeline: Timeline,
+    portals: Vec<Portal>,
+    maybe_powerup_spawner: Option<PowerupSpawner>,
+    solver: Solver,
+    /// Shared between every step's copy of the scene, so that it keeps drawing new numbers.
+    rng: Arc<Mutex<StdRng>>,
+    events: Vec<RaceEvent>,
     particles: SceneParticleSystem,
     finished_condition: Arc<dyn Fn(&Simulation) -> bool + Send + Sync>,
+    progress_function: Arc<ProgressFunction>,
 }
 
 impl Scene {
@@ -50,7 +104,20 @@ impl Scene {
         powerups: Vec<Box<dyn Powerup>>,
         walls: Vec<Box<dyn Wall>>,
         finished_condition: Arc<dyn Fn(&Simulation) -> bool + Send + Sync>,
+        progress_function: Arc<ProgressFunction>,
     ) -> Self {
+        let lap_trackers = vec![LapTracker::default(); balls.len()];
+        let scores = vec![Vec::new(); balls.len()];
+        let spawn_positions = balls
+            .iter()
+            .map(|ball| ball.get_position())
+            .collect::<Vec<DVec2>>();
+        let checkpoint_positions = spawn_positions.clone();
+        let touching_hazards = vec![Vec::new(); balls.len()];
+        let touching_trapdoors = vec![Vec::new(); balls.len()];
+        let ball_teams = vec![None; balls.len()];
+        let families = (0..balls.len()).collect();
+
         Self {
             level_id,
             time: 0.0,
@@ -59,8 +126,30 @@ impl Scene {
             walls,
             winners: Vec::new(),
             win_times: Vec::new(),
+            eliminated: Vec::new(),
+            elimination_times: Vec::new(),
+            maybe_elimination: None,
+            maybe_laps: None,
+            lap_trackers,
+            maybe_drops: None,
+            scores,
+            spawn_positions,
+            checkpoint_positions,
+            touching_hazards,
+            touching_trapdoors,
+            maybe_teams: None,
+            ball_teams,
+            families,
+            maybe_start: None,
+            timeline: Timeline::new(),
+            portals: Vec::new(),
+            maybe_powerup_spawner: None,
+            solver: Solver::default(),
+            rng: Arc::new(Mutex::new(StdRng::from_rng(&mut rng()))),
+            events: Vec::new(),
             particles: VecParticleSystem::default(),
             finished_condition,
+            progress_function,
         }
     }
 
@@ -68,6 +157,11 @@ impl Scene {
         self.level_id
     }
 
+    /// Seconds since "Go!".
+    pub fn get_time(&self) -> f64 {
+        self.time
+    }
+
     pub fn get_balls(&self) -> &Vec<Ball> {
         &self.balls
     }
@@ -77,9 +171,89 @@ impl Scene {
     }
 
     pub fn get_current_winner(&self) -> Option<&Ball> {
-        self.get_balls()
+        self.get_standings()
+            .first()
+            .and_then(|index| self.get_balls().get(*index))
+    }
+
+    /// How far along the race a ball is, from 0 at the start to 1 at the finish. In lap races
+    /// this counts checkpoints crossed, using the level's progress function to break ties between
+    /// checkpoints.
+    pub fn get_progress(&self, index: usize) -> f64 {
+        let position_progress = (self.progress_function)(self.get_balls()[index].get_position());
+
+        match self.get_laps() {
+            Some(laps) => {
+                let checkpoints = (laps * self.get_checkpoint_count() + 1) as f64;
+
+                ((self.lap_trackers[index].get_splits().len() as f64
+                    + position_progress.clamp(0.0, 0.99))
+                    / checkpoints)
+                    .min(1.0)
+            }
+            None => position_progress,
+        }
+    }
+
+    /// Indices of the balls that have neither finished nor been eliminated.
+    pub fn get_racing(&self) -> Vec<usize> {
+        (0..self.get_balls().len())
+            .filter(|index| {
+                !self.get_winners().contains(index) && !self.get_balls()[*index].is_eliminated()
+            })
+            .collect()
+    }
+
+    /// Ball indices from first to last place, finishers in finishing order followed by everyone
+    /// still racing ordered by how far along the level they are, followed by eliminated balls with
+    /// the most recently eliminated first. In scoring races everyone who hasn't been eliminated is
+    /// ordered by total score instead, with ties going to whoever finished first.
+    pub fn get_standings(&self) -> Vec<usize> {
+        let mut racing = self.get_racing();
+
+        racing.sort_by(|a, b| self.get_progress(*b).total_cmp(&self.get_progress(*a)));
+
+        let mut standings = self
+            .get_winners()
             .iter()
-            .max_by_key(|ball| ball.get_position().y as i32)
+            .copied()
+            .chain(racing)
+            .collect::<Vec<usize>>();
+
+        if self.get_drops().is_some() {
+            standings.sort_by_key(|index| Reverse(self.get_total_score(*index)));
+        }
+
+        standings
+            .into_iter()
+            .chain(self.get_eliminated().iter().rev().copied())
+            .collect()
+    }
+
+    /// Final places so far, with the time each ball finished or was eliminated. Balls that are
+    /// still racing aren't included.
+    pub fn get_placements(&self) -> Vec<(usize, Duration)> {
+        let mut finishers = self
+            .get_winners()
+            .iter()
+            .copied()
+            .zip(self.get_win_times().iter().copied())
+            .collect::<Vec<(usize, Duration)>>();
+
+        if self.get_drops().is_some() {
+            finishers.sort_by_key(|(index, _)| Reverse(self.get_total_score(*index)));
+        }
+
+        finishers
+            .into_iter()
+            .chain(
+                self.get_eliminated()
+                    .iter()
+                    .copied()
+                    .zip(self.get_elimination_times().iter().copied())
+                    .rev(),
+            )
+            .collect()
     }
 
     pub fn get_walls(&self) -> &Vec<Box<dyn Wall>> {
@@ -98,12 +272,808 @@ impl Scene {
         &self.win_times
     }
 
+    /// Ball indices in the order they were eliminated.
+    pub fn get_eliminated(&self) -> &Vec<usize> {
+        &self.eliminated
+    }
+
+    pub fn get_elimination_times(&self) -> &Vec<Duration> {
+        &self.elimination_times
+    }
+
+    /// Laps to complete in a circuit race. Balls finish by crossing checkpoint 0 for the last
+    /// time instead of touching a goal.
+    pub fn get_laps(&self) -> Option<usize> {
+        self.maybe_laps
+    }
+
+    pub fn set_laps(&mut self, maybe_laps: Option<usize>) {
+        self.maybe_laps = maybe_laps;
+    }
+
+    /// Checkpoints crossed by each ball, by ball index.
+    pub fn get_lap_trackers(&self) -> &Vec<LapTracker> {
+        &self.lap_trackers
+    }
+
+    pub fn get_checkpoint_count(&self) -> usize {
+        self.get_walls()
+            .iter()
+            .filter_map(|wall| match wall.get_trigger() {
+                Some(Trigger::Checkpoint(checkpoint)) => Some(checkpoint + 1),
+                _ => None,
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// How many times each ball drops in a scoring race. Every goal a ball lands on adds its score
+    /// to the ball's total and sends it back to where it started, until it's out of drops.
+    pub fn get_drops(&self) -> Option<usize> {
+        self.maybe_drops
+    }
+
+    pub fn set_drops(&mut self, maybe_drops: Option<usize>) {
+        self.maybe_drops = maybe_drops;
+    }
+
+    /// The score of each drop so far, by ball index.
+    pub fn get_scores(&self) -> &Vec<Vec<u32>> {
+        &self.scores
+    }
+
+    pub fn get_total_score(&self, index: usize) -> u32 {
+        self.scores[index].iter().sum()
+    }
+
+    pub fn get_teams(&self) -> Option<&Teams> {
+        self.maybe_teams.as_ref()
+    }
+
+    /// Makes this a team race, with `ball_teams` giving the team each ball races for.
+    pub fn set_teams(&mut self, maybe_teams: Option<Teams>, ball_teams: Vec<Option<usize>>) {
+        self.ball_teams = ball_teams;
+        self.maybe_teams = maybe_teams;
+    }
+
+    /// The index of the team the ball races for.
+    pub fn get_ball_team(&self, index: usize) -> Option<usize> {
+        self.ball_teams[index]
+    }
+
+    /// Every team's result so far, best first.
+    pub fn get_team_results(&self) -> Vec<TeamResult> {
+        let Some(teams) = self.get_teams() else {
+            return Vec::new();
+        };
+
+        let mut results = (0..teams.get_teams().len())
+            .map(|team| TeamResult {
+                team,
+                place_total: 0,
+                marbles: 0,
+                finishers: 0,
+            })
+            .collect::<Vec<TeamResult>>();
+
+        for (place, index) in self.get_standings().into_iter().enumerate() {
+            if let Some(team) = self.get_ball_team(index) {
+                results[team].place_total += place + 1;
+                results[team].marbles += 1;
+
+                if self.get_winners().contains(&index) {
+                    results[team].finishers += 1;
+                }
+            }
+        }
+
+        teams.rank(&mut results);
+
+        results
+    }
+
+    /// The team that has won, once the result can't change any more.
+    pub fn get_winning_team(&self) -> Option<usize> {
+        match self.get_teams()?.get_scoring() {
+            TeamScoring::AveragePlace => self
+                .all_won()
+                .then(|| self.get_team_results().first().map(|result| result.team))
+                .flatten(),
+            TeamScoring::FirstTo(finishers) => {
+                let mut counts = vec![0; self.get_teams()?.get_teams().len()];
+
+                self.get_winners().iter().find_map(|index| {
+                    let team = self.get_ball_team(*index)?;
+                    counts[team] += 1;
+
+                    (counts[team] >= finishers).then_some(team)
+                })
+            }
+        }
+    }
+
+    pub fn get_start(&self) -> Option<Start> {
+        self.maybe_start
+    }
+
+    /// Sets how the balls get going. A start with a gate puts the gate just under the balls'
+    /// starting positions, so this should only be called once, before the countdown.
+    pub fn set_start(&mut self, maybe_start: Option<Start>) {
+        if let Some(start) = maybe_start
+            && start.gate
+            && !self.balls.is_empty()
+        {
+            let (left, right, bottom) = self.balls.iter().fold(
+                (f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+                |(left, right, bottom), ball| {
+                    (
+                        left.min(ball.get_position().x),
+                        right.max(ball.get_position().x),
+                        bottom.max(ball.get_position().y + ball.get_radius()),
+                    )
+                },
+            );
+
+            let margin = if self.balls.len() > 1 {
+                (right - left) / (self.balls.len() - 1) as f64 / 2.0
+            } else {
+                64.0
+            };
+
+            self.walls.push(Box::new(GateWall::new(
+                Line::new(
+                    dvec2(left - margin, bottom + 4.0),
+                    dvec2(right + margin, bottom + 4.0),
+                ),
+                GATE_RETRACT_SECONDS,
+            )));
+        }
+
+        self.maybe_start = maybe_start;
+    }
+
+    /// Lets the balls fall into place against the starting gate during the countdown. Only the
+    /// balls move, and neither the race clock nor the balls' own clocks run.
+    pub fn settle(&self, dt: f64, timescale: f64, physics_steps: usize) -> (Self, Vec<Collision>) {
+        let step_dt = dt * timescale / physics_steps as f64;
+
+        let mut new_scene = self.clone();
+        let mut collisions = Vec::new();
+
+        for _ in 0..physics_steps {
+            let (_, resolved_collisions_scene, mut step_collisions) = new_scene.solve(step_dt);
+
+            new_scene.balls = resolved_collisions_scene
+                .get_balls()
+                .iter()
+                .map(|ball| {
+                    let mut new_ball = ball.update(step_dt);
+                    new_ball.set_time(ball.get_time());
+                    new_ball
+                })
+                .collect();
+
+            collisions.append(&mut step_collisions);
+        }
+
+        (new_scene, collisions)
+    }
+
+    /// Launches the balls and holds back the ones with a staggered release, at "Go!".
+    fn release(&mut self, start: Start) {
+        let mut order = (0..self.balls.len()).collect::<Vec<usize>>();
+        order.sort_by(|a, b| {
+            self.balls[*a]
+                .get_position()
+                .x
+                .total_cmp(&self.balls[*b].get_position().x)
+        });
+
+        for (position, index) in order.into_iter().enumerate() {
+            let ball = &mut self.balls[index];
+            ball.set_velocity(ball.get_velocity() + start.launch_velocity);
+
+            let hold_seconds = start.stagger_seconds * position as f64;
+
+            if hold_seconds > 0.0 {
+                let physics_ball = ball.get_physics_ball_mut();
+
+                physics_ball.add_status_effect(StatusEffect::new(
+                    "Held back",
+                    Stat::Speed,
+                    0.0,
+                    hold_seconds,
+                ));
+                physics_ball.add_status_effect(StatusEffect::new(
+                    "Held back",
+                    Stat::Gravity,
+                    0.0,
+                    hold_seconds,
+                ));
+            }
+        }
+    }
+
+    /// Reseeds the random number generator that powerups are drawn with.
+    pub fn set_rng(&mut self, rng: StdRng) {
+        self.rng = Arc::new(Mutex::new(rng));
+    }
+
+    pub fn get_powerup_spawner(&self) -> Option<&PowerupSpawner> {
+        self.maybe_powerup_spawner.as_ref()
+    }
+
+    /// Hands the powerup spaces over to a spawner, which stocks them straight away.
+    pub fn set_powerup_spawner(&mut self, maybe_powerup_spawner: Option<PowerupSpawner>) {
+        self.maybe_powerup_spawner = maybe_powerup_spawner;
+
+        if let Some(powerup_spawner) = self.maybe_powerup_spawner.as_mut() {
+            powerup_spawner.fill(
+                &mut *self.rng.lock().unwrap(),
+                self.time,
+                &mut self.powerups,
+            );
+        }
+    }
+
+    /// Restocks every powerup space from a different pool, like one loaded from the config.
+    pub fn set_powerup_pool(&mut self, powerup_pool: PowerupPool) {
+        if let Some(powerup_spawner) = self.maybe_powerup_spawner.as_mut() {
+            powerup_spawner.set_pool(powerup_pool);
+            powerup_spawner.fill(
+                &mut *self.rng.lock().unwrap(),
+                self.time,
+                &mut self.powerups,
+            );
+        }
+    }
+
+    /// Every powerup drawn so far, including the ones the race started with.
+    pub fn get_chosen_powerups(&self) -> Vec<ChosenPowerup> {
+        self.get_powerup_spawner()
+            .map(|powerup_spawner| powerup_spawner.get_chosen().clone())
+            .unwrap_or_default()
+    }
+
+    fn restock_powerups(&mut self) {
+        if let Some(powerup_spawner) = self.maybe_powerup_spawner.as_mut() {
+            powerup_spawner.update(
+                &mut *self.rng.lock().unwrap(),
+                self.time,
+                &mut self.powerups,
+            );
+        }
+    }
+
+    pub fn get_portals(&self) -> &Vec<Portal> {
+        &self.portals
+    }
+
+    pub fn get_solver(&self) -> Solver {
+        self.solver
+    }
+
+    pub fn set_solver(&mut self, solver: Solver) {
+        self.solver = solver;
+    }
+
+    pub fn set_portals(&mut self, portals: Vec<Portal>) {
+        self.portals = portals;
+    }
+
+    /// Whether the ball at `index` would fit here without touching any wall or another ball.
+    pub fn is_clear_position(&self, index: usize, position: DVec2) -> bool {
+        let mut probe = self.balls[index].get_physics_ball().clone();
+        probe.set_position(position);
+
+        self.get_walls()
+            .iter()
+            .all(|wall| wall.get_intersection_point(&probe).is_none())
+            && self
+                .get_balls()
+                .iter()
+                .enumerate()
+                .filter(|(other_index, other_ball)| {
+                    *other_index != index && !other_ball.is_eliminated()
+                })
+                .all(|(_, other_ball)| {
+                    position.distance(other_ball.get_position())
+                        >= probe.get_radius() + other_ball.get_radius()
+                })
+    }
+
+    /// Looks for somewhere in these ranges where the ball at `index` fits without touching any
+    /// wall or another ball, giving up after `CLEAR_POSITION_ATTEMPTS` tries.
+    pub fn find_clear_position(
+        &self,
+        index: usize,
+        x_range: RangeInclusive<f64>,
+        y_range: RangeInclusive<f64>,
+    ) -> Option<DVec2> {
+        let mut rng = self.rng.lock().unwrap();
+
+        (0..CLEAR_POSITION_ATTEMPTS).find_map(|_| {
+            let position = dvec2(
+                rng.random_range(x_range.clone()),
+                rng.random_range(y_range.clone()),
+            );
+
+            self.is_clear_position(index, position).then_some(position)
+        })
+    }
+
+    pub fn get_timeline(&self) -> &Timeline {
+        &self.timeline
+    }
+
+    pub fn set_timeline(&mut self, timeline: Timeline) {
+        self.timeline = timeline;
+    }
+
+    /// Fires every timeline entry whose cue has just been met.
+    fn apply_timeline(&mut self) {
+        let due = self
+            .timeline
+            .get_entries()
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| !entry.is_fired() && entry.get_cue().is_met(self))
+            .map(|(index, _)| index)
+            .collect::<Vec<usize>>();
+
+        for index in due {
+            let entry = &mut self.timeline.get_entries_mut()[index];
+            entry.set_fired(true);
+
+            let action = entry.get_action().clone();
+            self.run_action(action);
+        }
+    }
+
+    fn set_walls_open(&mut self, id: &str, open: bool) {
+        for wall in self.walls.iter_mut() {
+            if wall.get_id() == Some(id) {
+                wall.set_open(open);
+            }
+        }
+    }
+
+    fn run_action(&mut self, action: Action) {
+        match action {
+            Action::OpenWall(id) => self.set_walls_open(&id, true),
+            Action::CloseWall(id) => self.set_walls_open(&id, false),
+            Action::SpawnPowerup(powerup) => self.powerups.push(powerup),
+            Action::FlipGravity(seconds) => {
+                for index in self.get_racing() {
+                    self.balls[index]
+                        .get_physics_ball_mut()
+                        .add_status_effect(StatusEffect::new(
+                            "Gravity flip",
+                            Stat::Gravity,
+                            -1.0,
+                            seconds,
+                        ));
+                }
+            }
+            Action::SpecialOnLastPlace => {
+                let racing = self.get_racing();
+
+                if let Some(last) = self
+                    .get_standings()
+                    .into_iter()
+                    .rev()
+                    .find(|index| racing.contains(index))
+                {
+                    self.powerups
+                        .push(Box::new(Special::new(self.balls[last].get_position(), 8.0)));
+                }
+            }
+        }
+    }
+
+    /// Which of the balls the scene started with this ball is, or was split from. Copies of a
+    /// split marble share a family, and only one of them can finish or be eliminated.
+    pub fn get_family(&self, index: usize) -> usize {
+        self.families[index]
+    }
+
+    /// How many marbles are in the race, counting every copy of a split marble once.
+    pub fn get_family_count(&self) -> usize {
+        let mut families = self.families.clone();
+        families.sort();
+        families.dedup();
+        families.len()
+    }
+
+    /// Splits a racing ball into this many smaller copies of itself, fanned out around the way
+    /// it's going. The copies are added to the end of `get_balls`.
+    pub fn split_ball(&mut self, index: usize, copies: usize) {
+        if copies < 2 || !self.get_racing().contains(&index) {
+            return;
+        }
+
+        let ball = self.balls[index].clone();
+        let radius = ball.get_radius() / (copies as f64).sqrt();
+        let side = ball
+            .get_velocity()
+            .perp()
+            .try_normalize()
+            .unwrap_or(DVec2::X);
+
+        for copy in 0..copies {
+            let offset = copy as f64 - (copies - 1) as f64 / 2.0;
+
+            let mut new_ball = ball.clone();
+            new_ball.set_radius(radius);
+            new_ball.set_position(ball.get_position() + side * offset * radius * 2.0);
+            new_ball.set_velocity(DVec2::from_angle(offset * 0.3).rotate(ball.get_velocity()));
+
+            if copy == 0 {
+                self.balls[index] = new_ball;
+            } else {
+                self.balls.push(new_ball);
+                self.lap_trackers.push(self.lap_trackers[index].clone());
+                self.scores.push(self.scores[index].clone());
+                self.spawn_positions.push(self.spawn_positions[index]);
+                self.checkpoint_positions
+                    .push(self.checkpoint_positions[index]);
+                self.touching_hazards
+                    .push(self.touching_hazards[index].clone());
+                self.touching_trapdoors
+                    .push(self.touching_trapdoors[index].clone());
+                self.ball_teams.push(self.ball_teams[index]);
+                self.families.push(self.families[index]);
+            }
+        }
+
+        for _ in 0..30 {
+            self.particles.add_particle(Box::new(ShrinkingParticle::new(
+                ball.get_position(),
+                DVec2::from_angle(random_range(0.0..(2.0 * PI))) * random_range(50.0..=200.0),
+                random_range(2.0..=4.0),
+                ball.get_name_color(),
+                random_range(0.25..=0.5),
+                ParticleLayer::Front,
+            )));
+        }
+    }
+
+    /// Takes a ball out of the scene entirely, shifting every later ball's index down by one. Any
+    /// finish or event it had goes with it.
+    pub fn remove_ball(&mut self, index: usize) {
+        self.balls.remove(index);
+        self.lap_trackers.remove(index);
+        self.scores.remove(index);
+        self.spawn_positions.remove(index);
+        self.checkpoint_positions.remove(index);
+        self.touching_hazards.remove(index);
+        self.touching_trapdoors.remove(index);
+        self.ball_teams.remove(index);
+        self.families.remove(index);
+
+        if let Some(place) = self.winners.iter().position(|winner| *winner == index) {
+            self.winners.remove(place);
+            self.win_times.remove(place);
+
+            for event in self.events.iter_mut() {
+                if let RaceEvent::Finished {
+                    place: finished_place,
+                    ..
+                } = event
+                    && *finished_place > place
+                {
+                    *finished_place -= 1;
+                }
+            }
+        }
+
+        if let Some(place) = self
+            .eliminated
+            .iter()
+            .position(|eliminated| *eliminated == index)
+        {
+            self.eliminated.remove(place);
+            self.elimination_times.remove(place);
+        }
+
+        self.events.retain(|event| event.get_ball() != Some(index));
+
+        let shift = |other: &mut usize| {
+            if *other > index {
+                *other -= 1;
+            }
+        };
+
+        self.winners.iter_mut().for_each(shift);
+        self.eliminated.iter_mut().for_each(shift);
+        self.events
+            .iter_mut()
+            .filter_map(|event| event.get_ball_mut())
+            .for_each(shift);
+    }
+
+    /// Removes the copies of split marbles that no longer count: every copy but the one that
+    /// finished or was eliminated, and copies lost while another copy was still racing.
+    fn prune_copies(&mut self) {
+        let removals = (0..self.balls.len())
+            .filter(|index| {
+                let is_lost =
+                    self.balls[*index].is_eliminated() && !self.eliminated.contains(index);
+
+                let maybe_decider = self
+                    .winners
+                    .iter()
+                    .chain(self.eliminated.iter())
+                    .find(|other| self.get_family(**other) == self.get_family(*index));
+
+                is_lost || maybe_decider.is_some_and(|decider| decider != index)
+            })
+            .collect::<Vec<usize>>();
+
+        for index in removals.into_iter().rev() {
+            self.remove_ball(index);
+        }
+    }
+
+    pub fn get_elimination(&self) -> Option<&Elimination> {
+        self.maybe_elimination.as_ref()
+    }
+
+    pub fn set_elimination(&mut self, maybe_elimination: Option<Elimination>) {
+        self.maybe_elimination = maybe_elimination;
+    }
+
+    /// Takes a ball out of the race in last place. It stays in `get_balls` so that indices don't
+    /// shift.
+    pub fn eliminate(&mut self, index: usize) {
+        if self.balls[index].is_eliminated() || self.winners.contains(&index) {
+            return;
+        }
+
+        let ball = &mut self.balls[index];
+        ball.set_eliminated(true);
+
+        for _ in 0..50 {
+            self.particles.add_particle(Box::new(ShrinkingParticle::new(
+                ball.get_position(),
+                DVec2::from_angle(random_range(0.0..(2.0 * PI))) * random_range(50.0..=400.0),
+                random_range(2.0..=6.0),
+                ball.get_name_color(),
+                random_range(0.5..=1.0),
+                ParticleLayer::Front,
+            )));
+        }
+
+        self.eliminated.push(index);
+        self.elimination_times
+            .push(Duration::from_secs_f64(self.time));
+        self.events.push(RaceEvent::Eliminated {
+            ball: index,
+            place: self.get_family_count() - self.eliminated.len(),
+            time: Duration::from_secs_f64(self.time),
+        });
+    }
+
+    /// Sends every ball touching lava or spikes back to its last checkpoint or out of the race,
+    /// and notes each hazard a ball has just run into. Returns the sounds they make.
+    fn apply_hazards(&mut self) -> Vec<Collision> {
+        let mut collisions = Vec::new();
+
+        for index in 0..self.balls.len() {
+            if !self.get_racing().contains(&index) {
+                self.touching_hazards[index].clear();
+                continue;
+            }
+
+            let ball = &self.balls[index];
+
+            let touching = self
+                .walls
+                .iter()
+                .enumerate()
+                .filter_map(|(wall_index, wall)| {
+                    wall.get_hazard()
+                        .filter(|_| ball.get_intersection_point(wall.as_ref()).is_some())
+                        .map(|hazard| (wall_index, hazard))
+                })
+                .collect::<Vec<(usize, Hazard)>>();
+
+            for (wall_index, hazard) in touching.iter().copied() {
+                if self.touching_hazards[index].contains(&wall_index) {
+                    continue;
+                }
+
+                self.events.push(RaceEvent::Hazard {
+                    ball: index,
+                    hazard,
+                    time: Duration::from_secs_f64(self.time),
+                });
+                collisions.push(Collision::new(
+                    ball.get_sound_path().to_path_buf(),
+                    1.0,
+                    ball.get_position(),
+                ));
+            }
+
+            self.touching_hazards[index] =
+                touching.iter().map(|(wall_index, _)| *wall_index).collect();
+
+            let maybe_respawn = touching.iter().find_map(|(_, hazard)| match hazard {
+                Hazard::Lava { respawn } | Hazard::Spikes { respawn } => Some(*respawn),
+                Hazard::Mud { .. } => None,
+            });
+
+            let family = self.get_family(index);
+            let is_last_marble_racing = !self.any_won()
+                && self
+                    .get_racing()
+                    .iter()
+                    .all(|other| self.get_family(*other) == family);
+
+            match maybe_respawn {
+                // Somebody has to win, so the last marble racing gets sent back instead
+                Some(respawn) if respawn || is_last_marble_racing => self.respawn(index),
+                Some(_) => self.knock_out(index),
+                None => {}
+            }
+        }
+
+        collisions
+    }
+
+    /// Puts a ball back just past the last checkpoint it crossed, or where it started if it hasn't
+    /// crossed one yet.
+    fn respawn(&mut self, index: usize) {
+        let ball = &mut self.balls[index];
+
+        for _ in 0..30 {
+            self.particles.add_particle(Box::new(ShrinkingParticle::new(
+                ball.get_position(),
+                DVec2::from_angle(random_range(0.0..(2.0 * PI))) * random_range(50.0..=200.0),
+                random_range(2.0..=4.0),
+                Srgba::new(0.5, 0.5, 0.5, 1.0),
+                random_range(0.25..=0.5),
+                ParticleLayer::Front,
+            )));
+        }
+
+        ball.set_position(self.checkpoint_positions[index]);
+        ball.set_velocity(DVec2::ZERO);
+
+        // Landing back on the checkpoint, if the ball hadn't got through it yet, isn't crossing it
+        // again
+        let touching = get_touching_checkpoints(&self.walls, &self.balls[index]);
+        self.lap_trackers[index].hold(touching);
+    }
+
+    /// Eliminates a ball, unless it's a copy of a split marble with other copies still racing, in
+    /// which case only the copy is lost.
+    fn knock_out(&mut self, index: usize) {
+        let family = self.get_family(index);
+
+        let has_racing_copies = self
+            .get_racing()
+            .iter()
+            .any(|other| *other != index && self.get_family(*other) == family);
+
+        if has_racing_copies {
+            self.balls[index].set_eliminated(true);
+        } else {
+            self.eliminate(index);
+        }
+    }
+
+    /// Whether any wall says the ball is out of bounds.
+    pub fn is_out_of_bounds(&self, index: usize) -> bool {
+        let physics_ball = self.get_balls()[index].get_physics_ball();
+
+        self.get_walls()
+            .iter()
+            .any(|wall| wall.is_out_of_bounds(physics_ball))
+    }
+
+    /// Eliminates every ball that is out of bounds and, when the elimination rule says so, the
+    /// last-place ball. With an elimination rule the last ball standing is declared the winner.
+    fn apply_elimination(&mut self) {
+        let mut racing = self.get_racing();
+
+        racing.sort_by(|a, b| self.get_progress(*b).total_cmp(&self.get_progress(*a)));
+
+        let out_of_bounds = racing
+            .iter()
+            .copied()
+            .filter(|index| self.is_out_of_bounds(*index))
+            .collect::<Vec<usize>>();
+
+        let in_bounds_families = racing
+            .iter()
+            .filter(|index| !out_of_bounds.contains(index))
+            .map(|index| self.get_family(*index))
+            .collect::<Vec<usize>>();
+
+        // A split marble only goes out with its last copy, so the best placed copy of each marble
+        // that's entirely out of bounds stands in for it and the rest of the copies are lost
+        let mut lost = Vec::new();
+        let mut out_families = Vec::new();
+        let mut out_of_bounds = out_of_bounds
+            .into_iter()
+            .filter(|index| {
+                let family = self.get_family(*index);
+
+                if in_bounds_families.contains(&family) || out_families.contains(&family) {
+                    lost.push(*index);
+                    false
+                } else {
+                    out_families.push(family);
+                    true
+                }
+            })
+            .collect::<Vec<usize>>();
+
+        for index in lost.iter() {
+            self.balls[*index].set_eliminated(true);
+        }
+
+        let mut racing_families = Vec::new();
+        racing.retain(|index| {
+            let family = self.get_family(*index);
+
+            !lost.contains(index) && !racing_families.contains(&family) && {
+                racing_families.push(family);
+                true
+            }
+        });
+
+        // Somebody has to win, so if everyone left goes out at once the best placed ball stays in
+        if !out_of_bounds.is_empty() && out_of_bounds.len() == racing.len() && !self.any_won() {
+            out_of_bounds.remove(0);
+        }
+
+        for index in out_of_bounds.iter().rev() {
+            self.eliminate(*index);
+        }
+
+        racing.retain(|index| !out_of_bounds.contains(index));
+
+        let Some(elimination) = self.get_elimination().cloned() else {
+            return;
+        };
+
+        let leader_progress = racing
+            .first()
+            .map(|index| self.get_progress(*index))
+            .unwrap_or_default();
+
+        if racing.len() > 1
+            && elimination.is_due(self.eliminated.len(), self.time, leader_progress)
+            && let Some(last) = racing.pop()
+        {
+            self.eliminate(last);
+        }
+
+        if let [survivor] = racing[..] {
+            self.winners.push(survivor);
+            self.win_times.push(Duration::from_secs_f64(self.time));
+            self.events.push(RaceEvent::Finished {
+                ball: survivor,
+                place: self.winners.len() - 1,
+                time: Duration::from_secs_f64(self.time),
+            });
+        }
+    }
+
+    /// Events that happened during the most recent call to `update`.
+    pub fn get_events(&self) -> &Vec<RaceEvent> {
+        &self.events
+    }
+
     pub fn any_won(&self) -> bool {
         !self.get_winners().is_empty()
     }
 
+    /// Whether every ball has either finished or been eliminated.
     pub fn all_won(&self) -> bool {
-        self.get_balls().len() == self.get_winners().len()
+        self.get_balls().len() == self.get_winners().len() + self.get_eliminated().len()
     }
 
     pub fn get_particles(&self) -> &SceneParticleSystem {
@@ -114,8 +1084,19 @@ impl Scene {
         Arc::clone(&self.finished_condition)
     }
 
+    pub fn get_progress_function(&self) -> Arc<ProgressFunction> {
+        Arc::clone(&self.progress_function)
+    }
+
     pub fn update(&self, dt: f64, timescale: f64, physics_steps: usize) -> (Self, Vec<Collision>) {
         let mut new_scene = self.clone();
+        new_scene.events.clear();
+
+        if self.time == 0.0
+            && let Some(start) = self.get_start()
+        {
+            new_scene.release(start);
+        }
 
         let step_dt = dt * timescale / physics_steps as f64;
 
@@ -131,12 +1112,11 @@ impl Scene {
     }
 
     pub fn step_physics(&self, dt: f64) -> (Self, Vec<Collision>) {
-        let stepped_velocities_scene = self.step_velocities(dt); // Overlapping old positions, new velocities
-        let (resolved_collisions_scene, collisions) = stepped_velocities_scene.resolve_collisions(); // NOT overlapping old positions, new velocities
+        let (stepped_velocities_scene, resolved_collisions_scene, collisions) = self.solve(dt);
 
         let new_time = resolved_collisions_scene.time + dt;
 
-        let new_walls = resolved_collisions_scene
+        let mut new_walls = resolved_collisions_scene
             .get_walls()
             .iter()
             .map(|wall| wall.update(dt))
@@ -144,11 +1124,66 @@ impl Scene {
 
         let mut should_shuffle = false;
         let mut should_bring_gifts = false;
+        let mut splits = Vec::new();
+        let mut new_events = resolved_collisions_scene.get_events().clone();
+
+        let mut new_touching_trapdoors = self.touching_trapdoors.clone();
+
+        for (wall_index, (wall, new_wall)) in stepped_velocities_scene
+            .get_walls()
+            .iter()
+            .zip(new_walls.iter_mut())
+            .enumerate()
+        {
+            let mut maybe_ball = None;
+
+            // Trapdoors that open from being hit count each ball that comes in hard once, until
+            // it leaves again, and don't count the ones resting on them. The approach speed is from
+            // before this step's bounce.
+            if new_wall.get_contacts_left().is_some() {
+                for (index, ball) in self.get_balls().iter().enumerate() {
+                    let touching_trapdoors = &mut new_touching_trapdoors[index];
+
+                    let Some(intersection_point) = ball
+                        .get_intersection_point(wall.as_ref())
+                        .filter(|_| !ball.is_eliminated())
+                    else {
+                        touching_trapdoors.retain(|touching| *touching != wall_index);
+                        continue;
+                    };
+
+                    if touching_trapdoors.contains(&wall_index) {
+                        continue;
+                    }
+
+                    touching_trapdoors.push(wall_index);
+
+                    let normal = (ball.get_position() - intersection_point).normalize_or_zero();
+
+                    if ball.get_velocity().dot(normal) < -30.0 {
+                        new_wall.add_contact();
+                        maybe_ball = Some(index);
+                    }
+                }
+            }
+
+            if !wall.is_open() && new_wall.is_open() {
+                new_events.push(RaceEvent::TrapdoorOpened {
+                    maybe_ball,
+                    time: Duration::from_secs_f64(self.time),
+                });
+            }
+        }
 
         let mut new_balls = resolved_collisions_scene // Overlapping new positions, new velocities, powered up
             .get_balls()
             .iter()
-            .map(|ball| {
+            .enumerate()
+            .map(|(index, ball)| {
+                if ball.is_eliminated() {
+                    return ball.clone();
+                }
+
                 let mut new_ball = ball.update(dt);
 
                 for powerup in resolved_collisions_scene.get_powerups() {
@@ -156,51 +1191,69 @@ impl Scene {
                         if powerup.is_colliding_with(ball) {
                             powerup.apply(&mut new_ball);
 
+                            // Teleports only land somewhere clear, and fizzle if there's nowhere
+                            if let Some(change_position) =
+                                (powerup.as_ref() as &dyn Any).downcast_ref::<ChangePosition>()
+                            {
+                                new_ball.set_position(
+                                    resolved_collisions_scene
+                                        .find_clear_position(
+                                            index,
+                                            change_position.get_x_range(),
+                                            change_position.get_y_range(),
+                                        )
+                                        .unwrap_or(ball.get_position()),
+                                );
+                            }
+
                             if (powerup.as_ref() as &dyn Any).is::<Special>() {
                                 if new_ball.get_id() == "Black Hole" {
                                     new_ball.set_density(1000.0);
                                     new_ball.set_name(format!("Supermassive {}", ball.get_name()));
                                 } else if new_ball.get_id() == "Green Machine" {
                                     let new_physics_ball = new_ball.get_physics_ball_mut();
-                                    let new_physics_ball_time = new_physics_ball.get_time();
-                                    new_physics_ball
-                                        .get_velocity_coefficient_mut()
-                                        .add_modifier(
-                                            new_physics_ball_time..=(new_physics_ball_time + 10.0),
-                                            2.0,
-                                        );
-                                    new_physics_ball.get_gravity_coefficient_mut().add_modifier(
-                                        new_physics_ball_time..=(new_physics_ball_time + 10.0),
+                                    new_physics_ball.add_status_effect(StatusEffect::new(
+                                        "Fast forward",
+                                        Stat::Speed,
                                         2.0,
-                                    );
+                                        10.0,
+                                    ));
+                                    new_physics_ball.add_status_effect(StatusEffect::new(
+                                        "Fast forward",
+                                        Stat::Gravity,
+                                        2.0,
+                                        10.0,
+                                    ));
                                 } else if new_ball.get_id() == "IKEA" {
                                     new_ball.set_radius(ball.get_radius() * 0.5);
                                     new_ball.set_name(format!("{} Junior", ball.get_name()));
                                 } else if new_ball.get_id() == "Hollow Knight" {
                                     new_ball.set_radius(ball.get_radius() * 0.5);
                                 } else if new_ball.get_id() == "Jokester" {
+                                    // Bounces right up to an elasticity of 0.99
+                                    let elasticity = new_ball.get_elasticity();
                                     let new_physics_ball = new_ball.get_physics_ball_mut();
-                                    let new_physics_ball_time = new_physics_ball.get_time();
-                                    new_physics_ball
-                                        .get_velocity_coefficient_mut()
-                                        .add_modifier(
-                                            new_physics_ball_time..=(new_physics_ball_time + 8.0),
-                                            8.0,
-                                        );
-                                    new_physics_ball.get_gravity_coefficient_mut().add_modifier(
-                                        new_physics_ball_time..=(new_physics_ball_time + 8.0),
+                                    new_physics_ball.add_status_effect(StatusEffect::new(
+                                        "Why so serious",
+                                        Stat::Speed,
                                         8.0,
-                                    );
-                                    new_physics_ball.get_elasticity_mut().add_modifier(
-                                        new_physics_ball_time..=(new_physics_ball_time + 8.0),
-                                        0.99,
-                                    );
+                                        8.0,
+                                    ));
+                                    new_physics_ball.add_status_effect(StatusEffect::new(
+                                        "Why so serious",
+                                        Stat::Gravity,
+                                        8.0,
+                                        8.0,
+                                    ));
+                                    new_physics_ball.add_status_effect(StatusEffect::new(
+                                        "Why so serious",
+                                        Stat::Elasticity,
+                                        0.99 / elasticity,
+                                        8.0,
+                                    ));
                                 } else if new_ball.get_id() == "Psycho" {
-                                    let new_physics_ball = new_ball.get_physics_ball_mut();
-                                    let new_physics_ball_time = new_physics_ball.get_time();
-                                    new_physics_ball.get_bloodbath_mut().add_modifier(
-                                        new_physics_ball_time..=(new_physics_ball_time + 10.0),
-                                        true,
+                                    new_ball.get_physics_ball_mut().add_status_effect(
+                                        StatusEffect::new("Bloodbath", Stat::Bloodbath, 1.0, 10.0),
                                     );
                                 } else if new_ball.get_id() == "Instabwillity" {
                                     should_shuffle = true;
@@ -210,11 +1263,38 @@ impl Scene {
                             }
                         }
 
-                        for other_ball in resolved_collisions_scene
-                            .get_balls()
-                            .iter()
-                            .filter(|other_ball| ball.get_position() != other_ball.get_position())
+                        for other_ball in
+                            resolved_collisions_scene
+                                .get_balls()
+                                .iter()
+                                .filter(|other_ball| {
+                                    ball.get_position() != other_ball.get_position()
+                                        && !other_ball.is_eliminated()
+                                })
                         {
+                            if let Some(freeze_others) =
+                                (powerup.as_ref() as &dyn Any).downcast_ref::<FreezeOthers>()
+                                && freeze_others.is_colliding_with(other_ball)
+                            {
+                                new_ball.set_velocity(DVec2::ZERO);
+
+                                let new_physics_ball = new_ball.get_physics_ball_mut();
+                                new_physics_ball.add_status_effect(effect_from(
+                                    other_ball,
+                                    "Frozen",
+                                    Stat::Speed,
+                                    0.0,
+                                    freeze_others.get_seconds(),
+                                ));
+                                new_physics_ball.add_status_effect(effect_from(
+                                    other_ball,
+                                    "Frozen",
+                                    Stat::Gravity,
+                                    0.0,
+                                    freeze_others.get_seconds(),
+                                ));
+                            }
+
                             if (powerup.as_ref() as &dyn Any).is::<Special>()
                                 && powerup.is_colliding_with(other_ball)
                             {
@@ -231,25 +1311,38 @@ impl Scene {
                                 } else if other_ball.get_id() == "White Light" {
                                     new_ball.set_velocity(DVec2::ZERO);
 
-                                    let new_physics_ball = new_ball.get_physics_ball_mut();
-                                    let new_physics_ball_time = new_physics_ball.get_time();
-                                    new_physics_ball.get_gravity_coefficient_mut().add_modifier(
-                                        new_physics_ball_time..=(new_physics_ball_time + 10.0),
-                                        0.0,
-                                    );
+                                    new_ball
+                                        .get_physics_ball_mut()
+                                        .add_status_effect(effect_from(
+                                            other_ball,
+                                            "Freezeframe",
+                                            Stat::Gravity,
+                                            0.0,
+                                            10.0,
+                                        ));
                                 } else if other_ball.get_id() == "Deep Blue" {
+                                    // Getting caught again sinks the ball deeper
                                     let new_physics_ball = new_ball.get_physics_ball_mut();
-                                    let new_physics_ball_time = new_physics_ball.get_time();
-                                    new_physics_ball
-                                        .get_velocity_coefficient_mut()
-                                        .add_modifier(
-                                            new_physics_ball_time..=(new_physics_ball_time + 10.0),
+                                    new_physics_ball.add_status_effect(effect_with_stacking(
+                                        effect_from(
+                                            other_ball,
+                                            "Underwater",
+                                            Stat::Speed,
                                             0.5,
-                                        );
-                                    new_physics_ball.get_gravity_coefficient_mut().add_modifier(
-                                        new_physics_ball_time..=(new_physics_ball_time + 10.0),
-                                        0.05,
-                                    );
+                                            10.0,
+                                        ),
+                                        Stacking::Multiply,
+                                    ));
+                                    new_physics_ball.add_status_effect(effect_with_stacking(
+                                        effect_from(
+                                            other_ball,
+                                            "Underwater",
+                                            Stat::Gravity,
+                                            0.05,
+                                            10.0,
+                                        ),
+                                        Stacking::Multiply,
+                                    ));
                                 } else if other_ball.get_id() == "Timmy J"
                                     && let Some(current_winner) = self.get_current_winner()
                                 {
@@ -272,7 +1365,11 @@ impl Scene {
                             }
                         }
 
-                        for any_ball in resolved_collisions_scene.get_balls().iter() {
+                        for any_ball in resolved_collisions_scene
+                            .get_balls()
+                            .iter()
+                            .filter(|any_ball| !any_ball.is_eliminated())
+                        {
                             if (powerup.as_ref() as &dyn Any).is::<Special>()
                                 && powerup.is_colliding_with(any_ball)
                                 && any_ball.get_id() == "Timmy J"
@@ -311,10 +1408,18 @@ impl Scene {
             .map(|powerup| {
                 let mut new_powerup = powerup.update(dt);
 
-                for ball in resolved_collisions_scene.get_balls() {
-                    if powerup.is_active() && powerup.is_colliding_with(ball) {
+                for (index, ball) in resolved_collisions_scene.get_balls().iter().enumerate() {
+                    if powerup.is_active()
+                        && !ball.is_eliminated()
+                        && powerup.is_colliding_with(ball)
+                    {
                         new_powerup.consume();
 
+                        if let Some(split) = (powerup.as_ref() as &dyn Any).downcast_ref::<Split>()
+                        {
+                            splits.push((index, split.get_copies()));
+                        }
+
                         if let Some(special) =
                             (new_powerup.as_mut() as &mut dyn Any).downcast_mut::<Special>()
                         {
@@ -347,6 +1452,11 @@ impl Scene {
                             } else {
                                 special.set_text("nothing");
                             }
+
+                            new_events.push(RaceEvent::Special {
+                                ball: index,
+                                text: special.get_text().to_string(),
+                            });
                         }
                     }
                 }
@@ -354,48 +1464,167 @@ impl Scene {
                 new_powerup
             })
             .chain(new_balls.iter().filter_map(|new_ball| {
-                (should_bring_gifts && new_ball.get_id() != "Giftbringer")
-                    .then_some(
-                        Box::new(Special::new(new_ball.get_position(), 1.0)) as Box<dyn Powerup>
-                    )
+                (should_bring_gifts
+                    && new_ball.get_id() != "Giftbringer"
+                    && !new_ball.is_eliminated())
+                .then_some(Box::new(Special::new(new_ball.get_position(), 1.0)) as Box<dyn Powerup>)
             }))
             .collect();
 
         if should_shuffle {
-            let mut new_new_balls = new_balls.clone();
-            new_new_balls.shuffle(&mut rng());
-
-            for (new_ball, new_new_ball) in new_balls.iter_mut().zip(new_new_balls.iter()) {
-                new_ball.set_position(new_new_ball.get_position());
+            let mut positions = new_balls
+                .iter()
+                .filter(|new_ball| !new_ball.is_eliminated())
+                .map(|new_ball| new_ball.get_position())
+                .collect::<Vec<DVec2>>();
+            positions.shuffle(&mut rng());
+
+            for (new_ball, position) in new_balls
+                .iter_mut()
+                .filter(|new_ball| !new_ball.is_eliminated())
+                .zip(positions)
+            {
+                new_ball.set_position(position);
             }
         }
 
         let mut new_winners = resolved_collisions_scene.get_winners().clone();
         let mut new_win_times = resolved_collisions_scene.get_win_times().clone();
         let mut new_particles = resolved_collisions_scene.particles.clone();
+        let mut new_scores = resolved_collisions_scene.get_scores().clone();
 
-        for (index, ball) in new_balls.iter().enumerate() {
+        for (index, ball) in new_balls.iter_mut().enumerate() {
             for wall in new_walls.iter() {
                 let maybe_intersection_point = ball.get_intersection_point(wall.as_ref());
 
+                // With an elimination rule only the last ball standing wins, so goals don't count
                 if maybe_intersection_point.is_some()
                     && wall.is_goal()
-                    && !self.winners.contains(&index)
+                    && self.get_elimination().is_none()
+                    && !ball.is_eliminated()
+                    && !new_winners.contains(&index)
                 {
+                    if let Some(drops) = self.get_drops() {
+                        let score = wall.get_score().unwrap_or_default();
+
+                        new_scores[index].push(score);
+                        new_events.push(RaceEvent::Scored {
+                            ball: index,
+                            score,
+                            time: Duration::from_secs_f64(self.time),
+                        });
+
+                        if new_scores[index].len() < drops {
+                            add_confetti(&mut new_particles, ball);
+                            ball.set_position(self.spawn_positions[index]);
+                            ball.set_velocity(DVec2::ZERO);
+                            break;
+                        }
+                    }
+
                     new_winners.push(index);
                     new_win_times.push(Duration::from_secs_f64(self.time));
+                    new_events.push(RaceEvent::Finished {
+                        ball: index,
+                        place: new_winners.len() - 1,
+                        time: Duration::from_secs_f64(self.time),
+                    });
+
+                    add_confetti(&mut new_particles, ball);
+                }
+            }
+        }
+
+        let checkpoint_count = self.get_checkpoint_count();
+        let mut new_lap_trackers = resolved_collisions_scene.get_lap_trackers().clone();
+        let mut new_checkpoint_positions = self.checkpoint_positions.clone();
+        let mut new_portals = self
+            .get_portals()
+            .iter()
+            .map(|portal| portal.update(dt))
+            .collect::<Vec<Portal>>();
+
+        for (index, ball) in new_balls.iter_mut().enumerate() {
+            if ball.is_eliminated() || new_winners.contains(&index) {
+                continue;
+            }
+
+            let touching = get_touching_checkpoints(&new_walls, ball);
+
+            let lap_tracker = &mut new_lap_trackers[index];
+            let split_count = lap_tracker.get_splits().len();
+            let was_touching = lap_tracker.get_touching().clone();
+
+            let completed_lap = lap_tracker.observe(
+                touching.clone(),
+                checkpoint_count,
+                Duration::from_secs_f64(self.time),
+            );
+
+            if lap_tracker.get_splits().len() > split_count {
+                new_checkpoint_positions[index] = ball.get_position();
+            } else if let Some(split) = lap_tracker.get_splits().last()
+                && was_touching.contains(&split.checkpoint)
+                && !touching.contains(&split.checkpoint)
+                && (self.progress_function)(ball.get_position())
+                    > (self.progress_function)(new_checkpoint_positions[index])
+            {
+                // Once the ball is through the checkpoint, it respawns just past it instead of on
+                // it, so that it doesn't cross it again
+                new_checkpoint_positions[index] = ball.get_position();
+            }
+
+            if completed_lap {
+                new_events.push(RaceEvent::LapCompleted {
+                    ball: index,
+                    lap: lap_tracker.get_laps(),
+                    time: Duration::from_secs_f64(self.time),
+                });
+
+                if self
+                    .get_laps()
+                    .is_some_and(|laps| lap_tracker.get_laps() >= laps)
+                {
+                    new_winners.push(index);
+                    new_win_times.push(Duration::from_secs_f64(self.time));
+                    new_events.push(RaceEvent::Finished {
+                        ball: index,
+                        place: new_winners.len() - 1,
+                        time: Duration::from_secs_f64(self.time),
+                    });
+
+                    add_confetti(&mut new_particles, ball);
+                }
+            }
+
+            for wall in new_walls.iter() {
+                if let Some(Trigger::Teleport(offset)) = wall.get_trigger()
+                    && ball.get_intersection_point(wall.as_ref()).is_some()
+                {
+                    ball.set_position(ball.get_position() + offset);
+                }
+            }
 
-                    for _ in 0..100 {
-                        new_particles.add_particle(Box::new(ConfettiParticle::new(
-                            ball.get_position()
-                                + ball.get_radius()
-                                    * DVec2::from_angle(random_range(0.0..(2.0 * PI))),
-                            DVec2::from_angle(random_range((1.25 * PI)..(1.75 * PI)))
-                                * random_range(100.0..=1000.0),
-                            random_range(4.0..=8.0),
-                            2.0,
-                            ParticleLayer::random(),
-                        )));
+            for portal in new_portals.iter_mut() {
+                if let Some(entrance) = portal.get_entrance(ball) {
+                    let exit = portal.get_exit(entrance, ball);
+                    let end = portal.get_ends()[1 - entrance];
+                    let reach = portal.get_radius() + 2.0 * ball.get_radius();
+
+                    // Portals only let balls out somewhere clear too, and hold on to them until
+                    // there's room
+                    let maybe_exit = if resolved_collisions_scene.is_clear_position(index, exit) {
+                        Some(exit)
+                    } else {
+                        resolved_collisions_scene.find_clear_position(
+                            index,
+                            (end.x - reach)..=(end.x + reach),
+                            (end.y - reach)..=(end.y + reach),
+                        )
+                    };
+
+                    if let Some(exit) = maybe_exit {
+                        portal.send(entrance, ball, exit);
                     }
                 }
             }
@@ -409,10 +1638,43 @@ impl Scene {
             walls: new_walls,
             winners: new_winners,
             win_times: new_win_times,
+            eliminated: self.get_eliminated().clone(),
+            elimination_times: self.get_elimination_times().clone(),
+            maybe_elimination: self.get_elimination().cloned(),
+            maybe_laps: self.get_laps(),
+            lap_trackers: new_lap_trackers,
+            maybe_drops: self.get_drops(),
+            scores: new_scores,
+            spawn_positions: self.spawn_positions.clone(),
+            checkpoint_positions: new_checkpoint_positions,
+            touching_hazards: self.touching_hazards.clone(),
+            touching_trapdoors: new_touching_trapdoors,
+            maybe_teams: self.get_teams().cloned(),
+            ball_teams: self.ball_teams.clone(),
+            families: self.families.clone(),
+            maybe_start: self.get_start(),
+            timeline: self.get_timeline().clone(),
+            portals: new_portals,
+            maybe_powerup_spawner: self.get_powerup_spawner().cloned(),
+            solver: self.get_solver(),
+            rng: Arc::clone(&self.rng),
+            events: new_events,
             particles: new_particles,
             finished_condition: self.get_finished_condition(),
+            progress_function: self.get_progress_function(),
         };
 
+        for (index, copies) in splits {
+            updated_scene.split_ball(index, copies);
+        }
+
+        updated_scene.restock_powerups();
+        let mut collisions = collisions;
+        collisions.append(&mut updated_scene.apply_hazards());
+        updated_scene.apply_elimination();
+        updated_scene.apply_timeline();
+        updated_scene.prune_copies();
+
         for collision in &collisions {
             updated_scene
                 .particles
@@ -431,6 +1693,65 @@ impl Scene {
         (updated_scene, collisions)
     }
 
+    /// Works out the balls' new velocities and pushes them apart, with the scene's solver. The
+    /// first scene returned has the new velocities at the old positions, and the second has the
+    /// balls pushed apart too.
+    fn solve(&self, dt: f64) -> (Self, Self, Vec<Collision>) {
+        match self.solver {
+            Solver::Reflection => {
+                let stepped_velocities_scene = self.step_velocities(dt); // Overlapping old positions, new velocities
+                let (resolved_collisions_scene, collisions) =
+                    stepped_velocities_scene.resolve_collisions(); // NOT overlapping old positions, new velocities
+
+                (
+                    stepped_velocities_scene,
+                    resolved_collisions_scene,
+                    collisions,
+                )
+            }
+            Solver::Impulse => {
+                let (solved_impulses_scene, contacts) = self.solve_impulses(dt); // Overlapping old positions, new velocities
+                let (corrected_positions_scene, collisions) =
+                    solved_impulses_scene.correct_positions(&contacts); // Less overlapping old positions, new velocities
+
+                (solved_impulses_scene, corrected_positions_scene, collisions)
+            }
+        }
+    }
+
+    /// The change in a ball's velocity over a step from gravity, mud and magnets.
+    fn get_force_offset(&self, ball: &Ball, dt: f64) -> DVec2 {
+        let mut velocity_offsets = Vec::new();
+
+        // Gravity
+        velocity_offsets
+            .push(dvec2(0.0, 500.0) * dt * ball.get_physics_ball().get_gravity_coefficient());
+
+        // Mud
+        for wall in self.get_walls().iter() {
+            if let Some(Hazard::Mud { drag }) = wall.get_hazard()
+                && ball.get_intersection_point(wall.as_ref()).is_some()
+            {
+                velocity_offsets.push(-ball.get_velocity() * (drag * dt).min(1.0));
+            }
+        }
+
+        // Magnets
+        for other_ball in self.get_balls().iter().filter(|other_ball| {
+            ball.get_position() != other_ball.get_position() && !other_ball.is_eliminated()
+        }) {
+            let intersection_vector = ball.get_position() - other_ball.get_position();
+
+            let magnet = other_ball.get_physics_ball().get_magnet();
+
+            if magnet > 0.0 && intersection_vector.length() < MAGNET_RANGE {
+                velocity_offsets.push(-intersection_vector.normalize() * magnet * dt);
+            }
+        }
+
+        velocity_offsets.iter().sum()
+    }
+
     pub fn step_velocities(&self, dt: f64) -> Self {
         let new_balls = self
             .balls
@@ -438,57 +1759,79 @@ impl Scene {
             .map(|ball| {
                 let mut new_ball = ball.clone();
 
+                if ball.is_eliminated() {
+                    return new_ball;
+                }
+
                 let mut velocity_offsets = Vec::new();
+                let mut angular_velocity_offset = 0.0;
 
-                // Gravity
-                velocity_offsets.push(
-                    dvec2(0.0, 500.0)
-                        * dt
-                        * new_ball
-                            .get_physics_ball()
-                            .get_gravity_coefficient()
-                            .get_value(new_ball.get_time()),
-                );
+                let is_shielded = ball.get_physics_ball().is_shielded();
+
+                velocity_offsets.push(self.get_force_offset(ball, dt));
 
                 // Walls
                 let wall_intersection_points = self
                     .get_walls()
                     .iter()
+                    .filter(|wall| wall.is_solid())
                     .filter_map(|wall| ball.get_intersection_point(wall.as_ref()))
                     .collect::<Vec<DVec2>>();
                 let number_of_wall_intersection_points = wall_intersection_points.len();
 
                 for intersection_point in wall_intersection_points {
                     let intersection_vector = ball.get_position() - intersection_point;
-                    velocity_offsets.push(
-                        -((2.0 * ball.get_velocity()).dot(intersection_vector)
-                            / (intersection_vector.length() * intersection_vector.length()))
-                            * (intersection_vector)
-                            * ball.get_elasticity()
-                            / number_of_wall_intersection_points as f64,
+                    let normal_offset = -((2.0 * ball.get_velocity()).dot(intersection_vector)
+                        / (intersection_vector.length() * intersection_vector.length()))
+                        * (intersection_vector)
+                        * ball.get_elasticity()
+                        / number_of_wall_intersection_points as f64;
+                    velocity_offsets.push(normal_offset);
+
+                    let normal = intersection_vector.normalize();
+                    let (friction_offset, spin_offset) = get_friction(
+                        ball.get_physics_ball(),
+                        normal,
+                        ball.get_physics_ball().get_surface_velocity(-normal),
+                        normal_offset.length(),
+                        1.0,
                     );
+                    velocity_offsets.push(friction_offset);
+                    angular_velocity_offset += spin_offset;
                 }
 
                 // Other balls {
-                for other_ball in self
-                    .get_balls()
-                    .iter()
-                    .filter(|other_ball| ball.get_position() != other_ball.get_position())
-                {
+                for other_ball in self.get_balls().iter().filter(|other_ball| {
+                    ball.get_position() != other_ball.get_position() && !other_ball.is_eliminated()
+                }) {
                     let intersection_vector = ball.get_position() - other_ball.get_position();
 
-                    if intersection_vector.length() < ball.get_radius() + other_ball.get_radius() {
-                        velocity_offsets.push(
-                            -(2.0 * other_ball.get_physics_ball().get_mass()
-                                / (ball.get_physics_ball().get_mass()
-                                    + other_ball.get_physics_ball().get_mass()))
-                                * ((ball.get_velocity() - other_ball.get_velocity())
-                                    .dot(intersection_vector)
-                                    / (intersection_vector.length()
-                                        * intersection_vector.length()))
-                                * (intersection_vector)
-                                * ball.get_elasticity(),
+                    if !is_shielded
+                        && intersection_vector.length()
+                            < ball.get_radius() + other_ball.get_radius()
+                    {
+                        let share = other_ball.get_physics_ball().get_mass()
+                            / (ball.get_physics_ball().get_mass()
+                                + other_ball.get_physics_ball().get_mass());
+                        let normal_offset = -(2.0 * share)
+                            * ((ball.get_velocity() - other_ball.get_velocity())
+                                .dot(intersection_vector)
+                                / (intersection_vector.length() * intersection_vector.length()))
+                            * (intersection_vector)
+                            * ball.get_elasticity();
+                        velocity_offsets.push(normal_offset);
+
+                        let normal = intersection_vector.normalize();
+                        let (friction_offset, spin_offset) = get_friction(
+                            ball.get_physics_ball(),
+                            normal,
+                            ball.get_physics_ball().get_surface_velocity(-normal)
+                                - other_ball.get_physics_ball().get_surface_velocity(normal),
+                            normal_offset.length(),
+                            share,
                         );
+                        velocity_offsets.push(friction_offset);
+                        angular_velocity_offset += spin_offset;
                     }
                 }
 
@@ -503,6 +1846,8 @@ impl Scene {
                 }
 
                 new_ball.set_velocity(new_velocity);
+                new_ball
+                    .set_angular_velocity(ball.get_angular_velocity() + angular_velocity_offset);
 
                 new_ball
             })
@@ -514,6 +1859,116 @@ impl Scene {
         }
     }
 
+    /// Works out the balls' new velocities with sequential impulses. Returns the contacts too, for
+    /// pushing the balls apart afterwards.
+    pub fn solve_impulses(&self, dt: f64) -> (Self, Vec<Contact>) {
+        let mut pushed_balls = self.balls.clone();
+
+        for (ball, pushed_ball) in self.balls.iter().zip(pushed_balls.iter_mut()) {
+            if !ball.is_eliminated() {
+                pushed_ball.set_velocity(ball.get_velocity() + self.get_force_offset(ball, dt));
+            }
+        }
+
+        let mut contacts = solver::find_contacts(&pushed_balls, &self.walls);
+        solver::solve_velocities(&mut pushed_balls, &mut contacts);
+
+        let new_balls = self
+            .balls
+            .iter()
+            .zip(pushed_balls.iter())
+            .map(|(ball, pushed_ball)| {
+                let mut new_ball = ball.clone();
+
+                if ball.is_eliminated() {
+                    return new_ball;
+                }
+
+                let new_velocity = pushed_ball.get_velocity();
+
+                if new_velocity.distance(ball.get_velocity()) >= 100.0 {
+                    new_ball.handle_collision(new_velocity);
+                }
+
+                new_ball.set_velocity(new_velocity);
+                new_ball.set_angular_velocity(pushed_ball.get_angular_velocity());
+
+                new_ball
+            })
+            .collect();
+
+        (
+            Self {
+                balls: new_balls,
+                ..self.clone()
+            },
+            contacts,
+        )
+    }
+
+    /// Takes out most of the overlap at these contacts, and plays their sounds.
+    pub fn correct_positions(&self, contacts: &[Contact]) -> (Self, Vec<Collision>) {
+        let mut collisions = Vec::new();
+        let mut new_balls = self.balls.clone();
+
+        for contact in contacts {
+            let mut sides = vec![(
+                contact.get_ball(),
+                contact.get_normal(),
+                contact.get_other_ball(),
+            )];
+
+            if let Some(other_ball) = contact.get_other_ball() {
+                sides.push((other_ball, -contact.get_normal(), Some(contact.get_ball())));
+            }
+
+            for (index, normal, maybe_other_ball) in sides {
+                let ball = &self.balls[index];
+                let v_proj = ball.get_velocity().project_onto(normal);
+
+                if v_proj.length() > 30.0 {
+                    collisions.push(Collision::new(
+                        ball.get_sound_path().to_path_buf(),
+                        ((v_proj.length() as f32 - 30.0) * 0.005).min(1.0),
+                        contact.get_point(),
+                    ));
+                }
+
+                if let Some(other_ball) = maybe_other_ball.map(|other_ball| &self.balls[other_ball])
+                    && other_ball.get_physics_ball().is_bloodbath()
+                    && !ball.get_physics_ball().is_shielded()
+                {
+                    // Bleeding comes from every step of contact, so it only lasts longer rather than
+                    // getting worse
+                    let new_physics_ball = new_balls[index].get_physics_ball_mut();
+                    new_physics_ball.add_status_effect(effect_with_stacking(
+                        effect_from(other_ball, "Bled", Stat::Density, 0.5, 10.0),
+                        Stacking::Max,
+                    ));
+                    new_physics_ball.add_status_effect(effect_with_stacking(
+                        effect_from(other_ball, "Bled", Stat::Gravity, 0.01, 10.0),
+                        Stacking::Max,
+                    ));
+                }
+            }
+        }
+
+        for (new_ball, position_offset) in new_balls
+            .iter_mut()
+            .zip(solver::get_position_offsets(&self.balls, contacts))
+        {
+            new_ball.set_position(new_ball.get_position() + position_offset);
+        }
+
+        (
+            Self {
+                balls: new_balls,
+                ..self.clone()
+            },
+            collisions,
+        )
+    }
+
     pub fn resolve_collisions(&self) -> (Self, Vec<Collision>) {
         let mut collisions = Vec::new();
 
@@ -523,12 +1978,17 @@ impl Scene {
             .map(|ball| {
                 let mut new_ball = ball.clone();
 
+                if ball.is_eliminated() {
+                    return new_ball;
+                }
+
                 let mut position_offsets = Vec::new();
 
                 // Walls
                 let wall_intersection_points = self
                     .get_walls()
                     .iter()
+                    .filter(|wall| wall.is_solid())
                     .filter_map(|wall| ball.get_intersection_point(wall.as_ref()))
                     .collect::<Vec<DVec2>>();
 
@@ -549,11 +2009,9 @@ impl Scene {
                 }
 
                 // Other balls {
-                for other_ball in self
-                    .get_balls()
-                    .iter()
-                    .filter(|other_ball| ball.get_position() != other_ball.get_position())
-                {
+                for other_ball in self.get_balls().iter().filter(|other_ball| {
+                    ball.get_position() != other_ball.get_position() && !other_ball.is_eliminated()
+                }) {
                     let intersection_point =
                         ball.get_position().midpoint(other_ball.get_position());
                     let intersection_vector = ball.get_position() - other_ball.get_position();
@@ -575,27 +2033,18 @@ impl Scene {
                             ));
                         }
 
-                        if *other_ball
-                            .get_physics_ball()
-                            .get_bloodbath()
-                            .get_value(other_ball.get_time())
+                        if other_ball.get_physics_ball().is_bloodbath()
+                            && !ball.get_physics_ball().is_shielded()
                         {
-                            let new_ball_density =
-                                *new_ball.get_physics_ball().get_density().get_value(0.0);
-                            let new_ball_gravity_coefficient = *new_ball
-                                .get_physics_ball()
-                                .get_gravity_coefficient()
-                                .get_value(0.0);
                             let new_physics_ball = new_ball.get_physics_ball_mut();
-                            let new_physics_ball_time = new_physics_ball.get_time();
-                            new_physics_ball.get_density_mut().add_modifier(
-                                new_physics_ball_time..=(new_physics_ball_time + 10.0),
-                                new_ball_density / 2.0,
-                            );
-                            new_physics_ball.get_gravity_coefficient_mut().add_modifier(
-                                new_physics_ball_time..=(new_physics_ball_time + 10.0),
-                                new_ball_gravity_coefficient / 100.0,
-                            );
+                            new_physics_ball.add_status_effect(effect_with_stacking(
+                                effect_from(other_ball, "Bled", Stat::Density, 0.5, 10.0),
+                                Stacking::Max,
+                            ));
+                            new_physics_ball.add_status_effect(effect_with_stacking(
+                                effect_from(other_ball, "Bled", Stat::Gravity, 0.01, 10.0),
+                                Stacking::Max,
+                            ));
                         }
                     }
                 }
@@ -618,12 +2067,77 @@ impl Scene {
     }
 }
 
+/// The change in velocity and spin that friction gives a ball at a contact. `normal` points from
+/// the contact to the ball's centre, `slip` is how fast the ball's surface slides past whatever it's
+/// touching there, `normal_speed` is how hard the contact pushed the ball away, and `share` is how
+/// much of the sliding the ball takes up, 1 against walls. A ball with topspin kicks forward off
+/// what it hits, and one with backspin checks up.
+fn get_friction(
+    ball: &PhysicsBall,
+    normal: DVec2,
+    slip: DVec2,
+    normal_speed: f64,
+    share: f64,
+) -> (DVec2, f64) {
+    // A solid disc takes a third of a sliding impulse as velocity and the rest as spin
+    let velocity_offset = (-slip.reject_from_normalized(normal) * share / 3.0)
+        .clamp_length_max(FRICTION * normal_speed);
+    let angular_velocity_offset = 2.0 * (-normal).perp_dot(velocity_offset) / ball.get_radius();
+
+    (velocity_offset, angular_velocity_offset)
+}
+
+/// The checkpoints a ball is touching.
+fn get_touching_checkpoints(walls: &[Box<dyn Wall>], ball: &Ball) -> Vec<usize> {
+    walls
+        .iter()
+        .filter_map(|wall| match wall.get_trigger() {
+            Some(Trigger::Checkpoint(checkpoint))
+                if ball.get_intersection_point(wall.as_ref()).is_some() =>
+            {
+                Some(checkpoint)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// A status effect that came from another ball.
+fn effect_from(ball: &Ball, name: &str, stat: Stat, value: f64, seconds: f64) -> StatusEffect {
+    let mut effect = StatusEffect::new(name, stat, value, seconds);
+    effect.set_source(Some(ball.get_id().to_string()));
+    effect
+}
+
+fn effect_with_stacking(mut effect: StatusEffect, stacking: Stacking) -> StatusEffect {
+    effect.set_stacking(stacking);
+    effect
+}
+
+fn add_confetti(particles: &mut SceneParticleSystem, ball: &Ball) {
+    for _ in 0..100 {
+        particles.add_particle(Box::new(ConfettiParticle::new(
+            ball.get_position()
+                + ball.get_radius() * DVec2::from_angle(random_range(0.0..(2.0 * PI))),
+            DVec2::from_angle(random_range((1.25 * PI)..(1.75 * PI)))
+                * random_range(100.0..=1000.0),
+            random_range(4.0..=8.0),
+            2.0,
+            ParticleLayer::random(),
+        )));
+    }
+}
+
 impl Render for Scene {
     fn render(&self, renderer: &mut dyn Renderer) {
         for wall in self.get_walls().iter() {
             wall.render(renderer);
         }
 
+        for portal in self.get_portals().iter() {
+            portal.render(renderer);
+        }
+
         for ball in self.get_balls().iter() {
             ball.render(renderer);
         }
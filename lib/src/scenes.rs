@@ -3,9 +3,15 @@ use std::{path::PathBuf, sync::Arc};
 use crate::{
     ball::{Ball, PhysicsBall},
     drawer::base_style::BaseStyle,
-    levels::{level_1, level_2, level_3, level_4, level_5, level_6, level_8, level_9, level_10},
-    powerup::{change_position::ChangePosition, random_powerup},
+    elimination::Elimination,
+    levels::{
+        level_1, level_2, level_3, level_4, level_5, level_6, level_8, level_9, level_10, level_11,
+        level_12, level_13, level_14, level_15, level_16,
+    },
+    powerup::pool::{PowerupKind, PowerupPool, PowerupPoolEntry},
+    progress::vertical_progress,
     scene::Scene,
+    team::Teams,
     util::{all_won_condition, any_won_condition},
     wall::{Wall, straight_wall::StraightWall},
 };
@@ -17,20 +23,15 @@ use rand::Rng;
 pub fn scene_1(
     rng: &mut impl Rng,
     marbles: &[Marble],
+    maybe_teams: Option<&Teams>,
     scene_width: f64,
     scene_height: f64,
 ) -> Scene {
     level_1(marbles.len(), scene_width, scene_height).build_scene(
         rng,
         marbles,
-        |powerup_space| {
-            random_powerup(
-                &mut rand::rng(),
-                powerup_space.get_position(),
-                scene_width,
-                scene_height,
-            )
-        },
+        maybe_teams,
+        (scene_width, scene_height),
         all_won_condition(5.0),
     )
 }
@@ -38,20 +39,15 @@ pub fn scene_1(
 pub fn scene_2(
     rng: &mut impl Rng,
     marbles: &[Marble],
+    maybe_teams: Option<&Teams>,
     scene_width: f64,
     scene_height: f64,
 ) -> Scene {
     level_2(marbles.len(), scene_width, scene_height).build_scene(
         rng,
         marbles,
-        |powerup_space| {
-            random_powerup(
-                &mut rand::rng(),
-                powerup_space.get_position(),
-                scene_width,
-                scene_height,
-            )
-        },
+        maybe_teams,
+        (scene_width, scene_height),
         all_won_condition(5.0),
     )
 }
@@ -59,20 +55,15 @@ pub fn scene_2(
 pub fn scene_3(
     rng: &mut impl Rng,
     marbles: &[Marble],
+    maybe_teams: Option<&Teams>,
     scene_width: f64,
     scene_height: f64,
 ) -> Scene {
     level_3(marbles.len(), scene_width, scene_height).build_scene(
         rng,
         marbles,
-        |powerup_space| {
-            random_powerup(
-                &mut rand::rng(),
-                powerup_space.get_position(),
-                scene_width,
-                scene_height,
-            )
-        },
+        maybe_teams,
+        (scene_width, scene_height),
         any_won_condition(15.0),
     )
 }
@@ -80,20 +71,15 @@ pub fn scene_3(
 pub fn scene_4(
     rng: &mut impl Rng,
     marbles: &[Marble],
+    maybe_teams: Option<&Teams>,
     scene_width: f64,
     scene_height: f64,
 ) -> Scene {
     level_4(marbles.len(), scene_width, scene_height).build_scene(
         rng,
         marbles,
-        |powerup_space| {
-            random_powerup(
-                &mut rand::rng(),
-                powerup_space.get_position(),
-                scene_width,
-                scene_height,
-            )
-        },
+        maybe_teams,
+        (scene_width, scene_height),
         all_won_condition(5.0),
     )
 }
@@ -101,20 +87,15 @@ pub fn scene_4(
 pub fn scene_5(
     rng: &mut impl Rng,
     marbles: &[Marble],
+    maybe_teams: Option<&Teams>,
     scene_width: f64,
     scene_height: f64,
 ) -> Scene {
     level_5(4, scene_width, scene_height).build_scene(
         rng,
         marbles,
-        |powerup_space| {
-            random_powerup(
-                &mut rand::rng(),
-                powerup_space.get_position(),
-                scene_width,
-                scene_height,
-            )
-        },
+        maybe_teams,
+        (scene_width, scene_height),
         all_won_condition(5.0),
     )
 }
@@ -122,20 +103,15 @@ pub fn scene_5(
 pub fn scene_6(
     rng: &mut impl Rng,
     marbles: &[Marble],
+    maybe_teams: Option<&Teams>,
     scene_width: f64,
     scene_height: f64,
 ) -> Scene {
     level_6(marbles.len(), scene_width, scene_height).build_scene(
         rng,
         marbles,
-        |powerup_space| {
-            random_powerup(
-                &mut rand::rng(),
-                powerup_space.get_position(),
-                scene_width,
-                scene_height,
-            )
-        },
+        maybe_teams,
+        (scene_width, scene_height),
         all_won_condition(5.0),
     )
 }
@@ -165,27 +141,35 @@ pub fn scene_7(scene_width: f64, scene_height: f64) -> Scene {
         .map(|straight_wall| Box::new(straight_wall) as Box<dyn Wall>)
         .collect();
 
-    Scene::new(0, balls, Vec::new(), walls, Arc::new(|_| false))
+    Scene::new(
+        0,
+        balls,
+        Vec::new(),
+        walls,
+        Arc::new(|_| false),
+        Arc::new(vertical_progress(scene_height)),
+    )
 }
 
 pub fn scene_8(
     rng: &mut impl Rng,
     marbles: &[Marble],
+    maybe_teams: Option<&Teams>,
     scene_width: f64,
     scene_height: f64,
 ) -> Scene {
-    level_8(marbles.len(), scene_width, scene_height).build_scene(
+    let mut level = level_8(marbles.len(), scene_width, scene_height);
+
+    level.set_powerup_pool(PowerupPool::new(vec![PowerupPoolEntry::new(
+        PowerupKind::ChangePosition,
+        1.0,
+    )]));
+
+    level.build_scene(
         rng,
         marbles,
-        |powerup_space| {
-            Box::new(ChangePosition::new(
-                powerup_space.get_position(),
-                "Teleport",
-                8.0,
-                16.0..=(scene_width - 16.0),
-                16.0..=(scene_height - 16.0),
-            ))
-        },
+        maybe_teams,
+        (scene_width, scene_height),
         all_won_condition(5.0),
     )
 }
@@ -193,20 +177,15 @@ pub fn scene_8(
 pub fn scene_9(
     rng: &mut impl Rng,
     marbles: &[Marble],
+    maybe_teams: Option<&Teams>,
     scene_width: f64,
     scene_height: f64,
 ) -> Scene {
     level_9(marbles.len(), scene_width, scene_height).build_scene(
         rng,
         marbles,
-        |powerup_space| {
-            random_powerup(
-                &mut rand::rng(),
-                powerup_space.get_position(),
-                scene_width,
-                scene_height,
-            )
-        },
+        maybe_teams,
+        (scene_width, scene_height),
         all_won_condition(5.0),
     )
 }
@@ -214,20 +193,15 @@ pub fn scene_9(
 pub fn scene_10(
     rng: &mut impl Rng,
     marbles: &[Marble],
+    maybe_teams: Option<&Teams>,
     scene_width: f64,
     scene_height: f64,
 ) -> Scene {
     level_10(4, scene_width, scene_height).build_scene(
         rng,
         marbles,
-        |powerup_space| {
-            random_powerup(
-                &mut rand::rng(),
-                powerup_space.get_position(),
-                scene_width,
-                scene_height,
-            )
-        },
+        maybe_teams,
+        (scene_width, scene_height),
         all_won_condition(5.0),
     )
 }
@@ -235,20 +209,129 @@ pub fn scene_10(
 pub fn scene_11(
     rng: &mut impl Rng,
     marbles: &[Marble],
+    maybe_teams: Option<&Teams>,
     scene_width: f64,
     scene_height: f64,
 ) -> Scene {
     level_3(4, scene_width, scene_height).build_scene(
         rng,
         marbles,
-        |powerup_space| {
-            random_powerup(
-                &mut rand::rng(),
-                powerup_space.get_position(),
-                scene_width,
-                scene_height,
-            )
-        },
+        maybe_teams,
+        (scene_width, scene_height),
+        all_won_condition(5.0),
+    )
+}
+
+/// Three laps of the circuit.
+pub fn scene_12(
+    rng: &mut impl Rng,
+    marbles: &[Marble],
+    maybe_teams: Option<&Teams>,
+    scene_width: f64,
+    scene_height: f64,
+) -> Scene {
+    let mut scene = level_11(marbles.len(), scene_width, scene_height).build_scene(
+        rng,
+        marbles,
+        maybe_teams,
+        (scene_width, scene_height),
+        all_won_condition(5.0),
+    );
+
+    scene.set_laps(Some(3));
+
+    scene
+}
+
+/// Three drops each on the plinko board, highest total score wins.
+pub fn scene_13(
+    rng: &mut impl Rng,
+    marbles: &[Marble],
+    maybe_teams: Option<&Teams>,
+    scene_width: f64,
+    scene_height: f64,
+) -> Scene {
+    let mut scene = level_12(marbles.len(), scene_width, scene_height).build_scene(
+        rng,
+        marbles,
+        maybe_teams,
+        (scene_width, scene_height),
+        all_won_condition(5.0),
+    );
+
+    scene.set_drops(Some(3));
+
+    scene
+}
+
+/// Sumo in a shrinking ring, last marble inside wins.
+pub fn scene_14(
+    rng: &mut impl Rng,
+    marbles: &[Marble],
+    maybe_teams: Option<&Teams>,
+    scene_width: f64,
+    scene_height: f64,
+) -> Scene {
+    let mut scene = level_13(marbles.len(), scene_width, scene_height).build_scene(
+        rng,
+        marbles,
+        maybe_teams,
+        (scene_width, scene_height),
+        all_won_condition(5.0),
+    );
+
+    scene.set_elimination(Some(Elimination::OutOfBounds));
+
+    scene
+}
+
+/// A scripted level, with a trapdoor start and events along the way.
+pub fn scene_15(
+    rng: &mut impl Rng,
+    marbles: &[Marble],
+    maybe_teams: Option<&Teams>,
+    scene_width: f64,
+    scene_height: f64,
+) -> Scene {
+    level_14(marbles.len(), scene_width, scene_height).build_scene(
+        rng,
+        marbles,
+        maybe_teams,
+        (scene_width, scene_height),
+        all_won_condition(5.0),
+    )
+}
+
+/// Risk and reward, with a shortcut past spikes and lava or the long way through the mud.
+pub fn scene_16(
+    rng: &mut impl Rng,
+    marbles: &[Marble],
+    maybe_teams: Option<&Teams>,
+    scene_width: f64,
+    scene_height: f64,
+) -> Scene {
+    level_15(marbles.len(), scene_width, scene_height).build_scene(
+        rng,
+        marbles,
+        maybe_teams,
+        (scene_width, scene_height),
+        all_won_condition(5.0),
+    )
+}
+
+/// Shelves with a portal shortcut to the bottom and a portal back to the top.
+pub fn scene_17(
+    rng: &mut impl Rng,
+    marbles: &[Marble],
+    maybe_teams: Option<&Teams>,
+    scene_width: f64,
+    scene_height: f64,
+) -> Scene {
+    level_16(marbles.len(), scene_width, scene_height).build_scene(
+        rng,
+        marbles,
+        maybe_teams,
+        (scene_width, scene_height),
         all_won_condition(5.0),
     )
 }
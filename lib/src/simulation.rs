@@ -69,6 +69,10 @@ impl Simulation {
         &self.scene
     }
 
+    pub fn get_graphics(&self) -> &Vec<Box<dyn Graphic>> {
+        &self.graphics
+    }
+
     pub fn get_maybe_all_won_time(&self) -> Option<f64> {
         self.maybe_all_won_time
     }
@@ -95,6 +99,11 @@ impl Simulation {
 
     pub fn update(&self, dt: f64, timescale: f64, physics_steps: usize) -> (Self, Vec<Collision>) {
         let (new_scene, collisions) = match self.get_phase() {
+            SimulationPhase::Countdown
+                if self.scene.get_start().is_some_and(|start| start.gate) =>
+            {
+                self.scene.settle(dt, timescale, physics_steps)
+            }
             SimulationPhase::Countdown => (self.scene.clone(), vec![]),
             SimulationPhase::Running => self.scene.update(dt, timescale, physics_steps),
         };
@@ -102,10 +111,10 @@ impl Simulation {
         let mut new_simulation = self.clone();
         new_simulation.scene = new_scene;
 
-        new_simulation
-            .graphics
-            .iter_mut()
-            .for_each(|graphic| graphic.update(dt));
+        new_simulation.graphics.iter_mut().for_each(|graphic| {
+            graphic.update(dt);
+            graphic.observe(&new_simulation.scene);
+        });
 
         if new_simulation.scene.any_won() && new_simulation.maybe_any_won_time.is_none() {
             new_simulation.maybe_any_won_time = Some(new_simulation.time);
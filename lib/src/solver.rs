@@ -0,0 +1,281 @@
+use glam::DVec2;
+
+use crate::{ball::Ball, scene::FRICTION, wall::Wall};
+
+const ITERATIONS: usize = 10;
+/// Contacts closing slower than this, in pixels per second, rest instead of bouncing.
+const RESTING_SPEED: f64 = 30.0;
+/// How much of the overlap each pass of the position correction takes out.
+const CORRECTION: f64 = 0.2;
+/// Overlap that's left alone, so that resting balls stay in contact from one step to the next.
+const SLOP: f64 = 0.5;
+/// The furthest one pass of the position correction moves a contact apart, in pixels.
+const MAX_CORRECTION: f64 = 5.0;
+
+/// How a scene works out what happens when balls touch walls and each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Solver {
+    /// Every contact bounces a ball at once, and overlaps are pushed apart afterwards. The levels
+    /// were made with this one.
+    #[default]
+    Reflection,
+    /// Contacts are solved one at a time over several passes with sequential impulses, and
+    /// overlaps are taken out gradually without touching velocities, so stacks and piles settle
+    /// instead of jittering.
+    Impulse,
+}
+
+/// Somewhere a ball is touching a wall or another ball.
+#[derive(Debug, Clone)]
+pub struct Contact {
+    ball: usize,
+    maybe_other_ball: Option<usize>,
+    point: DVec2,
+    normal: DVec2,
+    overlap: f64,
+    inverse_masses: [f64; 2],
+    bounce_speed: f64,
+    normal_impulse: f64,
+    tangent_impulse: f64,
+}
+
+impl Contact {
+    fn new(
+        balls: &[Ball],
+        ball: usize,
+        maybe_other_ball: Option<usize>,
+        point: DVec2,
+        normal: DVec2,
+        overlap: f64,
+    ) -> Self {
+        let physics_ball = balls[ball].get_physics_ball();
+
+        // Shielded balls don't get pushed around by other balls, so they act like walls to them
+        let get_inverse_mass = |index: usize| {
+            let physics_ball = balls[index].get_physics_ball();
+
+            if maybe_other_ball.is_some() && physics_ball.is_shielded() {
+                0.0
+            } else {
+                1.0 / physics_ball.get_mass()
+            }
+        };
+
+        let (inverse_masses, elasticity) = match maybe_other_ball {
+            Some(other_ball) => (
+                [get_inverse_mass(ball), get_inverse_mass(other_ball)],
+                (physics_ball.get_elasticity() + balls[other_ball].get_elasticity()) / 2.0,
+            ),
+            None => ([get_inverse_mass(ball), 0.0], physics_ball.get_elasticity()),
+        };
+
+        let mut contact = Self {
+            ball,
+            maybe_other_ball,
+            point,
+            normal,
+            overlap,
+            inverse_masses,
+            bounce_speed: 0.0,
+            normal_impulse: 0.0,
+            tangent_impulse: 0.0,
+        };
+
+        // An elasticity of 1 bounces straight back and one of 0.5 stops dead, the same as with
+        // reflections
+        let normal_speed = contact.get_relative_velocity(balls).dot(normal);
+
+        if normal_speed < -RESTING_SPEED {
+            contact.bounce_speed = -normal_speed * (2.0 * elasticity - 1.0).clamp(0.0, 1.0);
+        }
+
+        contact
+    }
+
+    pub fn get_ball(&self) -> usize {
+        self.ball
+    }
+
+    /// The other ball, or `None` if the ball is touching a wall.
+    pub fn get_other_ball(&self) -> Option<usize> {
+        self.maybe_other_ball
+    }
+
+    pub fn get_point(&self) -> DVec2 {
+        self.point
+    }
+
+    /// Points from the other side of the contact to the ball.
+    pub fn get_normal(&self) -> DVec2 {
+        self.normal
+    }
+
+    pub fn get_overlap(&self) -> f64 {
+        self.overlap
+    }
+
+    /// How fast the ball's surface moves away from the other side's at the contact.
+    fn get_relative_velocity(&self, balls: &[Ball]) -> DVec2 {
+        let velocity = balls[self.ball]
+            .get_physics_ball()
+            .get_surface_velocity(-self.normal);
+
+        match self.maybe_other_ball {
+            Some(other_ball) => {
+                velocity
+                    - balls[other_ball]
+                        .get_physics_ball()
+                        .get_surface_velocity(self.normal)
+            }
+            None => velocity,
+        }
+    }
+
+    fn apply_impulse(&self, balls: &mut [Ball], impulse: DVec2) {
+        let [inverse_mass, other_inverse_mass] = self.inverse_masses;
+
+        // A solid disc's moment of inertia is half its mass times its radius squared
+        let ball = &mut balls[self.ball];
+        ball.set_velocity(ball.get_velocity() + impulse * inverse_mass);
+        ball.set_angular_velocity(
+            ball.get_angular_velocity()
+                + 2.0 * inverse_mass * (-self.normal).perp_dot(impulse) / ball.get_radius(),
+        );
+
+        if let Some(other_ball) = self.maybe_other_ball {
+            let other_ball = &mut balls[other_ball];
+            other_ball.set_velocity(other_ball.get_velocity() - impulse * other_inverse_mass);
+            other_ball.set_angular_velocity(
+                other_ball.get_angular_velocity()
+                    - 2.0 * other_inverse_mass * self.normal.perp_dot(impulse)
+                        / other_ball.get_radius(),
+            );
+        }
+    }
+}
+
+/// Every contact between the balls that are still racing and the solid walls and each other.
+pub fn find_contacts(balls: &[Ball], walls: &[Box<dyn Wall>]) -> Vec<Contact> {
+    let mut contacts = Vec::new();
+
+    for (index, ball) in balls.iter().enumerate() {
+        if ball.is_eliminated() {
+            continue;
+        }
+
+        for intersection_point in walls
+            .iter()
+            .filter(|wall| wall.is_solid())
+            .filter_map(|wall| ball.get_intersection_point(wall.as_ref()))
+        {
+            let intersection_vector = ball.get_position() - intersection_point;
+
+            if let Some(normal) = intersection_vector.try_normalize() {
+                contacts.push(Contact::new(
+                    balls,
+                    index,
+                    None,
+                    intersection_point,
+                    normal,
+                    ball.get_radius() - intersection_vector.length(),
+                ));
+            }
+        }
+
+        for (other_index, other_ball) in balls.iter().enumerate().skip(index + 1) {
+            if other_ball.is_eliminated() {
+                continue;
+            }
+
+            let intersection_vector = ball.get_position() - other_ball.get_position();
+            let overlap =
+                ball.get_radius() + other_ball.get_radius() - intersection_vector.length();
+
+            if overlap > 0.0
+                && let Some(normal) = intersection_vector.try_normalize()
+            {
+                contacts.push(Contact::new(
+                    balls,
+                    index,
+                    Some(other_index),
+                    ball.get_position().midpoint(other_ball.get_position()),
+                    normal,
+                    overlap,
+                ));
+            }
+        }
+    }
+
+    contacts
+}
+
+/// Solves the contacts one at a time, over and over, so that impulses travel through everything
+/// that's touching. Each contact's total push is kept from pulling, and its friction from going
+/// over what the push allows.
+pub fn solve_velocities(balls: &mut [Ball], contacts: &mut [Contact]) {
+    for _ in 0..ITERATIONS {
+        for contact in contacts.iter_mut() {
+            let inverse_mass = contact.inverse_masses.iter().sum::<f64>();
+
+            if inverse_mass == 0.0 {
+                continue;
+            }
+
+            let normal_speed = contact.get_relative_velocity(balls).dot(contact.normal);
+            let normal_impulse = (contact.normal_impulse
+                + (contact.bounce_speed - normal_speed) / inverse_mass)
+                .max(0.0);
+            contact.apply_impulse(
+                balls,
+                contact.normal * (normal_impulse - contact.normal_impulse),
+            );
+            contact.normal_impulse = normal_impulse;
+
+            // Spin takes up two thirds of a sliding impulse, so stopping the slide takes a third as
+            // much
+            let tangent = contact.normal.perp();
+            let tangent_speed = contact.get_relative_velocity(balls).dot(tangent);
+            let max_tangent_impulse = FRICTION * contact.normal_impulse;
+            let tangent_impulse = (contact.tangent_impulse - tangent_speed / (3.0 * inverse_mass))
+                .clamp(-max_tangent_impulse, max_tangent_impulse);
+            contact.apply_impulse(balls, tangent * (tangent_impulse - contact.tangent_impulse));
+            contact.tangent_impulse = tangent_impulse;
+        }
+    }
+}
+
+/// How far to move each ball to take out most of the overlap at its contacts. Velocities aren't
+/// touched, so pushing balls apart can't add energy.
+pub fn get_position_offsets(balls: &[Ball], contacts: &[Contact]) -> Vec<DVec2> {
+    let mut offsets = vec![DVec2::ZERO; balls.len()];
+
+    for _ in 0..ITERATIONS {
+        for contact in contacts {
+            let [inverse_mass, other_inverse_mass] = contact.inverse_masses;
+            let total_inverse_mass = inverse_mass + other_inverse_mass;
+
+            if total_inverse_mass == 0.0 {
+                continue;
+            }
+
+            let mut separation = offsets[contact.ball].dot(contact.normal);
+
+            if let Some(other_ball) = contact.maybe_other_ball {
+                separation -= offsets[other_ball].dot(contact.normal);
+            }
+
+            let correction =
+                (CORRECTION * (contact.overlap - separation - SLOP)).clamp(0.0, MAX_CORRECTION);
+
+            offsets[contact.ball] +=
+                contact.normal * correction * inverse_mass / total_inverse_mass;
+
+            if let Some(other_ball) = contact.maybe_other_ball {
+                offsets[other_ball] -=
+                    contact.normal * correction * other_inverse_mass / total_inverse_mass;
+            }
+        }
+    }
+
+    offsets
+}
@@ -0,0 +1,16 @@
+use glam::DVec2;
+
+/// How long a starting gate takes to slide out of the way.
+pub const GATE_RETRACT_SECONDS: f64 = 0.25;
+
+/// How the marbles get going at "Go!". Without one they hang in the air during the countdown and
+/// all drop at once.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Start {
+    /// The marbles settle onto a gate during the countdown, and it retracts at "Go!".
+    pub gate: bool,
+    /// Added to every marble's velocity when it's released, like a plunger firing it.
+    pub launch_velocity: DVec2,
+    /// Seconds between releasing each marble, from left to right. 0 lets them all go at once.
+    pub stagger_seconds: f64,
+}
@@ -0,0 +1,226 @@
+use chrono::{DateTime, Utc};
+use database::race_marble::DbMarbleResult;
+
+/// Finishes closer than this many seconds to the neighbouring marble count as close races.
+const CLOSE_RACE_SECONDS: f64 = 0.5;
+
+/// A marble's record, computed from its finishes in `race_marble`.
+#[derive(Debug, Clone)]
+pub struct MarbleRecord {
+    results: Vec<DbMarbleResult>,
+}
+
+impl MarbleRecord {
+    /// `results` must be ordered from oldest to newest.
+    pub fn new(results: Vec<DbMarbleResult>) -> Self {
+        Self { results }
+    }
+
+    pub fn races(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn wins(&self) -> usize {
+        self.results.iter().filter(|result| is_win(result)).count()
+    }
+
+    pub fn losses(&self) -> usize {
+        self.races() - self.wins()
+    }
+
+    /// Number of consecutive most recent races matching `predicate`.
+    fn streak(&self, predicate: impl Fn(&DbMarbleResult) -> bool) -> usize {
+        self.results
+            .iter()
+            .rev()
+            .take_while(|result| predicate(result))
+            .count()
+    }
+
+    pub fn win_streak(&self) -> usize {
+        self.streak(is_win)
+    }
+
+    pub fn losing_streak(&self) -> usize {
+        self.streak(|result| !is_win(result))
+    }
+
+    pub fn longest_losing_streak(&self) -> usize {
+        self.results
+            .split(is_win)
+            .map(|losses| losses.len())
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Races since the marble last finished in the top three.
+    pub fn podium_drought(&self) -> usize {
+        self.streak(|result| !is_podium(result))
+    }
+
+    /// Races since the marble last finished in the top half of the field.
+    pub fn top_half_drought(&self) -> usize {
+        self.streak(|result| !is_top_half(result))
+    }
+
+    /// Consecutive most recent races finished in second place.
+    pub fn second_place_streak(&self) -> usize {
+        self.streak(|result| result.place == 2)
+    }
+
+    /// Length of the losing streak that the most recent race ended, if it was a win.
+    pub fn broken_losing_streak(&self) -> usize {
+        match self.results.split_last() {
+            Some((last, rest)) if is_win(last) => rest
+                .iter()
+                .rev()
+                .take_while(|result| !is_win(result))
+                .count(),
+            _ => 0,
+        }
+    }
+
+    pub fn won_last_race(&self) -> bool {
+        self.results.last().is_some_and(is_win)
+    }
+
+    pub fn level_races(&self, level_id: i64) -> usize {
+        self.results
+            .iter()
+            .filter(|result| result.level_id == level_id)
+            .count()
+    }
+
+    pub fn level_wins(&self, level_id: i64) -> usize {
+        self.results
+            .iter()
+            .filter(|result| result.level_id == level_id && is_win(result))
+            .count()
+    }
+
+    pub fn close_wins(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|result| {
+                is_win(result)
+                    && result
+                        .maybe_next_time
+                        .is_some_and(|next_time| next_time - result.time < CLOSE_RACE_SECONDS)
+            })
+            .count()
+    }
+
+    pub fn close_losses(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|result| {
+                result.place == 2
+                    && result.maybe_previous_time.is_some_and(|previous_time| {
+                        result.time - previous_time < CLOSE_RACE_SECONDS
+                    })
+            })
+            .count()
+    }
+
+    /// Share of races finished either first or last.
+    pub fn first_or_last_rate(&self) -> f64 {
+        if self.results.is_empty() {
+            return 0.0;
+        }
+
+        self.results
+            .iter()
+            .filter(|result| is_win(result) || result.place == result.field_size)
+            .count() as f64
+            / self.races() as f64
+    }
+
+    /// Standard deviation of the marble's finishing position, scaled so that first is 0 and last
+    /// is 1 regardless of field size.
+    pub fn place_deviation(&self) -> f64 {
+        let positions = self
+            .results
+            .iter()
+            .filter(|result| result.field_size > 1)
+            .map(|result| (result.place - 1) as f64 / (result.field_size - 1) as f64)
+            .collect::<Vec<f64>>();
+
+        if positions.len() < 2 {
+            return 0.0;
+        }
+
+        let mean = positions.iter().sum::<f64>() / positions.len() as f64;
+
+        (positions
+            .iter()
+            .map(|position| (position - mean).powi(2))
+            .sum::<f64>()
+            / positions.len() as f64)
+            .sqrt()
+    }
+
+    /// How many places better the marble has finished on average over its last `window` races
+    /// compared to the `window` before that.
+    pub fn recent_improvement(&self, window: usize) -> f64 {
+        if self.races() < 2 * window || window == 0 {
+            return 0.0;
+        }
+
+        let average_place = |results: &[DbMarbleResult]| {
+            results
+                .iter()
+                .map(|result| result.place as f64)
+                .sum::<f64>()
+                / results.len() as f64
+        };
+
+        let recent = &self.results[self.races() - window..];
+        let before = &self.results[self.races() - 2 * window..self.races() - window];
+
+        average_place(before) - average_place(recent)
+    }
+
+    /// Whole days since the marble last finished on the podium, if it ever has and the race was
+    /// timestamped.
+    pub fn days_since_podium(&self, now: DateTime<Utc>) -> Option<i64> {
+        self.results
+            .iter()
+            .rev()
+            .find(|result| is_podium(result))
+            .and_then(|result| result.race_time)
+            .and_then(DateTime::from_timestamp_secs)
+            .map(|race_time| (now - race_time).num_days())
+    }
+
+    pub fn days_since_last_race(&self, now: DateTime<Utc>) -> Option<i64> {
+        self.results
+            .last()
+            .and_then(|result| result.race_time)
+            .and_then(DateTime::from_timestamp_secs)
+            .map(|race_time| (now - race_time).num_days())
+    }
+}
+
+fn is_win(result: &DbMarbleResult) -> bool {
+    result.place == 1
+}
+
+fn is_podium(result: &DbMarbleResult) -> bool {
+    result.place <= 3
+}
+
+fn is_top_half(result: &DbMarbleResult) -> bool {
+    result.place * 2 <= result.field_size
+}
+
+pub fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+
+    format!("{}{}", n, suffix)
+}
@@ -0,0 +1,114 @@
+use api::marble::Marble;
+use palette::Srgba;
+use rand::{Rng, seq::IndexedRandom};
+
+#[derive(Debug, Clone)]
+pub struct Team {
+    pub name: String,
+    pub color: Srgba,
+    pub marbles: Vec<Marble>,
+}
+
+impl Team {
+    pub fn new(name: String, color: Srgba, marbles: Vec<Marble>) -> Self {
+        Self {
+            name,
+            color,
+            marbles,
+        }
+    }
+}
+
+/// How a team race is won.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TeamScoring {
+    /// Every marble scores its place, and the lowest average place wins, so that teams with fewer
+    /// marbles in the race aren't penalised. Marbles still racing score the place they're in.
+    AveragePlace,
+    /// The first team to get this many marbles to the finish wins.
+    FirstTo(usize),
+}
+
+/// A team's result in a single race.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TeamResult {
+    /// Index into `Teams::get_teams`.
+    pub team: usize,
+    /// Sum of the team's places, counting from 1.
+    pub place_total: usize,
+    /// How many of the team's marbles are in the race.
+    pub marbles: usize,
+    pub finishers: usize,
+}
+
+impl TeamResult {
+    /// The team's average place, counting from 1.
+    pub fn get_average_place(&self) -> f64 {
+        self.place_total as f64 / self.marbles.max(1) as f64
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Teams {
+    teams: Vec<Team>,
+    scoring: TeamScoring,
+}
+
+impl Teams {
+    pub fn new(teams: Vec<Team>, scoring: TeamScoring) -> Self {
+        Self { teams, scoring }
+    }
+
+    pub fn get_teams(&self) -> &Vec<Team> {
+        &self.teams
+    }
+
+    pub fn get_scoring(&self) -> TeamScoring {
+        self.scoring
+    }
+
+    /// The team a marble races for.
+    pub fn get_team_index(&self, marble: &Marble) -> Option<usize> {
+        self.teams.iter().position(|team| {
+            team.marbles
+                .iter()
+                .any(|team_marble| team_marble.id == marble.id)
+        })
+    }
+
+    /// Picks `per_team` marbles at random from every team, alternating between teams.
+    pub fn pick_marbles(&self, rng: &mut impl Rng, per_team: usize) -> Vec<Marble> {
+        let picks = self
+            .teams
+            .iter()
+            .map(|team| {
+                team.marbles
+                    .sample(rng, per_team)
+                    .cloned()
+                    .collect::<Vec<Marble>>()
+            })
+            .collect::<Vec<Vec<Marble>>>();
+
+        (0..per_team)
+            .flat_map(|index| {
+                picks
+                    .iter()
+                    .filter_map(move |pick| pick.get(index).cloned())
+            })
+            .collect()
+    }
+
+    /// Orders team results from best to worst under the scoring rule.
+    pub fn rank(&self, results: &mut [TeamResult]) {
+        match self.scoring {
+            TeamScoring::AveragePlace => {
+                results.sort_by(|a, b| a.get_average_place().total_cmp(&b.get_average_place()))
+            }
+            TeamScoring::FirstTo(_) => results.sort_by(|a, b| {
+                b.finishers
+                    .cmp(&a.finishers)
+                    .then(a.get_average_place().total_cmp(&b.get_average_place()))
+            }),
+        }
+    }
+}
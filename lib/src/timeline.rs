@@ -0,0 +1,146 @@
+use glam::DVec2;
+
+use crate::{event::RaceEvent, powerup::Powerup, scene::Scene};
+
+/// The kinds of race event a timeline can wait for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EventKind {
+    Finished,
+    Eliminated,
+    LapCompleted,
+    Scored,
+    Special,
+    Hazard,
+    TrapdoorOpened,
+}
+
+impl EventKind {
+    pub fn matches(&self, event: &RaceEvent) -> bool {
+        matches!(
+            (self, event),
+            (Self::Finished, RaceEvent::Finished { .. })
+                | (Self::Eliminated, RaceEvent::Eliminated { .. })
+                | (Self::LapCompleted, RaceEvent::LapCompleted { .. })
+                | (Self::Scored, RaceEvent::Scored { .. })
+                | (Self::Special, RaceEvent::Special { .. })
+                | (Self::Hazard, RaceEvent::Hazard { .. })
+                | (Self::TrapdoorOpened, RaceEvent::TrapdoorOpened { .. })
+        )
+    }
+}
+
+/// What sets off a timeline action.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cue {
+    /// Once the race has run for this many seconds.
+    Time(f64),
+    /// Once a racing ball's center is inside the rectangle from `min` to `max`. With `leader_only`
+    /// it has to be the ball in the lead. A thin rectangle works as a line to cross.
+    Zone {
+        min: DVec2,
+        max: DVec2,
+        leader_only: bool,
+    },
+    /// Once something of this kind happens in the race.
+    Event(EventKind),
+}
+
+impl Cue {
+    pub fn is_met(&self, scene: &Scene) -> bool {
+        match self {
+            Self::Time(seconds) => scene.get_time() >= *seconds,
+            Self::Zone {
+                min,
+                max,
+                leader_only,
+            } => {
+                let racing = scene.get_racing();
+
+                let is_inside = |index: &usize| {
+                    let position = scene.get_balls()[*index].get_position();
+
+                    position.cmpge(*min).all() && position.cmple(*max).all()
+                };
+
+                if *leader_only {
+                    scene
+                        .get_standings()
+                        .iter()
+                        .find(|index| racing.contains(index))
+                        .is_some_and(is_inside)
+                } else {
+                    racing.iter().any(is_inside)
+                }
+            }
+            Self::Event(kind) => scene.get_events().iter().any(|event| kind.matches(event)),
+        }
+    }
+}
+
+/// What a timeline does to the scene once its cue is met.
+#[derive(Clone)]
+pub enum Action {
+    /// Opens every wall with this id.
+    OpenWall(String),
+    /// Closes every wall with this id.
+    CloseWall(String),
+    /// Adds a powerup to the scene, wherever it was built.
+    SpawnPowerup(Box<dyn Powerup>),
+    /// Turns gravity upside down for every racing ball for this many seconds.
+    FlipGravity(f64),
+    /// Drops a special right onto whichever racing ball is in last place.
+    SpecialOnLastPlace,
+}
+
+#[derive(Clone)]
+pub struct TimelineEntry {
+    cue: Cue,
+    action: Action,
+    fired: bool,
+}
+
+impl TimelineEntry {
+    pub fn get_cue(&self) -> &Cue {
+        &self.cue
+    }
+
+    pub fn get_action(&self) -> &Action {
+        &self.action
+    }
+
+    pub fn is_fired(&self) -> bool {
+        self.fired
+    }
+
+    pub fn set_fired(&mut self, fired: bool) {
+        self.fired = fired;
+    }
+}
+
+/// A level's script. Each entry fires its action once, the first time its cue is met.
+#[derive(Clone, Default)]
+pub struct Timeline {
+    entries: Vec<TimelineEntry>,
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, cue: Cue, action: Action) {
+        self.entries.push(TimelineEntry {
+            cue,
+            action,
+            fired: false,
+        });
+    }
+
+    pub fn get_entries(&self) -> &Vec<TimelineEntry> {
+        &self.entries
+    }
+
+    pub fn get_entries_mut(&mut self) -> &mut Vec<TimelineEntry> {
+        &mut self.entries
+    }
+}
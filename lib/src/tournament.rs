@@ -0,0 +1,251 @@
+use std::{collections::HashMap, fmt::Display};
+
+use database::{
+    league::DbLeague,
+    marble::DbMarble,
+    rating::DbRating,
+    tournament::{DbHeat, DbTournament},
+};
+use even_odd_traits::IsEven;
+use sqlx::SqlitePool;
+
+use crate::rating::DEFAULT_RATING;
+
+/// Where a heat sits in its tournament.
+#[derive(Debug, Clone)]
+pub struct Bracket {
+    pub tournament_name: String,
+    pub round: i64,
+    pub heat: i64,
+    pub heats_in_round: usize,
+    /// How many marbles from this heat go through to the next round.
+    pub advance: usize,
+}
+
+impl Bracket {
+    pub fn is_final(&self) -> bool {
+        self.heats_in_round == 1
+    }
+
+    pub fn stage(&self) -> String {
+        match self.heats_in_round {
+            1 => String::from("Final"),
+            2 => format!("Semifinal {}", self.heat),
+            4 => format!("Quarterfinal {}", self.heat),
+            _ => format!("Round {} Heat {}", self.round, self.heat),
+        }
+    }
+
+    pub fn stakes(&self) -> String {
+        if self.is_final() {
+            String::from("winner takes the tournament")
+        } else if self.advance == 1 {
+            String::from("winner advances")
+        } else {
+            format!("top {} advance", self.advance)
+        }
+    }
+}
+
+impl Display for Bracket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} — {}", self.stage(), self.stakes())
+    }
+}
+
+/// Enters every marble in the league into a new tournament, seeded by their rating in that league.
+pub async fn create_tournament(
+    pool: &SqlitePool,
+    league: &DbLeague,
+    name: &str,
+    heat_size: usize,
+    advance: usize,
+) -> sqlx::Result<DbTournament> {
+    let mut field = Vec::new();
+
+    for marble in DbMarble::get_by_league(pool, &league.name).await? {
+        let rating = DbRating::get(pool, marble.id, Some(league.id))
+            .await?
+            .map(|rating| rating.rating)
+            .unwrap_or(DEFAULT_RATING);
+
+        field.push((marble.id, rating));
+    }
+
+    field.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+    DbTournament::insert(
+        pool,
+        league.id,
+        name,
+        heat_size as i64,
+        advance as i64,
+        &field
+            .iter()
+            .map(|(marble_id, _)| *marble_id)
+            .collect::<Vec<i64>>(),
+    )
+    .await
+}
+
+/// Splits a seeded field into as few heats as `heat_size` allows, dealing the seeds back and forth
+/// so that every heat gets a fair share of the strong marbles.
+fn draw_heats(seeded: &[i64], heat_size: usize) -> Vec<Vec<i64>> {
+    let heat_count = seeded.len().div_ceil(heat_size.max(1)).max(1);
+    let mut heats = vec![Vec::new(); heat_count];
+
+    for (index, marble_id) in seeded.iter().enumerate() {
+        let position = index % heat_count;
+
+        let heat = if (index / heat_count).is_even() {
+            position
+        } else {
+            heat_count - 1 - position
+        };
+
+        heats[heat].push(*marble_id);
+    }
+
+    heats
+}
+
+/// At least one marble goes out of every heat, so the field always shrinks.
+fn advancing(heat_size: usize, advance: usize) -> usize {
+    advance.min(heat_size.saturating_sub(1)).max(1)
+}
+
+async fn get_bracket(
+    pool: &SqlitePool,
+    tournament: &DbTournament,
+    heats: &[DbHeat],
+    heat: &DbHeat,
+) -> sqlx::Result<Bracket> {
+    Ok(Bracket {
+        tournament_name: tournament.name.clone(),
+        round: heat.round,
+        heat: heat.number,
+        heats_in_round: heats
+            .iter()
+            .filter(|other| other.round == heat.round)
+            .count(),
+        advance: advancing(
+            heat.get_marbles(pool).await?.len(),
+            tournament.advance as usize,
+        ),
+    })
+}
+
+/// The next heat that needs to be run, drawing the next round from the previous round's results
+/// when it is complete. `None` once the final has been run.
+pub async fn next_heat(
+    pool: &SqlitePool,
+    tournament: &DbTournament,
+) -> sqlx::Result<Option<(DbHeat, Bracket)>> {
+    let mut heats = tournament.get_heats(pool).await?;
+
+    let seeds = tournament
+        .get_entries(pool)
+        .await?
+        .into_iter()
+        .map(|entry| (entry.marble_id, entry.seed))
+        .collect::<HashMap<i64, i64>>();
+
+    let (round, seeded) = match heats.last() {
+        None => {
+            let mut seeded = seeds.keys().copied().collect::<Vec<i64>>();
+            seeded.sort_by_key(|marble_id| seeds[marble_id]);
+
+            (1, seeded)
+        }
+        Some(last) => {
+            let round = last.round;
+
+            if let Some(heat) = heats
+                .iter()
+                .find(|heat| heat.round == round && heat.race_id.is_none())
+            {
+                let bracket = get_bracket(pool, tournament, &heats, heat).await?;
+
+                return Ok(Some((*heat, bracket)));
+            }
+
+            let round_heats = heats
+                .iter()
+                .filter(|heat| heat.round == round)
+                .collect::<Vec<&DbHeat>>();
+
+            if round_heats.len() == 1 {
+                return Ok(None);
+            }
+
+            let mut advancers = Vec::new();
+
+            for heat in round_heats {
+                let advance = advancing(
+                    heat.get_marbles(pool).await?.len(),
+                    tournament.advance as usize,
+                );
+
+                for result in heat.get_results(pool).await?.into_iter().take(advance) {
+                    advancers.push((
+                        result.place,
+                        seeds.get(&result.marble_id).copied().unwrap_or(i64::MAX),
+                        result.marble_id,
+                    ));
+                }
+            }
+
+            advancers.sort();
+
+            (
+                round + 1,
+                advancers
+                    .into_iter()
+                    .map(|(_, _, marble_id)| marble_id)
+                    .collect(),
+            )
+        }
+    };
+
+    for (index, marble_ids) in draw_heats(&seeded, tournament.heat_size as usize)
+        .iter()
+        .enumerate()
+    {
+        heats.push(
+            tournament
+                .insert_heat(pool, round, (index + 1) as i64, marble_ids)
+                .await?,
+        );
+    }
+
+    let heat = heats
+        .iter()
+        .find(|heat| heat.round == round)
+        .copied()
+        .expect("A drawn round has at least one heat");
+    let bracket = get_bracket(pool, tournament, &heats, &heat).await?;
+
+    Ok(Some((heat, bracket)))
+}
+
+/// The winner of the final, once it has been run.
+pub async fn get_champion(
+    pool: &SqlitePool,
+    tournament: &DbTournament,
+) -> sqlx::Result<Option<i64>> {
+    let heats = tournament.get_heats(pool).await?;
+
+    let Some(last) = heats.last() else {
+        return Ok(None);
+    };
+
+    if heats.iter().filter(|heat| heat.round == last.round).count() > 1 {
+        return Ok(None);
+    }
+
+    Ok(last
+        .get_results(pool)
+        .await?
+        .first()
+        .map(|result| result.marble_id))
+}
@@ -18,9 +18,10 @@ use crate::{
     scene::Scene,
     scenes::{
         scene_1, scene_2, scene_3, scene_4, scene_5, scene_6, scene_7, scene_8, scene_9, scene_10,
-        scene_11,
+        scene_11, scene_12, scene_13, scene_14, scene_15, scene_16, scene_17,
     },
     simulation::Simulation,
+    team::Teams,
 };
 
 #[cfg(feature = "macroquad")]
@@ -149,19 +150,31 @@ pub fn get_frame_template(padding: usize) -> String {
     format_frame_name(&format!("%0{padding}d"))
 }
 
-pub fn get_scenes(rng: &mut impl Rng, marbles: &[Marble], width: f64, height: f64) -> Vec<Scene> {
+pub fn get_scenes(
+    rng: &mut impl Rng,
+    marbles: &[Marble],
+    maybe_teams: Option<&Teams>,
+    width: f64,
+    height: f64,
+) -> Vec<Scene> {
     vec![
-        scene_1(rng, marbles, width, height),
-        scene_2(rng, marbles, width, height),
-        scene_3(rng, marbles, width, height),
-        scene_4(rng, marbles, width, height),
-        scene_5(rng, marbles, width, height),
-        scene_6(rng, marbles, width, height),
+        scene_1(rng, marbles, maybe_teams, width, height),
+        scene_2(rng, marbles, maybe_teams, width, height),
+        scene_3(rng, marbles, maybe_teams, width, height),
+        scene_4(rng, marbles, maybe_teams, width, height),
+        scene_5(rng, marbles, maybe_teams, width, height),
+        scene_6(rng, marbles, maybe_teams, width, height),
         scene_7(width, height),
-        scene_8(rng, marbles, width, height),
-        scene_9(rng, marbles, width, height),
-        scene_10(rng, marbles, width, height),
-        scene_11(rng, marbles, width, height),
+        scene_8(rng, marbles, maybe_teams, width, height),
+        scene_9(rng, marbles, maybe_teams, width, height),
+        scene_10(rng, marbles, maybe_teams, width, height),
+        scene_11(rng, marbles, maybe_teams, width, height),
+        scene_12(rng, marbles, maybe_teams, width, height),
+        scene_13(rng, marbles, maybe_teams, width, height),
+        scene_14(rng, marbles, maybe_teams, width, height),
+        scene_15(rng, marbles, maybe_teams, width, height),
+        scene_16(rng, marbles, maybe_teams, width, height),
+        scene_17(rng, marbles, maybe_teams, width, height),
     ]
 }
 
@@ -169,10 +182,11 @@ pub fn get_scene(
     rng: &mut impl Rng,
     scene_number: usize,
     marbles: &[Marble],
+    maybe_teams: Option<&Teams>,
     width: f64,
     height: f64,
 ) -> Scene {
-    let scenes = get_scenes(rng, marbles, width, height);
+    let scenes = get_scenes(rng, marbles, maybe_teams, width, height);
 
     scenes[scene_number - 1].clone()
 }
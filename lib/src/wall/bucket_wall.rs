@@ -0,0 +1,68 @@
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::Renderer;
+
+use crate::{ball::PhysicsBall, rendering::Render};
+
+use super::{
+    Wall,
+    straight_wall::{Line, StraightWall},
+};
+
+/// The floor of a plinko bucket. It's a goal worth `score` points to any ball that lands in it.
+#[derive(Clone)]
+pub struct BucketWall {
+    line: Line,
+    score: u32,
+}
+
+impl BucketWall {
+    pub fn new(line: Line, score: u32) -> Self {
+        Self { line, score }
+    }
+
+    pub fn get_line(&self) -> Line {
+        self.line
+    }
+}
+
+impl Wall for BucketWall {
+    fn update(&self, _dt: f64) -> Box<dyn Wall> {
+        Box::new(self.clone())
+    }
+
+    fn get_intersection_point(&self, ball: &PhysicsBall) -> Option<DVec2> {
+        StraightWall::new(self.get_line(), true).get_intersection_point(ball)
+    }
+
+    fn is_goal(&self) -> bool {
+        true
+    }
+
+    fn get_score(&self) -> Option<u32> {
+        Some(self.score)
+    }
+}
+
+impl Render for BucketWall {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let line = self.get_line();
+
+        renderer.render_line(
+            line.get_start(),
+            line.get_end(),
+            2.0,
+            Srgba::new(1.0, 0.85, 0.2, 1.0),
+        );
+
+        renderer.render_text_outline(
+            &self.score.to_string(),
+            line.get_point(0.5) - dvec2(0.0, 8.0),
+            anchor2d::CGB,
+            16.0,
+            1.0,
+            Srgba::new(1.0, 0.85, 0.2, 1.0),
+            Srgba::new(0.0, 0.0, 0.0, 1.0),
+        );
+    }
+}
@@ -15,6 +15,9 @@ pub struct CircleWall {
     start: f64,
     end: f64,
     is_goal: bool,
+    shrink_rate: f64,
+    min_radius: f64,
+    is_boundary: bool,
 }
 
 impl CircleWall {
@@ -25,6 +28,25 @@ impl CircleWall {
             start,
             end,
             is_goal,
+            shrink_rate: 0.0,
+            min_radius: radius,
+            is_boundary: false,
+        }
+    }
+
+    /// The bottom half of a ring that marbles fight to stay inside. It shrinks by `shrink_rate`
+    /// pixels per second down to `min_radius`, and any marble that ends up outside it is out of
+    /// bounds.
+    pub fn arena(position: DVec2, radius: f64, min_radius: f64, shrink_rate: f64) -> Self {
+        Self {
+            position,
+            radius,
+            start: 0.0,
+            end: 180.0,
+            is_goal: false,
+            shrink_rate,
+            min_radius,
+            is_boundary: true,
         }
     }
 
@@ -51,11 +73,25 @@ impl CircleWall {
     pub fn get_end_mod(&self) -> f64 {
         self.get_end() % 360.0
     }
+
+    /// Whether the wall covers the direction of `point` from its centre.
+    fn covers(&self, point: DVec2) -> bool {
+        let theta = ((point - self.get_position()).to_angle() / PI * 180.0 + 360.0) % 360.0;
+
+        if self.get_start_mod() < self.get_end_mod() {
+            (self.get_start_mod()..=self.get_end_mod()).contains(&theta)
+        } else {
+            !(self.get_end_mod()..=self.get_start_mod()).contains(&theta)
+        }
+    }
 }
 
 impl Wall for CircleWall {
-    fn update(&self, _dt: f64) -> Box<dyn Wall> {
-        Box::new(self.clone())
+    fn update(&self, dt: f64) -> Box<dyn Wall> {
+        let mut new_wall = self.clone();
+        new_wall.radius = (self.radius - self.shrink_rate * dt).max(self.min_radius);
+
+        Box::new(new_wall)
     }
 
     fn get_intersection_point(&self, ball: &PhysicsBall) -> Option<DVec2> {
@@ -89,24 +125,7 @@ impl Wall for CircleWall {
             let iy2 = p2y + h * (bx - wx) / d;
             let i2 = dvec2(ix2, iy2);
 
-            let theta1 =
-                ((i1 - self.get_position()).to_angle() as f64 / PI * 180.0 + 360.0) % 360.0;
-            let theta2 =
-                ((i2 - self.get_position()).to_angle() as f64 / PI * 180.0 + 360.0) % 360.0;
-
-            let theta1_valid = if self.get_start_mod() < self.get_end_mod() {
-                (self.get_start_mod()..=self.get_end_mod()).contains(&theta1)
-            } else {
-                !(self.get_end_mod()..=self.get_start_mod()).contains(&theta1)
-            };
-
-            let theta2_valid = if self.get_start_mod() < self.get_end_mod() {
-                (self.get_start_mod()..=self.get_end_mod()).contains(&theta2)
-            } else {
-                !(self.get_end_mod()..=self.get_start_mod()).contains(&theta2)
-            };
-
-            Some(match (theta1_valid, theta2_valid) {
+            Some(match (self.covers(i1), self.covers(i2)) {
                 (true, true) => i1.midpoint(i2),
                 (true, false) => i1,
                 (false, true) => i2,
@@ -118,6 +137,14 @@ impl Wall for CircleWall {
     fn is_goal(&self) -> bool {
         self.is_goal
     }
+
+    /// Balls are only out once they're past the wall itself, so ones thrown up through the open
+    /// side can still fall back in.
+    fn is_out_of_bounds(&self, ball: &PhysicsBall) -> bool {
+        self.is_boundary
+            && ball.get_position().distance(self.get_position()) > self.get_radius()
+            && self.covers(ball.get_position())
+    }
 }
 
 impl Render for CircleWall {
@@ -0,0 +1,67 @@
+use glam::DVec2;
+use render_agnostic::Renderer;
+
+use crate::{ball::PhysicsBall, rendering::Render};
+
+use super::Wall;
+
+/// A wall with an id that a level's timeline can open and close. It acts like the wall it wraps
+/// while closed, and like nothing at all while open.
+#[derive(Clone)]
+pub struct DoorWall {
+    id: String,
+    wall: Box<dyn Wall>,
+    open: bool,
+}
+
+impl DoorWall {
+    pub fn new(id: impl Into<String>, wall: Box<dyn Wall>, open: bool) -> Self {
+        Self {
+            id: id.into(),
+            wall,
+            open,
+        }
+    }
+}
+
+impl Wall for DoorWall {
+    fn update(&self, dt: f64) -> Box<dyn Wall> {
+        Box::new(Self {
+            id: self.id.clone(),
+            wall: self.wall.update(dt),
+            open: self.open,
+        })
+    }
+
+    fn get_intersection_point(&self, ball: &PhysicsBall) -> Option<DVec2> {
+        if self.is_open() {
+            return None;
+        }
+
+        self.wall.get_intersection_point(ball)
+    }
+
+    fn is_goal(&self) -> bool {
+        self.wall.is_goal()
+    }
+
+    fn get_id(&self) -> Option<&str> {
+        Some(&self.id)
+    }
+
+    fn set_open(&mut self, open: bool) {
+        self.open = open;
+    }
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+}
+
+impl Render for DoorWall {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        if !self.is_open() {
+            self.wall.render(renderer);
+        }
+    }
+}
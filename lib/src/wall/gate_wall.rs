@@ -0,0 +1,83 @@
+use glam::DVec2;
+use palette::Srgba;
+use render_agnostic::Renderer;
+
+use crate::{ball::PhysicsBall, rendering::Render};
+
+use super::{
+    Wall,
+    straight_wall::{Line, StraightWall},
+};
+
+/// A starting gate. It's solid until the race starts, then slides away towards its start point
+/// over `retract_seconds`.
+#[derive(Clone)]
+pub struct GateWall {
+    line: Line,
+    retract_seconds: f64,
+    time: f64,
+}
+
+impl GateWall {
+    pub fn new(line: Line, retract_seconds: f64) -> Self {
+        Self {
+            line,
+            retract_seconds,
+            time: 0.0,
+        }
+    }
+
+    /// How much of the gate is still in the way, from 1 when closed to 0 when fully open.
+    pub fn get_closed(&self) -> f64 {
+        if self.retract_seconds > 0.0 {
+            (1.0 - self.time / self.retract_seconds).clamp(0.0, 1.0)
+        } else if self.time > 0.0 {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    pub fn get_line(&self) -> Line {
+        Line::new(
+            self.line.get_start(),
+            self.line.get_point(self.get_closed()),
+        )
+    }
+}
+
+impl Wall for GateWall {
+    fn update(&self, dt: f64) -> Box<dyn Wall> {
+        let mut new_wall = self.clone();
+        new_wall.time += dt;
+
+        Box::new(new_wall)
+    }
+
+    fn get_intersection_point(&self, ball: &PhysicsBall) -> Option<DVec2> {
+        if self.get_closed() == 0.0 {
+            return None;
+        }
+
+        StraightWall::new(self.get_line(), false).get_intersection_point(ball)
+    }
+
+    fn is_goal(&self) -> bool {
+        false
+    }
+}
+
+impl Render for GateWall {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        if self.get_closed() == 0.0 {
+            return;
+        }
+
+        renderer.render_line(
+            self.get_line().get_start(),
+            self.get_line().get_end(),
+            4.0,
+            Srgba::new(0.9, 0.2, 0.2, 1.0),
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::Renderer;
+
+use crate::{ball::PhysicsBall, rendering::Render};
+
+use super::Wall;
+
+/// What happens to a ball inside a hazard zone.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hazard {
+    /// Knocks the ball out of the race, or sends it back to its last checkpoint when `respawn` is
+    /// set.
+    Lava { respawn: bool },
+    /// Works like lava, but looks like a bed of spikes.
+    Spikes { respawn: bool },
+    /// Slows the ball down, taking away this fraction of its velocity every second.
+    Mud { drag: f64 },
+}
+
+impl Hazard {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::Lava { .. } => "lava",
+            Self::Spikes { .. } => "spikes",
+            Self::Mud { .. } => "mud",
+        }
+    }
+}
+
+/// A rectangular zone that balls pass straight through, but that does something to them while
+/// they're touching it.
+#[derive(Clone)]
+pub struct HazardWall {
+    min: DVec2,
+    max: DVec2,
+    hazard: Hazard,
+    time: f64,
+}
+
+impl HazardWall {
+    pub fn new(min: DVec2, max: DVec2, hazard: Hazard) -> Self {
+        Self {
+            min: min.min(max),
+            max: min.max(max),
+            hazard,
+            time: 0.0,
+        }
+    }
+
+    pub fn get_min(&self) -> DVec2 {
+        self.min
+    }
+
+    pub fn get_max(&self) -> DVec2 {
+        self.max
+    }
+
+    pub fn get_size(&self) -> DVec2 {
+        self.max - self.min
+    }
+}
+
+impl Wall for HazardWall {
+    fn update(&self, dt: f64) -> Box<dyn Wall> {
+        let mut new_wall = self.clone();
+        new_wall.time += dt;
+
+        Box::new(new_wall)
+    }
+
+    fn get_intersection_point(&self, ball: &PhysicsBall) -> Option<DVec2> {
+        let closest_point = ball.get_position().clamp(self.min, self.max);
+
+        (closest_point.distance(ball.get_position()) < ball.get_radius()).then_some(closest_point)
+    }
+
+    fn is_goal(&self) -> bool {
+        false
+    }
+
+    fn get_hazard(&self) -> Option<Hazard> {
+        Some(self.hazard)
+    }
+}
+
+impl Render for HazardWall {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let size = self.get_size();
+
+        match self.hazard {
+            Hazard::Lava { .. } => {
+                let glow = 0.8 + 0.2 * (self.time * 3.0).sin() as f32;
+
+                renderer.render_rectangle(
+                    self.min,
+                    size.x,
+                    size.y,
+                    DVec2::ZERO,
+                    0.0,
+                    Srgba::new(0.9 * glow, 0.25 * glow, 0.05, 0.9),
+                );
+
+                let bubbles = (size.x / 24.0).floor().max(1.0) as usize;
+
+                for i in 0..bubbles {
+                    let phase = self.time * 1.5 + i as f64 * 1.7;
+                    let x = self.min.x + size.x * (i as f64 + 0.5) / bubbles as f64;
+                    let y = self.min.y + size.y * (0.5 + 0.3 * phase.sin());
+
+                    renderer.render_circle(
+                        dvec2(x, y),
+                        2.0 + phase.cos().abs() * 2.0,
+                        Srgba::new(1.0, 0.8, 0.2, 0.8),
+                    );
+                }
+            }
+            Hazard::Spikes { .. } => {
+                renderer.render_rectangle(
+                    self.min,
+                    size.x,
+                    size.y,
+                    DVec2::ZERO,
+                    0.0,
+                    Srgba::new(0.2, 0.2, 0.25, 0.9),
+                );
+
+                // A grid of spikes, so that strips along walls look right either way up
+                let columns = (size.x / 12.0).floor().max(1.0) as usize;
+                let rows = (size.y / 12.0).floor().max(1.0) as usize;
+                let spike_size = dvec2(size.x / columns as f64, size.y / rows as f64);
+                let color = Srgba::new(0.75, 0.75, 0.8, 1.0);
+
+                for row in 0..rows {
+                    for column in 0..columns {
+                        let left = self.min + spike_size * dvec2(column as f64, row as f64 + 1.0);
+                        let right = left + dvec2(spike_size.x, 0.0);
+                        let tip = left + dvec2(spike_size.x * 0.5, -spike_size.y);
+
+                        renderer.render_line(left, tip, 1.5, color);
+                        renderer.render_line(tip, right, 1.5, color);
+                    }
+                }
+            }
+            Hazard::Mud { .. } => {
+                renderer.render_rectangle(
+                    self.min,
+                    size.x,
+                    size.y,
+                    DVec2::ZERO,
+                    0.0,
+                    Srgba::new(0.4, 0.26, 0.13, 0.6),
+                );
+
+                let ripples = (size.x / 40.0).floor().max(1.0) as usize;
+
+                for i in 0..ripples {
+                    let x = self.min.x + size.x * (i as f64 + 0.5) / ripples as f64;
+                    let y = self.min.y + size.y * 0.5;
+
+                    renderer.render_circle_lines(
+                        dvec2(x, y),
+                        4.0 + 4.0 * (self.time + i as f64).sin().abs(),
+                        1.0,
+                        Srgba::new(0.55, 0.38, 0.2, 0.8),
+                    );
+                }
+            }
+        }
+    }
+}
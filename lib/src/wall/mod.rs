@@ -1,15 +1,72 @@
 use dyn_clone::DynClone;
 use glam::DVec2;
 
-use crate::{ball::PhysicsBall, rendering::Render};
+use crate::{
+    ball::PhysicsBall,
+    rendering::Render,
+    wall::{hazard_wall::Hazard, trigger_wall::Trigger},
+};
 
+pub mod bucket_wall;
 pub mod circle_wall;
+pub mod door_wall;
+pub mod gate_wall;
+pub mod hazard_wall;
 pub mod straight_wall;
+pub mod trapdoor_wall;
+pub mod trigger_wall;
 
 pub trait Wall: Render + Send + Sync + DynClone {
     fn update(&self, dt: f64) -> Box<dyn Wall>;
     fn get_intersection_point(&self, ball: &PhysicsBall) -> Option<DVec2>;
     fn is_goal(&self) -> bool;
+
+    /// Walls with a trigger don't collide with balls.
+    fn get_trigger(&self) -> Option<Trigger> {
+        None
+    }
+
+    /// Walls with a hazard don't collide with balls either, but do something to the balls touching
+    /// them.
+    fn get_hazard(&self) -> Option<Hazard> {
+        None
+    }
+
+    /// Whether balls bounce off this wall.
+    fn is_solid(&self) -> bool {
+        self.get_trigger().is_none() && self.get_hazard().is_none()
+    }
+
+    /// Whether this wall knocks a ball out of the race, either by the ball touching it or by the
+    /// ball leaving the area it encloses.
+    fn is_out_of_bounds(&self, _ball: &PhysicsBall) -> bool {
+        false
+    }
+
+    /// Walls with an id can be found by actions in a level's timeline.
+    fn get_id(&self) -> Option<&str> {
+        None
+    }
+
+    /// Opens or closes the wall, for walls that can be opened.
+    fn set_open(&mut self, _open: bool) {}
+
+    fn is_open(&self) -> bool {
+        false
+    }
+
+    /// How many more hits it takes to open this wall, for walls that open when balls hit them.
+    fn get_contacts_left(&self) -> Option<usize> {
+        None
+    }
+
+    /// Counts a ball hitting the wall, for walls that open when balls hit them.
+    fn add_contact(&mut self) {}
+
+    /// Points for landing on this goal in a scoring race.
+    fn get_score(&self) -> Option<u32> {
+        None
+    }
 }
 
 dyn_clone::clone_trait_object!(Wall);
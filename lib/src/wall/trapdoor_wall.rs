@@ -0,0 +1,131 @@
+use glam::DVec2;
+use palette::Srgba;
+use render_agnostic::Renderer;
+
+use crate::{ball::PhysicsBall, rendering::Render};
+
+use super::{
+    Wall,
+    straight_wall::{Line, StraightWall},
+};
+
+/// When a trapdoor opens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trapdoor {
+    /// After this many seconds.
+    Timer(f64),
+    /// After balls have hit it this many times.
+    Contacts(usize),
+}
+
+/// A solid line that drops open for good once its trapdoor condition is met, swinging down from
+/// its start point.
+#[derive(Clone)]
+pub struct TrapdoorWall {
+    line: Line,
+    trapdoor: Trapdoor,
+    time: f64,
+    contacts: usize,
+}
+
+impl TrapdoorWall {
+    pub fn new(line: Line, trapdoor: Trapdoor) -> Self {
+        Self {
+            line,
+            trapdoor,
+            time: 0.0,
+            contacts: 0,
+        }
+    }
+
+    pub fn get_line(&self) -> Line {
+        self.line
+    }
+
+    pub fn get_trapdoor(&self) -> Trapdoor {
+        self.trapdoor
+    }
+}
+
+impl Wall for TrapdoorWall {
+    fn update(&self, dt: f64) -> Box<dyn Wall> {
+        let mut new_wall = self.clone();
+        new_wall.time += dt;
+
+        Box::new(new_wall)
+    }
+
+    fn get_intersection_point(&self, ball: &PhysicsBall) -> Option<DVec2> {
+        if self.is_open() {
+            return None;
+        }
+
+        StraightWall::new(self.get_line(), false).get_intersection_point(ball)
+    }
+
+    fn is_goal(&self) -> bool {
+        false
+    }
+
+    fn is_open(&self) -> bool {
+        match self.trapdoor {
+            Trapdoor::Timer(seconds) => self.time >= seconds,
+            Trapdoor::Contacts(contacts) => self.contacts >= contacts,
+        }
+    }
+
+    fn get_contacts_left(&self) -> Option<usize> {
+        match self.trapdoor {
+            Trapdoor::Timer(_) => None,
+            Trapdoor::Contacts(contacts) => Some(contacts.saturating_sub(self.contacts)),
+        }
+    }
+
+    fn add_contact(&mut self) {
+        self.contacts += 1;
+    }
+}
+
+impl Render for TrapdoorWall {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let start = self.get_line().get_start();
+        let end = self.get_line().get_end();
+
+        if self.is_open() {
+            let hanging = start + DVec2::Y * start.distance(end);
+
+            renderer.render_line(start, hanging, 2.0, Srgba::new(0.6, 0.45, 0.25, 0.4));
+            return;
+        }
+
+        let wear = match self.trapdoor {
+            Trapdoor::Timer(seconds) => (self.time / seconds).clamp(0.0, 1.0),
+            Trapdoor::Contacts(contacts) => (self.contacts as f64 / contacts as f64).min(1.0),
+        } as f32;
+
+        renderer.render_line(
+            start,
+            end,
+            4.0,
+            Srgba::new(
+                0.6 + 0.4 * wear,
+                0.45 * (1.0 - wear),
+                0.25 * (1.0 - wear),
+                1.0,
+            ),
+        );
+        renderer.render_circle(start, 4.0, Srgba::new(0.3, 0.3, 0.3, 1.0));
+
+        if let Some(contacts_left) = self.get_contacts_left() {
+            renderer.render_text_outline(
+                &contacts_left.to_string(),
+                self.get_line().get_point(0.5) - DVec2::Y * 6.0,
+                anchor2d::CGB,
+                16.0,
+                1.0,
+                Srgba::new(1.0, 1.0, 1.0, 1.0),
+                Srgba::new(0.0, 0.0, 0.0, 1.0),
+            );
+        }
+    }
+}
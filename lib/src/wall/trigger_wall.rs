@@ -0,0 +1,72 @@
+use glam::DVec2;
+use palette::Srgba;
+use render_agnostic::Renderer;
+
+use crate::{ball::PhysicsBall, rendering::Render};
+
+use super::{
+    Wall,
+    straight_wall::{Line, StraightWall},
+};
+
+/// What happens when a ball touches a trigger wall.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trigger {
+    /// A timing gate. Checkpoint 0 is the start/finish line, and the rest have to be crossed in
+    /// order before it counts again.
+    Checkpoint(usize),
+    /// Moves the ball by this offset, keeping its velocity.
+    Teleport(DVec2),
+}
+
+/// A line that balls pass straight through, but that notices when they touch it.
+#[derive(Clone)]
+pub struct TriggerWall {
+    line: Line,
+    trigger: Trigger,
+}
+
+impl TriggerWall {
+    pub fn new(line: Line, trigger: Trigger) -> Self {
+        Self { line, trigger }
+    }
+
+    pub fn get_line(&self) -> Line {
+        self.line
+    }
+}
+
+impl Wall for TriggerWall {
+    fn update(&self, _dt: f64) -> Box<dyn Wall> {
+        Box::new(self.clone())
+    }
+
+    fn get_intersection_point(&self, ball: &PhysicsBall) -> Option<DVec2> {
+        StraightWall::new(self.get_line(), false).get_intersection_point(ball)
+    }
+
+    fn is_goal(&self) -> bool {
+        false
+    }
+
+    fn get_trigger(&self) -> Option<Trigger> {
+        Some(self.trigger)
+    }
+}
+
+impl Render for TriggerWall {
+    fn render(&self, renderer: &mut dyn Renderer) {
+        let color = match self.trigger {
+            Trigger::Checkpoint(0) => Srgba::new(1.0, 1.0, 1.0, 0.5),
+            Trigger::Checkpoint(_) => Srgba::new(1.0, 0.85, 0.2, 0.35),
+            Trigger::Teleport(_) => Srgba::new(0.6, 0.3, 1.0, 0.6),
+        };
+
+        renderer.render_line(
+            self.get_line().get_start(),
+            self.get_line().get_end(),
+            2.0,
+            color,
+        );
+    }
+}
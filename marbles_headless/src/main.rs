@@ -1,4 +1,5 @@
 use std::{
+    any::Any,
     collections::HashMap,
     env,
     fmt::Display,
@@ -10,9 +11,12 @@ use std::{
 
 use ab_glyph::FontArc;
 use api::marble::Marble;
-use chrono::{Local, TimeDelta};
+use chrono::{Local, TimeDelta, Utc};
 use clap::{Parser, ValueEnum};
-use database::{marble::DbMarble, race::DbRace};
+use database::{
+    league::DbLeague, marble::DbMarble, race::DbRace, season::DbSeason, team::DbTeam,
+    tournament::DbTournament,
+};
 use dotenvy::dotenv;
 use glam::{DVec2, dvec2};
 use image::ImageReader;
@@ -20,14 +24,25 @@ use keyframe::{AnimationSequence, functions::EaseOutQuart, keyframes};
 use lib::{
     Config,
     collision::{Collision, render_collisions},
+    elimination::Elimination,
     engagement::get_engagement_for_scene,
     graphic::{
-        Graphic, countdown::Countdown, engagement::Engagement, marble_stat::MarbleStat,
-        special_message::SpecialMessage,
+        Graphic, bracket::BracketBanner, commentary::Commentary, countdown::Countdown,
+        engagement::Engagement, lap_counter::LapCounter, marble_stat::MarbleStat,
+        season_rank::SeasonRank, special_message::SpecialMessage, standings::Standings,
+        team_scoreboard::TeamScoreboard,
     },
+    handicap::get_handicaps,
     posting::{cloudinary::Cloudinary, instagram::InstagramPoster},
+    promotion::settle_seasons,
+    race_context::RaceContext,
+    rating::{backfill_ratings, update_ratings_for_race},
     rendering::Render,
     simulation::Simulation,
+    solver::Solver,
+    start::Start,
+    team::{Team, TeamScoring, Teams},
+    tournament::{create_tournament, get_champion, next_heat},
     util::{
         MaybeMessage, Message, get_formatted_frame_name, get_frame_template, get_scene,
         render_video, upload_to_instagram, upload_to_youtube,
@@ -110,6 +125,67 @@ pub struct Cli {
     #[arg(short, long, value_enum)]
     league: Option<League>,
 
+    /// This is the last race of the league's current season
+    #[arg(long, requires = "league")]
+    finale: bool,
+
+    /// Run the next heats of this tournament instead of open races, starting it from the league's
+    /// marbles if it doesn't exist yet
+    #[arg(long, requires_all = ["league", "stats"])]
+    tournament: Option<String>,
+
+    /// Knock out the last-place marble this often, in seconds, until one is left
+    #[arg(long)]
+    elimination_seconds: Option<f64>,
+
+    /// Knock out the last-place marble each time the leader gets this far through the level, from
+    /// 0 at the start to 1 at the goal
+    #[arg(long, value_delimiter = ',', conflicts_with = "elimination_seconds")]
+    elimination_checkpoints: Vec<f64>,
+
+    /// Race these teams against each other, e.g. `--teams Blues,Reds`
+    #[arg(long, value_delimiter = ',', conflicts_with = "tournament")]
+    teams: Vec<String>,
+
+    /// How many marbles race for each team
+    #[arg(long, default_value_t = 3)]
+    team_size: usize,
+
+    /// The first team to get this many marbles to the finish wins, instead of the lowest average
+    /// place
+    #[arg(long)]
+    first_to: Option<usize>,
+
+    /// Hold the marbles on a gate during the countdown that opens at "Go!"
+    #[arg(long)]
+    start_gate: bool,
+
+    /// Fire every marble downwards at this speed when it's released, in pixels per second
+    #[arg(long)]
+    launch_speed: Option<f64>,
+
+    /// Release the marbles one at a time from left to right, this many seconds apart
+    #[arg(long)]
+    stagger_seconds: Option<f64>,
+
+    /// Solve contacts with sequential impulses instead of reflections, so piles of marbles settle
+    /// down instead of jittering, even on levels that don't use it already
+    #[arg(long)]
+    impulse_solver: bool,
+
+    /// Handicap marbles that have been winning a lot lately and boost ones that haven't, as set up
+    /// in the config
+    #[arg(long)]
+    handicaps: bool,
+
+    /// Most marbles in one heat of a new tournament, at least 2
+    #[arg(long, default_value_t = 6, value_parser = parse_heat_size)]
+    heat_size: usize,
+
+    /// How many marbles go through from each heat of a new tournament
+    #[arg(long, default_value_t = 2)]
+    advance: usize,
+
     #[arg(long)]
     instagram_caption: Option<String>,
 
@@ -124,10 +200,34 @@ pub struct Cli {
 
     #[arg(long)]
     special_message: Option<String>,
+
+    #[arg(long, default_value = "default")]
+    profile: String,
+
+    /// Recompute every marble's rating from the recorded races, then exit
+    #[arg(long)]
+    backfill_ratings: bool,
+
+    /// Promote and relegate marbles between leagues whose seasons have ended, then exit
+    #[arg(long)]
+    settle_seasons: bool,
 }
 
 const FRAME_PADDING: usize = 6;
 
+/// A heat needs two marbles to be a race, and one to go through.
+fn parse_heat_size(heat_size: &str) -> Result<usize, String> {
+    let heat_size = heat_size
+        .parse::<usize>()
+        .map_err(|error| error.to_string())?;
+
+    if heat_size < 2 {
+        return Err(String::from("a heat needs at least 2 marbles"));
+    }
+
+    Ok(heat_size)
+}
+
 #[tokio::main]
 async fn main() {
     dotenv().unwrap();
@@ -155,6 +255,73 @@ async fn main() {
 
     let config_string = std::fs::read_to_string("config.toml").unwrap();
     let config = from_str::<Config>(&config_string).unwrap();
+    let profile = config.get_profile(&cli.profile);
+
+    if cli.backfill_ratings {
+        let races = backfill_ratings(&pool)
+            .await
+            .expect("Could not backfill ratings");
+
+        info!("Backfilled ratings from {} races", races);
+
+        return;
+    }
+
+    if cli.settle_seasons {
+        let moves = settle_seasons(&pool, config.get_season_config(), Utc::now())
+            .await
+            .expect("Could not settle seasons");
+
+        for league_move in moves.iter() {
+            if league_move.is_promotion() {
+                info!(
+                    league_move.marble_id,
+                    "Promoted to the {}", league_move.to_league_name
+                );
+            } else {
+                info!(
+                    league_move.marble_id,
+                    "Relegated to the {}", league_move.to_league_name
+                );
+            }
+        }
+
+        info!("Settled seasons with {} moves", moves.len());
+
+        return;
+    }
+
+    let maybe_league = if let Some(league) = cli.league {
+        Some(
+            DbLeague::get_by_name(&pool, &league.to_string())
+                .await
+                .unwrap()
+                .expect("League not found"),
+        )
+    } else {
+        None
+    };
+
+    let maybe_league_id = maybe_league.as_ref().map(|league| league.id);
+
+    let maybe_tournament = if let Some(name) = &cli.tournament {
+        match DbTournament::get_by_name(&pool, name).await.unwrap() {
+            Some(tournament) => Some(tournament),
+            None => Some(
+                create_tournament(
+                    &pool,
+                    maybe_league.as_ref().unwrap(),
+                    name,
+                    cli.heat_size,
+                    cli.advance,
+                )
+                .await
+                .expect("Could not create tournament"),
+            ),
+        }
+    } else {
+        None
+    };
 
     let marbles = if let Some(league) = cli.league {
         DbMarble::get_by_league(&pool, &league.to_string()).await
@@ -166,24 +333,162 @@ async fn main() {
     .map(|db_marble| db_marble.into())
     .collect::<Vec<Marble>>();
 
+    let maybe_teams = if cli.teams.is_empty() {
+        None
+    } else {
+        let mut teams = Vec::new();
+
+        for name in cli.teams.iter() {
+            let db_team = DbTeam::get_by_name(&pool, name)
+                .await
+                .unwrap()
+                .expect("No team with that name");
+
+            let team_marbles = db_team
+                .get_marbles(&pool)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|db_marble| db_marble.into())
+                .collect::<Vec<Marble>>();
+
+            teams.push(Team::new(
+                db_team.name.clone(),
+                db_team.get_color(),
+                team_marbles,
+            ));
+        }
+
+        let scoring = match cli.first_to {
+            Some(finishers) => TeamScoring::FirstTo(finishers),
+            None => TeamScoring::AveragePlace,
+        };
+
+        Some(Teams::new(teams, scoring))
+    };
+
     for _ in 0..cli.renders {
         let now = Local::now();
 
+        let mut maybe_heat = if let Some(tournament) = &maybe_tournament {
+            match next_heat(&pool, tournament).await.unwrap() {
+                Some(heat) => Some(heat),
+                None => {
+                    info!("{} is over", tournament.name);
+                    break;
+                }
+            }
+        } else {
+            None
+        };
+
+        let race_marbles = if let Some((heat, _)) = &maybe_heat {
+            heat.get_marbles(&pool)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|db_marble| db_marble.into())
+                .collect::<Vec<Marble>>()
+        } else if let Some(teams) = &maybe_teams {
+            teams.pick_marbles(&mut rng, cli.team_size)
+        } else {
+            marbles.clone()
+        };
+
         let render_path = renders_path.join(now.format("%Y-%m-%d-%H-%M-%S").to_string());
         fs::create_dir_all(&render_path).unwrap();
 
         let frames_path = render_path.join("frames/");
         fs::create_dir_all(&frames_path).unwrap();
 
-        let scene = get_scene(
+        let mut scene = get_scene(
             &mut rng,
             config.get_scene(),
-            &marbles,
+            &race_marbles,
+            maybe_teams.as_ref(),
             WIDTH as f64,
             HEIGHT as f64,
         );
         let mut collisions: HashMap<usize, Vec<Collision>> = HashMap::new();
-        let engagement = get_engagement_for_scene(&pool, &mut rng, &scene)
+
+        if let Some(seconds) = cli.elimination_seconds {
+            scene.set_elimination(Some(Elimination::Interval(seconds)));
+        } else if !cli.elimination_checkpoints.is_empty() {
+            scene.set_elimination(Some(Elimination::Checkpoints(
+                cli.elimination_checkpoints.clone(),
+            )));
+        }
+
+        if cli.impulse_solver {
+            scene.set_solver(Solver::Impulse);
+        }
+
+        if cli.start_gate || cli.launch_speed.is_some() || cli.stagger_seconds.is_some() {
+            scene.set_start(Some(Start {
+                gate: cli.start_gate,
+                launch_velocity: dvec2(0.0, cli.launch_speed.unwrap_or_default()),
+                stagger_seconds: cli.stagger_seconds.unwrap_or_default(),
+            }));
+        }
+
+        if let Some(powerup_pool) = config.get_powerup_pool(scene.get_level_id()) {
+            scene.set_powerup_pool(powerup_pool.clone());
+        }
+
+        let mut handicaps = if cli.handicaps {
+            get_handicaps(&pool, config.get_handicap_config(), &race_marbles)
+                .await
+                .unwrap()
+        } else {
+            HashMap::new()
+        };
+
+        handicaps.retain(|name, _| scene.get_balls().iter().any(|ball| ball.get_name() == name));
+
+        for ball in scene.get_balls_mut() {
+            if let Some(handicap) = handicaps.get(ball.get_name()) {
+                info!(marble = ball.get_name(), "{}", handicap);
+                handicap.apply(ball);
+            }
+        }
+
+        let maybe_season = if let Some(league_id) = maybe_league_id {
+            match DbSeason::get_current(&pool, league_id, now.to_utc())
+                .await
+                .unwrap()
+            {
+                Some(season) => Some(season),
+                None if cli.stats => {
+                    let season_config = config.get_season_config();
+
+                    Some(
+                        DbSeason::insert(
+                            &pool,
+                            league_id,
+                            &format!("{} {}", cli.league.unwrap(), now.format("%B %Y")),
+                            now.to_utc(),
+                            now.to_utc() + TimeDelta::days(season_config.days),
+                            &season_config.points,
+                        )
+                        .await
+                        .expect("Could not start a new season"),
+                    )
+                }
+                None => None,
+            }
+        } else {
+            None
+        };
+
+        let context = RaceContext {
+            maybe_league_id,
+            maybe_season,
+            finale: cli.finale,
+            maybe_bracket: maybe_heat.as_ref().map(|(_, bracket)| bracket.clone()),
+            maybe_elimination: scene.get_elimination().cloned(),
+        };
+
+        let engagement = get_engagement_for_scene(&pool, &mut rng, &scene, &context)
             .await
             .unwrap();
 
@@ -278,6 +583,79 @@ async fn main() {
             )),
         ];
 
+        if let Some(season) = &context.maybe_season {
+            graphics.push(Box::new(
+                SeasonRank::new(
+                    &pool,
+                    keyframes![(
+                        Vector2::from(dvec2(viewport.0 * 0.5, viewport.1 * 0.8)),
+                        0.0
+                    )],
+                    cli.countdown_seconds as f64 - 2.0,
+                    2.0,
+                    season,
+                    &scene
+                        .get_balls()
+                        .iter()
+                        .map(|ball| ball.get_name().to_string())
+                        .collect::<Vec<String>>(),
+                    viewport,
+                )
+                .await
+                .unwrap(),
+            ));
+        }
+
+        if let Some(bracket) = &context.maybe_bracket {
+            graphics.push(Box::new(BracketBanner::new(
+                keyframes![(
+                    Vector2::from(dvec2(viewport.0 * 0.5, viewport.1 * 0.06)),
+                    0.0
+                )],
+                0.0,
+                bracket.clone(),
+                viewport,
+            )));
+        }
+
+        if let Some(laps) = scene.get_laps() {
+            graphics.push(Box::new(LapCounter::new(
+                keyframes![(Vector2::from(dvec2(viewport.0 - 8.0, 40.0)), 0.0)],
+                cli.countdown_seconds as f64,
+                laps,
+                viewport,
+            )));
+        }
+
+        if scene.get_teams().is_some() {
+            graphics.push(Box::new(TeamScoreboard::new(
+                keyframes![(Vector2::from(dvec2(8.0, 40.0)), 0.0)],
+                cli.countdown_seconds as f64,
+                viewport,
+            )));
+        }
+
+        if profile.standings {
+            graphics.push(Box::new(Standings::new(
+                keyframes![(Vector2::from(dvec2(8.0, viewport.1 * 0.5)), 0.0)],
+                cli.countdown_seconds as f64,
+                profile.standings_count,
+                viewport,
+            )));
+        }
+
+        if profile.commentary {
+            graphics.push(Box::new(Commentary::new(
+                keyframes![(
+                    Vector2::from(dvec2(viewport.0 * 0.5, viewport.1 * 0.9)),
+                    0.0
+                )],
+                cli.countdown_seconds as f64,
+                profile.commentary_lines,
+                viewport,
+            )));
+        }
+
         let distance_from_top = 0.15;
         let vertical_spacing = 80.0;
         let temporal_spacing = 0.125;
@@ -320,6 +698,8 @@ async fn main() {
                         )
                     ],
                     ball.get_name().to_owned(),
+                    maybe_league_id,
+                    handicaps.get(ball.get_name()).copied(),
                     viewport,
                 )
                 .await,
@@ -347,22 +727,26 @@ async fn main() {
             simulation_states.push(simulation.clone());
 
             if simulation.is_finished() {
+                if let (Some(teams), Some(winning_team)) = (
+                    simulation.get_scene().get_teams(),
+                    simulation.get_scene().get_winning_team(),
+                ) {
+                    info!("{} win", teams.get_teams()[winning_team].name);
+                }
+
                 if cli.stats {
                     let race = DbRace::insert(
                         &pool,
                         now.timestamp(),
                         simulation.get_scene().get_level_id(),
+                        maybe_league_id,
                         now.to_utc(),
                     )
                     .await
                     .expect("Could not insert race into database");
 
-                    for (index, (winner_index, win_time)) in simulation
-                        .get_scene()
-                        .get_winners()
-                        .iter()
-                        .zip(simulation.get_scene().get_win_times())
-                        .enumerate()
+                    for (index, (winner_index, win_time)) in
+                        simulation.get_scene().get_placements().iter().enumerate()
                     {
                         let winner = simulation
                             .get_scene()
@@ -384,6 +768,88 @@ async fn main() {
                             .expect("Could not insert race participant into database");
                         }
                     }
+
+                    let mut split_marbles = Vec::new();
+
+                    for (ball, lap_tracker) in simulation
+                        .get_scene()
+                        .get_balls()
+                        .iter()
+                        .zip(simulation.get_scene().get_lap_trackers())
+                    {
+                        // Copies of a split marble that were still racing share its splits
+                        if split_marbles.contains(&ball.get_name()) {
+                            continue;
+                        }
+
+                        split_marbles.push(ball.get_name());
+
+                        if let Some(marble) =
+                            DbMarble::get_by_name(&pool, ball.get_name()).await.unwrap()
+                        {
+                            for split in lap_tracker.get_splits() {
+                                race.insert_split(
+                                    &pool,
+                                    marble.id,
+                                    split.lap as i64,
+                                    split.checkpoint as i64,
+                                    TimeDelta::from_std(split.time).unwrap(),
+                                )
+                                .await
+                                .expect("Could not insert split into database");
+                            }
+                        }
+                    }
+
+                    for (name, handicap) in handicaps.iter() {
+                        if let Some(marble) = DbMarble::get_by_name(&pool, name).await.unwrap() {
+                            race.insert_handicap(
+                                &pool,
+                                marble.id,
+                                handicap.radius,
+                                handicap.density,
+                                handicap.start_delay,
+                            )
+                            .await
+                            .expect("Could not insert handicap into database");
+                        }
+                    }
+
+                    for chosen_powerup in simulation.get_scene().get_chosen_powerups() {
+                        race.insert_powerup(
+                            &pool,
+                            &chosen_powerup.kind.to_string(),
+                            chosen_powerup
+                                .maybe_rarity
+                                .map(|rarity| rarity.to_string())
+                                .as_deref(),
+                            chosen_powerup.position.x,
+                            chosen_powerup.position.y,
+                            chosen_powerup.time,
+                        )
+                        .await
+                        .expect("Could not insert powerup into database");
+                    }
+
+                    update_ratings_for_race(&pool, &race)
+                        .await
+                        .expect("Could not update ratings");
+
+                    if let (Some(tournament), Some((mut heat, bracket))) =
+                        (&maybe_tournament, maybe_heat.take())
+                    {
+                        heat.set_race(&pool, &race)
+                            .await
+                            .expect("Could not record heat result");
+
+                        info!("{} finished", bracket.stage());
+
+                        if bracket.is_final()
+                            && let Some(champion) = get_champion(&pool, tournament).await.unwrap()
+                        {
+                            info!(champion, "{} has a champion", tournament.name);
+                        }
+                    }
                 }
 
                 break;
@@ -493,6 +959,16 @@ async fn main() {
 
         let video_path = render_path.join(video_name);
 
+        let subtitles_path = video_path.with_extension("srt");
+
+        if let Some(commentary) = simulation
+            .get_graphics()
+            .iter()
+            .find_map(|graphic| (graphic.as_ref() as &dyn Any).downcast_ref::<Commentary>())
+        {
+            fs::write(&subtitles_path, commentary.to_srt()).expect("Could not write subtitles");
+        }
+
         info!("Rendering video...");
         let status = render_video(
             &video_path,
@@ -559,6 +1035,10 @@ async fn main() {
             fs::remove_file(video_path).expect("Could not delete video");
         }
 
+        if !cli.keep_video && subtitles_path.exists() {
+            fs::remove_file(subtitles_path).expect("Could not delete subtitles");
+        }
+
         if !cli.keep_audio && !cli.keep_frames && !cli.keep_video {
             fs::remove_dir_all(render_path).expect("Could not delete render directory");
         }
@@ -208,6 +208,7 @@ async fn main() {
             &mut ::rand::rng(),
             config.get_scene(),
             &marbles,
+            None,
             screen_width() as f64,
             screen_height() as f64,
         );
@@ -0,0 +1,3 @@
+ALTER TABLE marble DROP COLUMN possessive_pronoun;
+ALTER TABLE marble DROP COLUMN object_pronoun;
+ALTER TABLE marble DROP COLUMN subject_pronoun;
@@ -0,0 +1,11 @@
+ALTER TABLE marble ADD COLUMN subject_pronoun TEXT NOT NULL DEFAULT "he";
+ALTER TABLE marble ADD COLUMN object_pronoun TEXT NOT NULL DEFAULT "him";
+ALTER TABLE marble ADD COLUMN possessive_pronoun TEXT NOT NULL DEFAULT "his";
+
+UPDATE marble
+SET subject_pronoun = "she", object_pronoun = "her", possessive_pronoun = "her"
+WHERE name IN ("Blue's Wife", "Black Hole", "Molly", "Perl");
+
+UPDATE marble
+SET subject_pronoun = "it", object_pronoun = "it", possessive_pronoun = "its"
+WHERE name IN ("IKEA", "Beebo", "Canada", "Creepy", "Estonia", "Amogus");
@@ -0,0 +1,26 @@
+CREATE TABLE rivalry (
+    marble_id INTEGER NOT NULL,
+    rival_id INTEGER NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ("opposites", "grudge")),
+    PRIMARY KEY (marble_id, rival_id),
+    FOREIGN KEY (marble_id) REFERENCES marble (id),
+    FOREIGN KEY (rival_id) REFERENCES marble (id)
+);
+
+WITH pair (marble_name, rival_name) AS (
+    VALUES
+    ("White Light", "Black Hole"),
+    ("Fireball", "Deep Blue"),
+    ("Mastermind", "Jokester"),
+    ("Giftbringer", "Psycho"),
+    ("Blue's Wife", "White's Brother"),
+    ("Trump Card", "Joe Mama"),
+    ("Homelander", "The Butcher")
+)
+INSERT INTO rivalry
+SELECT marble.id, rival.id, "opposites"
+FROM pair
+INNER JOIN marble
+ON marble.name = pair.marble_name
+INNER JOIN marble AS rival
+ON rival.name = pair.rival_name;
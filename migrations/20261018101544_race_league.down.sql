@@ -0,0 +1,3 @@
+UPDATE league SET name = "Fan Leage" WHERE name = "Fan League";
+
+ALTER TABLE race DROP COLUMN league_id;
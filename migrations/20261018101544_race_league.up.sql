@@ -0,0 +1,3 @@
+ALTER TABLE race ADD COLUMN league_id INTEGER REFERENCES league (id);
+
+UPDATE league SET name = "Fan League" WHERE name = "Fan Leage";
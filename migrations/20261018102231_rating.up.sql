@@ -0,0 +1,13 @@
+CREATE TABLE rating (
+    id INTEGER NOT NULL PRIMARY KEY,
+    marble_id INTEGER NOT NULL,
+    league_id INTEGER,
+    rating REAL NOT NULL,
+    deviation REAL NOT NULL,
+    volatility REAL NOT NULL,
+    races INTEGER NOT NULL,
+    FOREIGN KEY (marble_id) REFERENCES marble (id),
+    FOREIGN KEY (league_id) REFERENCES league (id)
+);
+
+CREATE UNIQUE INDEX rating_marble_league ON rating (marble_id, IFNULL(league_id, 0));
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS season_points;
+DROP TABLE IF EXISTS season;
@@ -0,0 +1,16 @@
+CREATE TABLE season (
+    id INTEGER NOT NULL PRIMARY KEY,
+    league_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    start_time INTEGER NOT NULL,
+    end_time INTEGER NOT NULL,
+    FOREIGN KEY (league_id) REFERENCES league (id)
+);
+
+CREATE TABLE season_points (
+    season_id INTEGER NOT NULL,
+    place INTEGER NOT NULL,
+    points INTEGER NOT NULL,
+    PRIMARY KEY (season_id, place),
+    FOREIGN KEY (season_id) REFERENCES season (id)
+);
@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS heat_marble;
+DROP TABLE IF EXISTS heat;
+DROP TABLE IF EXISTS tournament_entry;
+DROP TABLE IF EXISTS tournament;
@@ -0,0 +1,36 @@
+CREATE TABLE tournament (
+    id INTEGER NOT NULL PRIMARY KEY,
+    league_id INTEGER NOT NULL,
+    name TEXT NOT NULL UNIQUE,
+    heat_size INTEGER NOT NULL,
+    advance INTEGER NOT NULL,
+    FOREIGN KEY (league_id) REFERENCES league (id)
+);
+
+CREATE TABLE tournament_entry (
+    tournament_id INTEGER NOT NULL,
+    marble_id INTEGER NOT NULL,
+    seed INTEGER NOT NULL,
+    PRIMARY KEY (tournament_id, marble_id),
+    FOREIGN KEY (tournament_id) REFERENCES tournament (id),
+    FOREIGN KEY (marble_id) REFERENCES marble (id)
+);
+
+CREATE TABLE heat (
+    id INTEGER NOT NULL PRIMARY KEY,
+    tournament_id INTEGER NOT NULL,
+    round INTEGER NOT NULL,
+    number INTEGER NOT NULL,
+    race_id INTEGER,
+    UNIQUE (tournament_id, round, number),
+    FOREIGN KEY (tournament_id) REFERENCES tournament (id),
+    FOREIGN KEY (race_id) REFERENCES race (id)
+);
+
+CREATE TABLE heat_marble (
+    heat_id INTEGER NOT NULL,
+    marble_id INTEGER NOT NULL,
+    PRIMARY KEY (heat_id, marble_id),
+    FOREIGN KEY (heat_id) REFERENCES heat (id),
+    FOREIGN KEY (marble_id) REFERENCES marble (id)
+);
@@ -0,0 +1,7 @@
+ALTER TABLE season DROP COLUMN settled;
+
+DELETE FROM league_marble WHERE left_time IS NOT NULL;
+ALTER TABLE league_marble DROP COLUMN left_time;
+ALTER TABLE league_marble DROP COLUMN joined_time;
+
+ALTER TABLE league DROP COLUMN tier;
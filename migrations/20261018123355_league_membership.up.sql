@@ -0,0 +1,10 @@
+ALTER TABLE league ADD COLUMN tier INTEGER NOT NULL DEFAULT 0;
+
+UPDATE league SET tier = 1 WHERE name = "Major League";
+UPDATE league SET tier = 2 WHERE name = "Minor League";
+UPDATE league SET tier = 3 WHERE name = "Fan League";
+
+ALTER TABLE league_marble ADD COLUMN joined_time INTEGER;
+ALTER TABLE league_marble ADD COLUMN left_time INTEGER;
+
+ALTER TABLE season ADD COLUMN settled INTEGER NOT NULL DEFAULT FALSE;
@@ -0,0 +1,10 @@
+CREATE TABLE race_split (
+    race_id INTEGER NOT NULL,
+    marble_id INTEGER NOT NULL,
+    lap INTEGER NOT NULL,
+    checkpoint INTEGER NOT NULL,
+    time REAL NOT NULL,
+    PRIMARY KEY (race_id, marble_id, lap, checkpoint),
+    FOREIGN KEY (race_id) REFERENCES race (id),
+    FOREIGN KEY (marble_id) REFERENCES marble (id)
+);
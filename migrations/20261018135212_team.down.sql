@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS team_marble;
+DROP TABLE IF EXISTS team;
@@ -0,0 +1,38 @@
+CREATE TABLE team (
+    id INTEGER NOT NULL PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    red REAL NOT NULL,
+    green REAL NOT NULL,
+    blue REAL NOT NULL
+);
+
+CREATE TABLE team_marble (
+    team_id INTEGER NOT NULL,
+    marble_id INTEGER NOT NULL,
+    PRIMARY KEY (team_id, marble_id),
+    FOREIGN KEY (team_id) REFERENCES team (id),
+    FOREIGN KEY (marble_id) REFERENCES marble (id)
+);
+
+INSERT INTO team
+(name, red, green, blue)
+VALUES
+("Blues", 0.0, 0.5, 1.0),
+("Reds", 1.0, 0.1, 0.1);
+
+WITH member (team_name, marble_name) AS (
+    VALUES
+    ("Blues", "Deep Blue"),
+    ("Blues", "Blue's Wife"),
+    ("Blues", "Joe Mama"),
+    ("Reds", "Fireball"),
+    ("Reds", "Trump Card"),
+    ("Reds", "Apollo")
+)
+INSERT INTO team_marble
+SELECT team.id, marble.id
+FROM member
+INNER JOIN team
+ON team.name = member.team_name
+INNER JOIN marble
+ON marble.name = member.marble_name;
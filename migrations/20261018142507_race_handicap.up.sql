@@ -0,0 +1,10 @@
+CREATE TABLE race_handicap (
+    race_id INTEGER NOT NULL,
+    marble_id INTEGER NOT NULL,
+    radius REAL NOT NULL,
+    density REAL NOT NULL,
+    start_delay REAL NOT NULL,
+    PRIMARY KEY (race_id, marble_id),
+    FOREIGN KEY (race_id) REFERENCES race (id),
+    FOREIGN KEY (marble_id) REFERENCES marble (id)
+);
@@ -0,0 +1,9 @@
+CREATE TABLE race_powerup (
+    race_id INTEGER NOT NULL,
+    kind TEXT NOT NULL,
+    rarity TEXT,
+    x REAL NOT NULL,
+    y REAL NOT NULL,
+    time REAL NOT NULL,
+    FOREIGN KEY (race_id) REFERENCES race (id)
+);
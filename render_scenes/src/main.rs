@@ -58,7 +58,7 @@ async fn main() {
         })
         .collect::<HashMap<_, _>>();
 
-    for (scene_index, scene) in get_scenes(&mut rand::rng(), &marbles, WIDTH as f64, HEIGHT as f64)
+    for (scene_index, scene) in get_scenes(&mut rand::rng(), &marbles, None, WIDTH as f64, HEIGHT as f64)
         .into_iter()
         .enumerate()
     {